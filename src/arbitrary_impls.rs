@@ -0,0 +1,57 @@
+use arbitrary::{self, Arbitrary, Unstructured};
+use StridedBuf;
+
+// The layouts generated are kept reasonably small, so that fuzzers
+// spend their input on the elements rather than on padding.
+const MAX_STRIDE: usize = 16;
+const MAX_OFFSET: usize = 16;
+
+/// Generates a buffer with a random offset, stride (between 1 and 16)
+/// and length, with random padding after the last element.
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for StridedBuf<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<StridedBuf<T>> {
+        let stride = u.int_in_range(1..=MAX_STRIDE)?;
+        let offset = u.int_in_range(0..=MAX_OFFSET)?;
+        let padding = u.int_in_range(0..=stride - 1)?;
+        let len = u.arbitrary_len::<T>()? / stride;
+        let total = match len {
+            0 => offset + padding,
+            _ => offset + (len - 1) * stride + 1 + padding,
+        };
+        let data = (0..total).map(|_| T::arbitrary(u)).collect::<arbitrary::Result<Vec<T>>>()?;
+        StridedBuf::new(data, offset, stride, len).ok_or(arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let (n, _) = usize::size_hint(depth);
+        (3 * n, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use StridedBuf;
+
+    #[test]
+    fn arbitrary() {
+        let bytes = (0..1024u32).map(|i| (i * 7919 % 251) as u8).collect::<Vec<_>>();
+        let mut seen_stride = false;
+        for start in 0..64 {
+            let mut u = Unstructured::new(&bytes[start * 4..]);
+            let b = StridedBuf::<u16>::arbitrary(&mut u).unwrap();
+            let s = b.as_stride();
+            assert_eq!(s.len(), b.len());
+            assert_eq!(s.stride(), b.stride());
+            assert!(b.stride() >= 1 && b.stride() <= 16);
+            for (i, x) in s.iter().enumerate() {
+                assert_eq!(*x, b.data()[b.offset() + i * b.stride()]);
+            }
+            seen_stride |= b.stride() > 1 && b.len() > 1;
+        }
+        assert!(seen_stride);
+
+        let mut u = Unstructured::new(&[]);
+        assert!(StridedBuf::<u16>::arbitrary(&mut u).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+//! Per-lane views over Apache Arrow arrays of interleaved records.
+//!
+//! A `FixedSizeListArray` of `n` primitives (such as a column of
+//! `[f32; 3]` positions) stores its records back to back in a single
+//! child array, so each lane is a strided view with stride `n`. These
+//! functions return the `n` lanes, in order, without copying.
+//!
+//! The views include the values of null slots, which Arrow leaves
+//! arbitrary; check the array's null buffer where that matters.
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::ArrowPrimitiveType;
+use arrow_array::{Array, FixedSizeListArray, PrimitiveArray};
+use {Stride, Substrides};
+
+/// Returns the lanes of `a`, the `i`th of which views element `i` of
+/// each list.
+///
+/// Returns `None` if the child values of `a` are not a
+/// `PrimitiveArray<T>`, or if the lists are empty.
+///
+/// ```rust
+/// # extern crate arrow_array;
+/// # extern crate strided;
+/// # fn main() {
+/// use arrow_array::FixedSizeListArray;
+/// use arrow_array::types::Float32Type;
+///
+/// let points = FixedSizeListArray::from_iter_primitive::<Float32Type, _, _>(
+///     vec![Some(vec![Some(1.0), Some(2.0)]), Some(vec![Some(3.0), Some(4.0)])], 2);
+/// let mut lanes = strided::arrow::fixed_size_list_lanes::<Float32Type>(&points).unwrap();
+/// assert_eq!(lanes.next().unwrap(), [1.0, 3.0]);
+/// assert_eq!(lanes.next().unwrap(), [2.0, 4.0]);
+/// # }
+/// ```
+pub fn fixed_size_list_lanes<T: ArrowPrimitiveType>(a: &FixedSizeListArray)
+                                                    -> Option<Substrides<'_, T::Native>> {
+    let n = a.value_length() as usize;
+    if n == 0 { return None }
+    let values = a.values().as_primitive_opt::<T>()?.values();
+    values.get(..a.len() * n).map(|v| Stride::new(v).substrides(n))
+}
+
+/// Returns the `n` lanes of `a`, whose values are taken to be records
+/// of `n` interleaved fields.
+///
+/// # Panic
+///
+/// Panics if `n` is zero.
+#[track_caller]
+pub fn interleaved_lanes<T: ArrowPrimitiveType>(a: &PrimitiveArray<T>, n: usize)
+                                                -> Substrides<'_, T::Native> {
+    Stride::new(a.values()).substrides(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::types::{Int16Type, Int32Type};
+    use arrow_array::{FixedSizeListArray, Int32Array};
+    use super::{fixed_size_list_lanes, interleaved_lanes};
+
+    #[test]
+    fn lanes() {
+        let lists = FixedSizeListArray::from_iter_primitive::<Int32Type, _, _>(
+            (0..4).map(|i| Some((0..3).map(|j| Some(10 * i + j)).collect::<Vec<_>>())), 3);
+        let lanes = fixed_size_list_lanes::<Int32Type>(&lists).unwrap().collect::<Vec<_>>();
+        assert_eq!(lanes, [[0, 10, 20, 30], [1, 11, 21, 31], [2, 12, 22, 32]]);
+
+        let sliced = lists.slice(1, 2);
+        let lanes = fixed_size_list_lanes::<Int32Type>(&sliced).unwrap().collect::<Vec<_>>();
+        assert_eq!(lanes, [[10, 20], [11, 21], [12, 22]]);
+
+        assert!(fixed_size_list_lanes::<Int16Type>(&lists).is_none());
+        let empty = FixedSizeListArray::from_iter_primitive::<Int32Type, _, _>(
+            vec![Some(Vec::<Option<i32>>::new()); 2], 0);
+        assert!(fixed_size_list_lanes::<Int32Type>(&empty).is_none());
+    }
+
+    #[test]
+    fn interleaved() {
+        let a = Int32Array::from(vec![1, 2, 3, 4, 5, 6, 7]);
+        let lanes = interleaved_lanes(&a, 3).collect::<Vec<_>>();
+        assert_eq!(lanes, [&[1, 4, 7][..], &[2, 5], &[3, 6]]);
+        let sliced = a.slice(1, 4);
+        let lanes = interleaved_lanes(&sliced, 2).collect::<Vec<_>>();
+        assert_eq!(lanes, [[2, 4], [3, 5]]);
+    }
+}
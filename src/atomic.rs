@@ -0,0 +1,66 @@
+use std::mem;
+use std::sync::atomic::*;
+use {Stride, MutStride, StrideError};
+
+macro_rules! atomic_impls {
+    ($($width: tt: $($int: ident => $Atomic: ident),+;)+) => {$($(
+        #[cfg(target_has_atomic = $width)]
+        impl<'a> MutStride<'a, $int> {
+            #[doc = concat!("Converts this strided slice into a shared strided slice of `",
+                            stringify!($Atomic), "`s, like `", stringify!($Atomic),
+                            "::from_mut_slice`, consuming `self`.")]
+            ///
+            /// The elements can then be updated concurrently through
+            /// copies of the returned slice on any number of threads.
+            ///
+            /// Returns `Err(Misaligned)` if the elements are not
+            /// aligned for the atomic type, which can only happen on
+            /// platforms where it is more strictly aligned than the
+            /// plain integer.
+            #[inline]
+            pub fn as_atomic_stride(self) -> Result<Stride<'a, $Atomic>, StrideError> {
+                if self.is_empty() { return Ok(Stride::empty()) }
+                let align = mem::align_of::<$Atomic>();
+                if !(self.base.as_mut_ptr() as usize).is_multiple_of(align) ||
+                    !self.base.stride().is_multiple_of(align)
+                {
+                    return Err(StrideError::Misaligned)
+                }
+                // the atomic has the same size and bit validity, and
+                // (now) alignment, and the exclusive borrow is handed
+                // over to it.
+                Ok(Stride::new_raw(unsafe { self.base.cast() }))
+            }
+        }
+    )+)+}
+}
+atomic_impls! {
+    "8": u8 => AtomicU8, i8 => AtomicI8;
+    "16": u16 => AtomicU16, i16 => AtomicI16;
+    "32": u32 => AtomicU32, i32 => AtomicI32;
+    "64": u64 => AtomicU64, i64 => AtomicI64;
+    "ptr": usize => AtomicUsize, isize => AtomicIsize;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering::Relaxed;
+    use std::thread;
+    use MutStride;
+
+    #[test]
+    fn histogram() {
+        // counts in the odd elements, labels in the even ones.
+        let mut buf = [0u32; 8];
+        let counts = MutStride::new(&mut buf).substrides2_mut().1.as_atomic_stride().unwrap();
+        thread::scope(|s| {
+            for t in 0..4 {
+                s.spawn(move || {
+                    for i in 0..100 { counts[(t + i) % 4].fetch_add(1, Relaxed); }
+                });
+            }
+        });
+        assert_eq!(buf, [0, 100, 0, 100, 0, 100, 0, 100]);
+        assert_eq!(MutStride::<i64>::new(&mut []).as_atomic_stride().unwrap().len(), 0);
+    }
+}
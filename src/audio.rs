@@ -0,0 +1,219 @@
+//! Helpers for interleaved audio sample buffers.
+//!
+//! Audio APIs such as cpal hand callbacks a buffer of frames, each
+//! holding one sample per channel, so channel `i` of an `n`-channel
+//! buffer is every `n`th sample starting at `i`.
+//!
+//! ```rust
+//! let mut buf = [0.5f32, 1.0, 0.5, 1.0, 0.5, 1.0];
+//! let mut chans = strided::audio::channels_mut(&mut buf, 2);
+//! let right = chans.pop().unwrap();
+//! strided::audio::gain(right, 0.5);
+//! strided::audio::mix(chans.pop().unwrap(), &strided::Stride::new(&[0.25; 3]), 2.0);
+//! assert_eq!(buf, [1.0, 0.5, 1.0, 0.5, 1.0, 0.5]);
+//! ```
+
+use std::ops::{AddAssign, Mul, MulAssign};
+use {Stride, MutStride};
+
+#[track_caller]
+fn check_frames(len: usize, n_channels: usize, func: &str) {
+    assert!(n_channels != 0, "strided::audio::{}: n_channels must be non-zero", func);
+    assert!(len.is_multiple_of(n_channels),
+            "strided::audio::{}: buffer of {} samples is not a whole number of {}-channel frames",
+            func, len, n_channels);
+}
+
+/// Returns the `n_channels` channels of the interleaved buffer `buf`,
+/// in order.
+///
+/// # Panic
+///
+/// Panics if `n_channels` is zero or does not divide `buf.len()`.
+#[track_caller]
+pub fn channels<S>(buf: &[S], n_channels: usize) -> Vec<Stride<'_, S>> {
+    check_frames(buf.len(), n_channels, "channels");
+    Stride::new(buf).substrides(n_channels).collect()
+}
+
+/// Returns the `n_channels` channels of the interleaved buffer `buf`
+/// as mutable views, in order.
+///
+/// # Panic
+///
+/// Panics if `n_channels` is zero or does not divide `buf.len()`.
+#[track_caller]
+pub fn channels_mut<S>(buf: &mut [S], n_channels: usize) -> Vec<MutStride<'_, S>> {
+    check_frames(buf.len(), n_channels, "channels_mut");
+    MutStride::new(buf).substrides_mut(n_channels).collect()
+}
+
+/// Multiplies every sample of `channel` by `gain`.
+pub fn gain<S: Copy + MulAssign>(channel: MutStride<'_, S>, gain: S) {
+    for x in channel { *x *= gain }
+}
+
+/// Adds every sample of `src`, multiplied by `gain`, to the
+/// corresponding sample of `dst`.
+///
+/// # Panic
+///
+/// Panics if `dst` and `src` have different lengths.
+#[track_caller]
+pub fn mix<S>(dst: MutStride<'_, S>, src: &Stride<'_, S>, gain: S)
+    where S: Copy + AddAssign + Mul<Output = S>
+{
+    for (x, y) in ::zip(*src, dst) { *y += *x * gain }
+}
+
+/// Adds each of `frames` into `out`, the `k`th starting at sample
+/// `k * hop`, and returns the number of frames. This undoes the
+/// framing of `Stride::chunks_with_hop`, summing where frames
+/// overlap, so windowed frames can be resynthesised into a signal.
+///
+/// `out` is accumulated into rather than overwritten, so it should
+/// usually be zeroed first.
+///
+/// # Panic
+///
+/// Panics if `hop` is zero, or if a frame runs past the end of `out`,
+/// after adding the frames before it.
+///
+/// ```rust
+/// use strided::{Stride, MutStride, audio};
+///
+/// let signal = [1; 6];
+/// let frames = Stride::new(&signal).chunks_with_hop(4, 2);
+/// let mut out = [0; 12];
+/// let (left, _) = MutStride::new(&mut out).substrides2_mut();
+/// assert_eq!(audio::overlap_add(frames, 2, left), 2);
+/// assert_eq!(out, [1, 0, 1, 0, 2, 0, 2, 0, 1, 0, 1, 0]);
+/// ```
+#[track_caller]
+pub fn overlap_add<'b, S, I>(frames: I, hop: usize, mut out: MutStride<'_, S>) -> usize
+    where S: Copy + AddAssign + 'b, I: IntoIterator<Item = Stride<'b, S>>
+{
+    assert!(hop != 0, "strided::audio::overlap_add: hop must be non-zero");
+    let mut n = 0;
+    for frame in frames {
+        let start = n * hop;
+        assert!(start.checked_add(frame.len()).is_some_and(|end| end <= out.len()),
+                "strided::audio::overlap_add: frame {} of {} samples runs past the end of {}",
+                n, frame.len(), out.len());
+        let dst = out.reborrow().slice_mut(start, start + frame.len());
+        for (x, y) in ::zip(frame, dst) { *y += *x }
+        n += 1;
+    }
+    n
+}
+
+/// Returns the smallest and largest samples of each bucket of
+/// `bucket` consecutive samples of `src`, the last of which may be
+/// shorter, for drawing a long signal as one vertical line per
+/// bucket.
+///
+/// NaNs are skipped like `stats::min_max`, and a bucket of nothing
+/// but NaNs gives its first sample as both extremes.
+///
+/// # Panic
+///
+/// Panics if `bucket` is zero.
+///
+/// ```rust
+/// use strided::{Stride, audio};
+///
+/// let stereo = [0, 9, 3, 9, -2, 9, 5, 9, 1, 9];
+/// let (left, _) = Stride::new(&stereo).substrides2();
+/// assert_eq!(audio::decimate_min_max(&left, 2), [(0, 3), (-2, 5), (1, 1)]);
+/// ```
+#[track_caller]
+pub fn decimate_min_max<S: PartialOrd + Copy>(src: &Stride<'_, S>, bucket: usize) -> Vec<(S, S)> {
+    assert!(bucket != 0, "strided::audio::decimate_min_max: bucket must be non-zero");
+    let min_max = |b: Stride<'_, S>| ::stats::min_max(&b).unwrap_or((b[0], b[0]));
+    if let Some(slice) = src.as_slice() {
+        // without the stride multiplication, the contiguous case
+        // vectorises well.
+        return slice.chunks(bucket).map(|b| min_max(Stride::new(b))).collect()
+    }
+    (0..src.len()).step_by(bucket)
+        .map(|i| min_max(src.slice(i, src.len().min(i + bucket))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use Stride;
+    use super::{channels, channels_mut, gain, mix, overlap_add, decimate_min_max};
+
+    #[test]
+    fn split() {
+        let buf = [1, 2, 3, 4, 5, 6];
+        assert_eq!(channels(&buf, 3), [[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(channels(&buf, 1), [buf]);
+        assert_eq!(channels::<i32>(&[], 2), [[0; 0]; 2]);
+    }
+
+    #[test]
+    fn gain_and_mix() {
+        let mut buf = [1, 2, 3, 4, 5, 6];
+        let mut chans = channels_mut(&mut buf, 2);
+        let right = chans.pop().unwrap();
+        let left = chans.pop().unwrap();
+        gain(left, 10);
+        mix(right, &Stride::new(&[1, 1, 1]), 100);
+        assert_eq!(buf, [10, 102, 30, 104, 50, 106]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::audio::channels_mut: buffer of 5 samples is not a whole number of 2-channel frames")]
+    fn partial_frame() {
+        channels_mut(&mut [0; 5], 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::audio::channels: n_channels must be non-zero")]
+    fn no_channels() {
+        channels(&[0; 4], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::zip: mismatched lengths")]
+    fn mix_mismatched() {
+        let mut buf = [0; 4];
+        mix(::MutStride::new(&mut buf), &Stride::new(&[1, 2]), 1);
+    }
+
+    #[test]
+    fn overlap_add_round_trip() {
+        // a triangular window at half overlap sums to one, apart from
+        // the ramps at either end.
+        let signal: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        let window = [0.0, 0.5, 1.0, 0.5];
+        let frames: Vec<Vec<f64>> = Stride::new(&signal).chunks_with_hop(4, 2)
+            .map(|f| f.iter().zip(&window).map(|(x, w)| x * w).collect())
+            .collect();
+        let mut out = [0.0; 10];
+        let n = overlap_add(frames.iter().map(|f| Stride::new(f)), 2, ::MutStride::new(&mut out));
+        assert_eq!(n, 4);
+        assert_eq!(out[2..8], signal[2..8]);
+        assert_eq!(overlap_add(None::<Stride<f64>>, 1, ::MutStride::new(&mut [])), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::audio::overlap_add: frame 1 of 3 samples runs past the end of 4")]
+    fn overlap_add_overrun() {
+        let frame = [1; 3];
+        overlap_add(vec![Stride::new(&frame); 2], 2, ::MutStride::new(&mut [0; 4]));
+    }
+
+    #[test]
+    fn decimate() {
+        let v: Vec<i32> = (0..20).map(|i| (i * 7) % 11).collect();
+        let (even, _) = Stride::new(&v).substrides2();
+        assert_eq!(decimate_min_max(&even, 4), [(0, 9), (1, 10), (2, 5)]);
+        assert_eq!(decimate_min_max(&Stride::new(&v[..8]), 8), [(0, 10)]);
+        assert!(decimate_min_max(&Stride::new(&[f64::NAN, 1.0, f64::NAN]), 2)[1].0.is_nan());
+        assert_eq!(decimate_min_max(&Stride::new(&[f64::NAN, 1.0]), 1)[1], (1.0, 1.0));
+        assert_eq!(decimate_min_max::<u8>(&Stride::empty(), 3), []);
+    }
+}
@@ -1,11 +1,27 @@
-use std::cmp::Ordering;
-use std::fmt::{self, Debug};
+use std::cmp::{self, Ordering};
+use std::fmt::{self, Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
 use std::marker;
 use std::mem;
-
+use std::ops::Range;
+use std::ptr::NonNull;
+#[cfg(feature = "unstable")]
+use std::num::NonZeroUsize;
+#[cfg(feature = "unstable")]
+use std::ops::{ControlFlow, Try};
+use std::slice;
+use StrideError;
+
+// Every `Stride` refers to `len` elements within a single allocation,
+// so the span from the first to the last element, `(len - 1) * stride`
+// bytes, is at most `isize::MAX`, and offsetting `data` by `i * stride`
+// for `i < len` can neither overflow nor leave the allocation. Safe
+// code can only shrink a view, so only the constructors need to check
+// this.
 #[repr(C)]
 pub struct Stride<'a,T: 'a> {
-    data: *const T,
+    data: NonNull<T>,
     len: usize,
     stride: usize,
 
@@ -17,17 +33,31 @@ impl<'a, T> Clone for Stride<'a, T> {
     fn clone(&self) -> Stride<'a, T> { *self }
 }
 
+// Contiguous views, such as the halves from `split_at`, are common
+// enough that these compare them as slices, which the standard
+// library does with `memcmp` where it can.
 impl<'a, T: PartialEq> PartialEq for Stride<'a, T> {
     fn eq(&self, other: &Stride<'a, T>) -> bool {
         if self.len() != other.len() { return false }
+        if let (Some(a), Some(b)) = (self.as_slice(), other.as_slice()) { return a == b }
 
         self.iter().zip(other.iter()).all(|(a, b)| a == b)
     }
 }
 impl<'a, T: Eq> Eq for Stride<'a, T> {}
 
+impl<'a, T: Hash> Hash for Stride<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for x in self.iter() {
+            x.hash(state)
+        }
+    }
+}
+
 impl<'a, T: PartialOrd> PartialOrd for Stride<'a, T> {
     fn partial_cmp(&self, other: &Stride<'a, T>) -> Option<Ordering> {
+        if let (Some(a), Some(b)) = (self.as_slice(), other.as_slice()) { return a.partial_cmp(b) }
         let mut a = self.iter();
         let mut b = other.iter();
         loop {
@@ -35,7 +65,7 @@ impl<'a, T: PartialOrd> PartialOrd for Stride<'a, T> {
                 (None, None) => return Some(Ordering::Equal),
                 (None, _   ) => return Some(Ordering::Less),
                 (_   , None) => return Some(Ordering::Greater),
-                (Some(x), Some(y)) => match x.partial_cmp(&y) {
+                (Some(x), Some(y)) => match x.partial_cmp(y) {
                     Some(Ordering::Equal) => (),
                     non_eq => return non_eq,
                 },
@@ -45,6 +75,7 @@ impl<'a, T: PartialOrd> PartialOrd for Stride<'a, T> {
 }
 impl<'a, T: Ord> Ord for Stride<'a, T> {
     fn cmp(&self, other: &Stride<'a, T>) -> Ordering {
+        if let (Some(a), Some(b)) = (self.as_slice(), other.as_slice()) { return a.cmp(b) }
         let mut a = self.iter();
         let mut b = other.iter();
         loop {
@@ -52,7 +83,7 @@ impl<'a, T: Ord> Ord for Stride<'a, T> {
                 (None, None) => return Ordering::Equal,
                 (None, _   ) => return Ordering::Less,
                 (_   , None) => return Ordering::Greater,
-                (Some(x), Some(y)) => match x.cmp(&y) {
+                (Some(x), Some(y)) => match x.cmp(y) {
                     Ordering::Equal => (),
                     non_eq => return non_eq,
                 },
@@ -62,25 +93,84 @@ impl<'a, T: Ord> Ord for Stride<'a, T> {
 }
 
 impl<'a, T: Debug> Debug for Stride<'a, T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "["));
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
         let mut is_first = true;
         for x in self.iter() {
             if is_first {
                 is_first = false;
             } else {
-                try!(write!(f, ", "));
+                write!(f, ", ")?;
             }
-            try!(write!(f, "{:?}", *x))
+            write!(f, "{:?}", *x)?
         }
         write!(f, "]")
     }
 }
 
+impl<'a, T: Display> Display for Stride<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bare = f.alternate();
+        if !bare { write!(f, "[")? }
+        let mut is_first = true;
+        for x in self.iter() {
+            if is_first {
+                is_first = false;
+            } else {
+                write!(f, ", ")?;
+            }
+            Display::fmt(x, f)?
+        }
+        if !bare { write!(f, "]")? }
+        Ok(())
+    }
+}
 
 unsafe fn step<T>(ptr: *const T, stride: usize) -> *const T {
-    debug_assert!(stride % mem::size_of::<T>() == 0);
-    (ptr as *const u8).offset(stride as isize) as *const T
+    debug_assert!(stride.is_multiple_of(mem::size_of::<T>()));
+    (ptr as *const u8).add(stride) as *const T
+}
+// converts the signed stride of `len` elements used by other array
+// libraries, for which the stride of at most one element is
+// irrelevant.
+pub fn positive_stride(stride: isize, len: usize) -> Result<usize, StrideError> {
+    if len <= 1 { return Ok(1) }
+    match stride {
+        0 => Err(StrideError::ZeroStride),
+        s if s < 0 => Err(StrideError::NegativeStride),
+        s => Ok(s as usize),
+    }
+}
+
+// returns `(g, x, y)` with `g = gcd(a, b) = a * x + b * y`, for
+// positive `a` and `b`.
+fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = ext_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -(-a).div_euclid(b)
+}
+
+// returns the stride in bytes of `len` elements `elem_stride` apart,
+// if their span fits in an `isize`.
+fn checked_layout<T>(len: usize, elem_stride: usize) -> Result<usize, StrideError> {
+    if elem_stride == 0 { return Err(StrideError::ZeroStride) }
+    let byte_stride = elem_stride.checked_mul(mem::size_of::<T>()).ok_or(StrideError::TooLarge)?;
+    match len.saturating_sub(1).checked_mul(byte_stride) {
+        Some(span) if span <= isize::MAX as usize => Ok(byte_stride),
+        _ => Err(StrideError::TooLarge),
+    }
+}
+
+// for pointers that may be one stride past the last element, and so
+// outside the allocation: these must not be dereferenced.
+fn wrapping_step<T>(ptr: *const T, stride: usize) -> *const T {
+    (ptr as *const u8).wrapping_add(stride) as *const T
 }
 
 impl<'a, T> Stride<'a, T> {
@@ -89,12 +179,90 @@ impl<'a, T> Stride<'a, T> {
         Stride::new_raw(data, len, elem_stride * mem::size_of::<T>())
     }
 
+    #[inline(always)]
+    pub const fn empty() -> Stride<'a, T> {
+        assert!(mem::size_of::<T>() != 0);
+        Stride {
+            data: NonNull::dangling(),
+            len: 0,
+            stride: mem::size_of::<T>(),
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Creates a view of `len` elements, `elem_stride` elements apart,
+    /// starting `offset` elements into the `data_len` elements at
+    /// `data`.
+    pub fn with_layout(data: *mut T, data_len: usize,
+                       offset: usize, elem_stride: usize, len: usize)
+                       -> Result<Stride<'a, T>, StrideError> {
+        let byte_stride = checked_layout::<T>(len, elem_stride)?;
+        let fits = match len {
+            0 => offset <= data_len,
+            _ => (len - 1) * elem_stride < data_len && offset < data_len - (len - 1) * elem_stride,
+        };
+        if !fits { return Err(StrideError::OutOfBounds) }
+        let data = unsafe { data.add(offset) };
+        Ok(Stride::new_raw(data, len, byte_stride))
+    }
+
+    /// Creates a view of `len` elements, `elem_stride` elements apart,
+    /// starting at `data`, which must satisfy the invariant described
+    /// on `Stride` (other than the size, which is checked).
+    pub unsafe fn from_raw_parts(data: *mut T, len: usize, elem_stride: usize)
+                                 -> Result<Stride<'a, T>, StrideError> {
+        let byte_stride = checked_layout::<T>(len, elem_stride)?;
+        Ok(Stride::new_raw(data, len, byte_stride))
+    }
+
+    /// Like `from_raw_parts`, but with a signed stride in bytes, as
+    /// used by NumPy and the buffer protocol. Strides that are not a
+    /// whole number of elements, and unaligned `data`, are rejected
+    /// with `Misaligned`.
+    pub unsafe fn from_byte_stride(data: *mut T, len: usize, byte_stride: isize)
+                                   -> Result<Stride<'a, T>, StrideError> {
+        let size = mem::size_of::<T>();
+        if len == 0 { return Ok(Stride::empty()) }
+        let stride = if len == 1 { size } else { positive_stride(byte_stride, len)? };
+        if !stride.is_multiple_of(size) || !data.is_aligned() {
+            return Err(StrideError::Misaligned)
+        }
+        Stride::from_raw_parts(data, len, stride / size)
+    }
+
+    /// Views the elements as `U`s, which must have the same size as
+    /// `T` and be valid wherever a `T` is.
+    pub unsafe fn cast<U>(self) -> Stride<'a, U> {
+        debug_assert_eq!(mem::size_of::<T>(), mem::size_of::<U>());
+        Stride::new_raw(self.data.as_ptr() as *mut U, self.len, self.stride)
+    }
+
+    /// Views the `T` at `byte_offset` within each of the `len`
+    /// consecutive `S`s starting at `data`, which must satisfy the
+    /// invariant described on `Stride`.
+    #[track_caller]
+    pub unsafe fn project_field<S>(data: *mut S, len: usize, byte_offset: usize) -> Stride<'a, T> {
+        let (size, field_size) = (mem::size_of::<S>(), mem::size_of::<T>());
+        assert!(byte_offset.checked_add(field_size).is_some_and(|end| end <= size),
+                "Stride.project_field: a {}-byte field at byte {} does not fit in a {}-byte struct",
+                field_size, byte_offset, size);
+        let field = (data as *mut u8).wrapping_add(byte_offset) as *mut T;
+        match Stride::from_byte_stride(field, len, size as isize) {
+            Ok(s) => s,
+            Err(e) => panic!("Stride.project_field: {} ({}-byte field in a {}-byte struct)",
+                             e, field_size, size),
+        }
+    }
+
     fn new_raw(data: *mut T, len: usize, byte_stride: usize) -> Stride<'a, T> {
         // remove this assertion
         assert!(mem::size_of::<T>() != 0);
+        debug_assert!(!data.is_null());
         Stride {
-            data: data,
-            len: len,
+            // every caller derives `data` from a reference (or a
+            // dangling pointer, for empty views), so it is never null.
+            data: unsafe { NonNull::new_unchecked(data) },
+            len,
             stride: byte_stride,
             _marker: marker::PhantomData,
         }
@@ -105,22 +273,41 @@ impl<'a, T> Stride<'a, T> {
         self.len
     }
     #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    #[inline(always)]
     pub fn stride(&self) -> usize {
         self.stride
     }
     #[inline(always)]
     pub fn as_mut_ptr(&self) -> *mut T {
-        self.data as *mut T
+        self.data.as_ptr()
+    }
+
+
+    // the stride in elements, for messages.
+    fn elem_stride(&self) -> usize {
+        self.stride / mem::size_of::<T>()
     }
 
+    #[cold]
+    #[track_caller]
+    pub fn fail(&self, method: &str, e: StrideError) -> ! {
+        panic!("Stride.{}: {} (stride {})", method, e, self.elem_stride())
+    }
 
+    #[track_caller]
     pub fn substrides2(self) -> (Stride<'a, T>, Stride<'a, T>) {
-        let left_len = (self.len() + 1)/2;
+        let left_len = self.len().div_ceil(2);
         let right_len = self.len() - left_len;
-        let stride = self.stride.checked_mul(2).expect("Stride.substrides2: stride too large");
+        let stride = match self.stride.checked_mul(2) {
+            Some(s) => s,
+            None => self.fail("substrides2", StrideError::TooLarge),
+        };
 
-        let left_ptr = self.data;
-        let right_ptr = if self.len() == 0 {
+        let left_ptr = self.data.as_ptr();
+        let right_ptr = if self.is_empty() {
             left_ptr
         } else {
             unsafe {step(left_ptr, self.stride)}
@@ -131,45 +318,206 @@ impl<'a, T> Stride<'a, T> {
     }
 
     #[inline]
+    #[track_caller]
     pub fn substrides(self, n: usize) -> Substrides<'a, T> {
-        assert!(n != 0);
-        let long_len = (self.len() + n - 1) / n;
-        let new_stride = n.checked_mul(self.stride).expect("Stride.substrides: stride too large");
-        Substrides {
-            x: Stride::new_raw(self.data as *mut _, long_len, new_stride),
-            base_stride: self.stride,
-            nlong: self.len() % n,
-            count: n
+        match self.try_substrides(n) {
+            Ok(s) => s,
+            Err(e) => self.fail("substrides", e),
         }
     }
+    #[inline]
+    pub fn try_substrides(self, n: usize) -> Result<Substrides<'a, T>, StrideError> {
+        if n == 0 { return Err(StrideError::ZeroStride) }
+        let new_stride = n.checked_mul(self.stride).ok_or(StrideError::TooLarge)?;
+        Ok(Substrides {
+            x: self,
+            n,
+            stride: new_stride,
+            index: 0,
+            end: n,
+        })
+    }
+    #[inline]
+    pub fn try_substride(self, n: usize, i: usize) -> Result<Stride<'a, T>, StrideError> {
+        if i >= n && n != 0 { return Err(StrideError::IndexOutOfBounds { index: i, len: n }) }
+        Ok(self.try_substrides(n)?.phase(i))
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn substrides3(self) -> (Stride<'a, T>, Stride<'a, T>, Stride<'a, T>) {
+        let s = self.substrides(3);
+        (s.phase(0), s.phase(1), s.phase(2))
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn substrides4(self) -> (Stride<'a, T>, Stride<'a, T>, Stride<'a, T>, Stride<'a, T>) {
+        let s = self.substrides(4);
+        (s.phase(0), s.phase(1), s.phase(2), s.phase(3))
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn substride(self, n: usize, i: usize) -> Stride<'a, T> {
+        assert!(i < n, "Stride.substride: index {} out of bounds for {} substrides", i, n);
+        self.substrides(n).phase(i)
+    }
 
     pub fn iter(&self) -> Items<'a, T> {
-        assert!(self.data as usize + self.len * self.stride >= self.data as usize);
         Items {
-            start: self.data as *const _,
-            // this points one-stride past the end, and so is
-            // possibly undefined behaviour since the underlying array
-            // doesn't necessarily extend this far (e.g. a Stride of
-            // [1, 2, 3] starting at 2 with stride 2)
-            end: unsafe {step(self.data, self.stride * self.len)},
+            start: self.data.as_ptr() as *const _,
+            len: self.len,
             stride: self.stride,
             _marker: marker::PhantomData,
         }
     }
     pub fn iter_mut(&mut self) -> MutItems<'a, T> {
-        assert!(self.data as usize + self.len * self.stride >= self.data as usize);
         MutItems {
-            start: self.data as *mut _,
-            end: unsafe {step(self.data, self.stride * self.len) as *mut _},
+            start: self.data.as_ptr(),
+            len: self.len,
             stride: self.stride,
             _marker: marker::PhantomData,
         }
     }
 
+    #[inline]
+    pub fn is_contiguous(&self) -> bool {
+        self.stride == mem::size_of::<T>() || self.len <= 1
+    }
+
+    // the memory from the start of the first element to the end of
+    // the last.
+    pub fn ptr_range(&self) -> Range<*mut T> {
+        let start = self.data.as_ptr();
+        if self.is_empty() { return start..start }
+        unsafe {
+            let last = step(start, (self.len - 1) * self.stride) as *mut T;
+            start..last.add(1)
+        }
+    }
+
+    // the index of the element at `p`, if there is one.
+    pub fn index_of(&self, p: *const T) -> Option<usize> {
+        let offset = (p as usize).checked_sub(self.data.as_ptr() as usize)?;
+        if !offset.is_multiple_of(self.stride) { return None }
+        let i = offset / self.stride;
+        if i < self.len { Some(i) } else { None }
+    }
+
+    pub fn overlaps<U>(&self, other: &Stride<'_, U>) -> bool {
+        if self.is_empty() || other.is_empty() { return false }
+        let (a, sa, la) = (self.data.as_ptr() as i128, self.stride as i128, self.len as i128);
+        let (b, sb, lb) = (other.data.as_ptr() as i128, other.stride as i128, other.len as i128);
+        let (size_a, size_b) = (mem::size_of::<T>() as i128, mem::size_of::<U>() as i128);
+
+        // the elements `a + i * sa` and `b + j * sb` overlap when
+        // `(a + i * sa) - (b + j * sb)` lies in `(-size_a, size_b)`,
+        // i.e. when `i * sa - j * sb = d` for one of those `d - (a - b)`.
+        // Only those `d` divisible by `g = gcd(sa, sb)` have solutions,
+        // and since `g` is at least as large as an element, there are
+        // very few of them.
+        let (g, x, y) = ext_gcd(sa, sb);
+        let (ta, tb) = (sb / g, sa / g);
+        let lo = -size_a + 1 - (a - b);
+        let hi = size_b - 1 - (a - b);
+        let mut d = lo + (-lo).rem_euclid(g);
+        while d <= hi {
+            // all solutions are `i = i0 + k * ta`, `j = j0 + k * tb`.
+            let (i0, j0) = (x * (d / g), -y * (d / g));
+            let k_lo = cmp::max(div_ceil(-i0, ta), div_ceil(-j0, tb));
+            let k_hi = cmp::min((la - 1 - i0).div_euclid(ta), (lb - 1 - j0).div_euclid(tb));
+            if k_lo <= k_hi { return true }
+            d += g;
+        }
+        false
+    }
+
+    pub fn try_merge(a: Stride<'a, T>, b: Stride<'a, T>) -> Option<Stride<'a, T>> {
+        if b.is_empty() { return Some(a) }
+        if a.is_empty() { return Some(b) }
+
+        let start = a.data.as_ptr() as usize;
+        let b_start = b.data.as_ptr() as usize;
+        if b_start <= start { return None }
+        let gap = b_start - start;
+        if !gap.is_multiple_of(mem::size_of::<T>()) { return None }
+        let len = a.len.checked_add(b.len)?;
+
+        // adjacent: b starts one stride after the last element of a.
+        let stride = if a.len > 1 { a.stride } else if b.len > 1 { b.stride } else { gap };
+        if (a.len == 1 || a.stride == stride) && (b.len == 1 || b.stride == stride) &&
+            a.len.checked_mul(stride) == Some(gap) {
+            return Some(Stride::new_raw(a.data.as_ptr(), len, stride))
+        }
+
+        // interleaved: b starts between the first two elements of a.
+        let double = gap.checked_mul(2)?;
+        if (a.len == 1 || a.stride == double) && (b.len == 1 || b.stride == double) &&
+            (a.len == b.len || a.len == b.len + 1) {
+            return Some(Stride::new_raw(a.data.as_ptr(), len, gap))
+        }
+        None
+    }
+
+    // `try_merge`, but only if every element of the result is an
+    // element of `self`, in which case the result is rebuilt from
+    // `self.data`, so that it can only reach memory `self` can.
+    pub fn try_merge_within(self, a: Stride<'a, T>, b: Stride<'a, T>) -> Option<Stride<'a, T>> {
+        let m = Stride::try_merge(a, b)?;
+        if m.is_empty() { return Some(Stride::new_raw(self.data.as_ptr(), 0, self.stride)) }
+        let offset = (m.data.as_ptr() as usize).checked_sub(self.data.as_ptr() as usize)?;
+        if !offset.is_multiple_of(self.stride) { return None }
+        let first = offset / self.stride;
+        let step_by = if m.len > 1 {
+            if !m.stride.is_multiple_of(self.stride) { return None }
+            m.stride / self.stride
+        } else {
+            0
+        };
+        let last = (m.len - 1).checked_mul(step_by)?.checked_add(first)?;
+        if last >= self.len { return None }
+        unsafe { Some(Stride::new_raw(step(self.data.as_ptr(), offset) as *mut T, m.len, m.stride)) }
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> Option<&'a [T]> {
+        if self.is_contiguous() {
+            unsafe { Some(slice::from_raw_parts(self.data.as_ptr(), self.len)) }
+        } else {
+            None
+        }
+    }
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> Option<&'a mut [T]> {
+        if self.is_contiguous() {
+            unsafe { Some(slice::from_raw_parts_mut(self.data.as_ptr(), self.len)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub unsafe fn get_unchecked(&self, n: usize) -> &'a T {
+        &*step(self.data.as_ptr(), n * self.stride)
+    }
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, n: usize) -> &'a mut T {
+        &mut *(step(self.data.as_ptr(), n * self.stride) as *mut T)
+    }
+    // a pointer to element `n`, without creating a reference to it.
+    #[inline]
+    pub fn get_ptr(&self, n: usize) -> Option<*mut T> {
+        if n < self.len {
+            unsafe { Some(step(self.data.as_ptr(), n * self.stride) as *mut T) }
+        } else {
+            None
+        }
+    }
     #[inline]
     pub fn get(&self, n: usize) -> Option<&'a T> {
         if n < self.len {
-            unsafe {Some(&*step(self.data, n * self.stride))}
+            unsafe {Some(&*step(self.data.as_ptr(), n * self.stride))}
         } else {
             None
         }
@@ -177,7 +525,7 @@ impl<'a, T> Stride<'a, T> {
     #[inline]
     pub fn get_mut(&mut self, n: usize) -> Option<&'a mut T> {
         if n < self.len {
-            unsafe {Some(&mut *(step(self.data, n * self.stride) as *mut _))}
+            unsafe {Some(&mut *(step(self.data.as_ptr(), n * self.stride) as *mut _))}
         } else {
             None
         }
@@ -185,42 +533,123 @@ impl<'a, T> Stride<'a, T> {
 
 
     #[inline]
+    #[track_caller]
     pub fn slice(self, from: usize, to: usize) -> Stride<'a, T> {
-        assert!(from <= to && to <= self.len());
-        unsafe {
-            Stride::new_raw(step(self.data, from * self.stride) as *mut _,
-                            to - from, self.stride)
+        match self.try_slice(from, to) {
+            Ok(s) => s,
+            Err(e) => self.fail("slice", e),
         }
     }
     #[inline]
+    pub unsafe fn slice_unchecked(self, from: usize, to: usize) -> Stride<'a, T> {
+        debug_assert!(from <= to && to <= self.len());
+        // `from == len` may be past the allocation, see `wrapping_step`.
+        Stride::new_raw(wrapping_step(self.data.as_ptr(), from * self.stride) as *mut _,
+                        to - from, self.stride)
+    }
+    #[inline]
+    pub fn try_slice(self, from: usize, to: usize) -> Result<Stride<'a, T>, StrideError> {
+        if from > to { return Err(StrideError::InvalidRange { from, to }) }
+        if to > self.len { return Err(StrideError::IndexOutOfBounds { index: to, len: self.len }) }
+        Ok(unsafe { self.slice_unchecked(from, to) })
+    }
+    #[inline]
+    pub fn try_slice_from(self, from: usize) -> Result<Stride<'a, T>, StrideError> {
+        if from > self.len { return Err(StrideError::IndexOutOfBounds { index: from, len: self.len }) }
+        Ok(unsafe { self.slice_unchecked(from, self.len) })
+    }
+    #[inline]
+    #[track_caller]
     pub fn slice_from(self, from: usize) -> Stride<'a, T> {
-        self.slice(from, self.len())
+        match self.try_slice_from(from) {
+            Ok(s) => s,
+            Err(e) => self.fail("slice_from", e),
+        }
     }
     #[inline]
+    #[track_caller]
     pub fn slice_to(self, to: usize) -> Stride<'a, T> {
-        self.slice(0, to)
+        match self.try_slice(0, to) {
+            Ok(s) => s,
+            Err(e) => self.fail("slice_to", e),
+        }
+    }
+    #[inline]
+    pub fn slice_clamped(self, from: usize, to: usize) -> Stride<'a, T> {
+        let to = cmp::min(to, self.len());
+        let from = cmp::min(from, to);
+        self.slice(from, to)
     }
 
+    #[track_caller]
     pub fn split_at(self, idx: usize) -> (Stride<'a, T>, Stride<'a, T>) {
-        assert!(idx <= self.len());
-        unsafe {
-            (Stride::new_raw(self.data as *mut _, idx, self.stride),
-             Stride::new_raw(step(self.data, idx * self.stride) as *mut _,
-                             self.len() - idx, self.stride))
+        match self.try_split_at(idx) {
+            Ok(s) => s,
+            Err(e) => self.fail("split_at", e),
         }
     }
+    #[inline]
+    pub fn try_split_at(self, idx: usize) -> Result<(Stride<'a, T>, Stride<'a, T>), StrideError> {
+        if idx > self.len { return Err(StrideError::IndexOutOfBounds { index: idx, len: self.len }) }
+        Ok(unsafe { self.split_at_unchecked(idx) })
+    }
+    #[inline]
+    pub unsafe fn split_at_unchecked(self, idx: usize) -> (Stride<'a, T>, Stride<'a, T>) {
+        debug_assert!(idx <= self.len());
+        (Stride::new_raw(self.data.as_ptr(), idx, self.stride),
+         Stride::new_raw(wrapping_step(self.data.as_ptr(), idx * self.stride) as *mut _,
+                         self.len() - idx, self.stride))
+    }
+
+    #[inline]
+    pub fn take_prefix(&mut self, n: usize) -> Option<Stride<'a, T>> {
+        if n > self.len() { return None }
+        let (l, r) = unsafe { self.split_at_unchecked(n) };
+        *self = r;
+        Some(l)
+    }
+    #[inline]
+    pub fn take_suffix(&mut self, n: usize) -> Option<Stride<'a, T>> {
+        let idx = self.len().checked_sub(n)?;
+        let (l, r) = unsafe { self.split_at_unchecked(idx) };
+        *self = l;
+        Some(r)
+    }
 }
 
+// Iterators store a pointer to the next element and the number of
+// elements remaining, rather than a one-past-the-end pointer: with a
+// stride larger than one element, a pointer one stride past the last
+// element can lie well outside the allocation, and even computing it
+// with `add` is undefined behaviour. For the same reason, `start` is
+// advanced with wrapping arithmetic, and only dereferenced while
+// `len > 0`.
 macro_rules! iterator {
-    ($name: ident -> $elem: ty) => {
+    ($name: ident, *$raw: tt T -> $elem: ty, $from_raw_parts: path, $iter: ident, $($mut_: tt)*) => {
+        impl<'a, T> $name<'a, T> {
+            // takes the remaining elements as a slice, if they are
+            // contiguous (and there are some, since the pointer of an
+            // exhausted iterator may have wrapped around).
+            #[inline]
+            fn take_slice(&mut self) -> Option<&'a $($mut_)* [T]> {
+                if self.len > 0 && self.stride == mem::size_of::<T>() {
+                    let len = mem::replace(&mut self.len, 0);
+                    unsafe { Some($from_raw_parts(self.start as *$raw T, len)) }
+                } else {
+                    None
+                }
+            }
+        }
+
         impl<'a, T> Iterator for $name<'a, T> {
             type Item = $elem;
             #[inline]
             fn next(&mut self) -> Option<$elem> {
-                if self.start < self.end {
+                if self.len > 0 {
                     unsafe {
-                        let ret = Some(mem::transmute::<_, $elem>(self.start));
-                        self.start = mem::transmute(step(self.start as *mut T, self.stride));
+                        let ret = Some(&$($mut_)* *self.start);
+                        self.start = wrapping_step(self.start, self.stride) as *$raw T;
+                        self.len -= 1;
                         ret
                     }
                 } else {
@@ -230,39 +659,183 @@ macro_rules! iterator {
 
             #[inline]
             fn size_hint(&self) -> (usize, Option<usize>) {
-                let n = (self.end as usize - self.start as usize) / self.stride as usize;
-                (n, Some(n))
+                (self.len, Some(self.len))
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<$elem> {
+                if n < self.len {
+                    unsafe {
+                        self.start = step(self.start, n * self.stride) as *$raw T;
+                    }
+                    self.len -= n;
+                    self.next()
+                } else {
+                    self.len = 0;
+                    None
+                }
+            }
+
+            #[inline]
+            fn last(mut self) -> Option<$elem> {
+                self.next_back()
+            }
+
+            #[inline]
+            fn count(self) -> usize {
+                self.len
+            }
+
+            // a counted loop (rather than the length check in `next`)
+            // gives LLVM a known trip count to unroll and vectorise
+            // with, and contiguous elements use the slice iterator,
+            // which it does best with.
+            #[inline]
+            fn fold<B, F>(mut self, init: B, mut f: F) -> B
+                where F: FnMut(B, $elem) -> B
+            {
+                if let Some(s) = self.take_slice() { return s.$iter().fold(init, f) }
+                let mut acc = init;
+                for i in 0..self.len {
+                    unsafe {
+                        let p = step(self.start, i * self.stride) as *$raw T;
+                        acc = f(acc, &$($mut_)* *p);
+                    }
+                }
+                acc
+            }
+
+            #[cfg(feature = "unstable")]
+            #[inline]
+            fn try_fold<B, F, R>(&mut self, init: B, mut f: F) -> R
+                where F: FnMut(B, $elem) -> R, R: Try<Output = B>
+            {
+                let mut acc = init;
+                while self.len > 0 {
+                    unsafe {
+                        let p = self.start;
+                        self.start = wrapping_step(p, self.stride) as *$raw T;
+                        self.len -= 1;
+                        match f(acc, &$($mut_)* *p).branch() {
+                            ControlFlow::Continue(c) => acc = c,
+                            ControlFlow::Break(r) => return R::from_residual(r),
+                        }
+                    }
+                }
+                R::from_output(acc)
+            }
+
+            #[cfg(feature = "unstable")]
+            #[inline]
+            fn advance_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
+                let k = cmp::min(n, self.len);
+                self.start = wrapping_step(self.start, k * self.stride) as *$raw T;
+                self.len -= k;
+                NonZeroUsize::new(n - k).map_or(Ok(()), Err)
+            }
+        }
+
+        impl<'a, T> FusedIterator for $name<'a, T> {}
+
+        impl<'a, T> Debug for $name<'a, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("len", &self.len)
+                    .field("stride", &(self.stride / mem::size_of::<T>()))
+                    .field("ptr", &self.start)
+                    .finish()
+            }
+        }
+
+        #[cfg(feature = "nightly")]
+        unsafe impl<'a, T> ::std::iter::TrustedLen for $name<'a, T> {}
+
+        impl<'a, T> ExactSizeIterator for $name<'a, T> {
+            #[inline]
+            fn len(&self) -> usize {
+                self.len
             }
         }
 
         impl<'a, T> DoubleEndedIterator for $name<'a, T> {
             #[inline]
-            #[allow(unsigned_negation)]
             fn next_back(&mut self) -> Option<$elem> {
-                if self.start < self.end {
+                if self.len > 0 {
+                    self.len -= 1;
                     unsafe {
-                        self.end = mem::transmute(step(self.end as *mut T,
-                                                       0usize.wrapping_sub(self.stride)));
-                        Some(mem::transmute::<_, $elem>(self.end))
+                        Some(&$($mut_)* *(step(self.start, self.len * self.stride) as *$raw T))
                     }
                 } else {
                     None
                 }
             }
+
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> Option<$elem> {
+                if n < self.len {
+                    self.len -= n;
+                    self.next_back()
+                } else {
+                    self.len = 0;
+                    None
+                }
+            }
+
+            #[inline]
+            fn rfold<B, F>(mut self, init: B, mut f: F) -> B
+                where F: FnMut(B, $elem) -> B
+            {
+                if let Some(s) = self.take_slice() { return s.$iter().rfold(init, f) }
+                let mut acc = init;
+                for i in (0..self.len).rev() {
+                    unsafe {
+                        let p = step(self.start, i * self.stride) as *$raw T;
+                        acc = f(acc, &$($mut_)* *p);
+                    }
+                }
+                acc
+            }
+
+            #[cfg(feature = "unstable")]
+            #[inline]
+            fn advance_back_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
+                let k = cmp::min(n, self.len);
+                self.len -= k;
+                NonZeroUsize::new(n - k).map_or(Ok(()), Err)
+            }
         }
     }
 }
 
 /// An iterator over shared references to the elements of a strided
 /// slice.
-#[allow(raw_pointer_derive)]
 pub struct Items<'a, T: 'a> {
     start: *const T,
-    end: *const T,
+    len: usize,
     stride: usize,
     _marker: marker::PhantomData<&'a T>,
 }
-iterator!(Items -> &'a T);
+iterator!(Items, *const T -> &'a T, slice::from_raw_parts, iter, );
+
+// As for `slice::Iter`.
+unsafe impl<'a, T: Sync> Sync for Items<'a, T> {}
+unsafe impl<'a, T: Sync> Send for Items<'a, T> {}
+
+impl<'a, T> Items<'a, T> {
+    /// Returns a strided slice of the elements that have not yet
+    /// been yielded by this iterator.
+    #[inline]
+    pub fn as_stride(&self) -> ::Stride<'a, T> {
+        let base = Stride::new_raw(self.start as *mut T, self.len, self.stride);
+        ::imm::Stride::new_raw(base)
+    }
+
+    /// Returns `true` if there are no elements left to yield.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
 
 impl<'a, T> Copy for Items<'a, T> {}
 impl<'a, T> Clone for Items<'a, T> {
@@ -273,40 +846,89 @@ impl<'a, T> Clone for Items<'a, T> {
 /// slice.
 pub struct MutItems<'a, T: 'a> {
     start: *mut T,
-    end: *mut T,
+    len: usize,
     stride: usize,
     _marker: marker::PhantomData<&'a mut T>,
 }
-iterator!(MutItems -> &'a mut T);
+iterator!(MutItems, *mut T -> &'a mut T, slice::from_raw_parts_mut, iter_mut, mut);
+
+// As for `slice::IterMut`.
+unsafe impl<'a, T: Sync> Sync for MutItems<'a, T> {}
+unsafe impl<'a, T: Send> Send for MutItems<'a, T> {}
+
+impl<'a, T> MutItems<'a, T> {
+    /// Converts this iterator into a strided slice of the elements
+    /// that have not yet been yielded.
+    #[inline]
+    pub fn into_stride(self) -> ::MutStride<'a, T> {
+        let base = Stride::new_raw(self.start, self.len, self.stride);
+        ::mut_::Stride::new_raw(base)
+    }
+
+    /// Returns `true` if there are no elements left to yield.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
 
 pub struct Substrides<'a, T: 'a> {
     x: Stride<'a, T>,
-    base_stride: usize,
-    nlong: usize,
-    count: usize
+    n: usize,
+    stride: usize,
+    index: usize,
+    end: usize,
+}
+
+impl<'a, T> Substrides<'a, T> {
+    // the `i`th of the `n` interleaved substrides, pointing to the
+    // elements at offsets `i`, `i + n`, `i + 2n`, ... of `x`.
+    #[inline]
+    fn phase(&self, i: usize) -> Stride<'a, T> {
+        let len = self.x.len;
+        if i < len {
+            let data = unsafe {step(self.x.data.as_ptr(), i * self.x.stride)};
+            Stride::new_raw(data as *mut _, (len - i).div_ceil(self.n), self.stride)
+        } else {
+            Stride::new_raw(self.x.data.as_ptr() as *mut _, 0, self.stride)
+        }
+    }
+}
+
+impl<'a, T> Debug for Substrides<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Substrides")
+            .field("remaining", &(self.end - self.index))
+            .field("index", &self.index)
+            .field("n", &self.n)
+            .field("stride", &(self.stride / mem::size_of::<T>()))
+            .field("ptr", &self.x.data.as_ptr())
+            .finish()
+    }
 }
 
 impl<'a, T> Iterator for Substrides<'a, T> {
     type Item = Stride<'a, T>;
+    #[inline]
     fn next(&mut self) -> Option<Stride<'a, T>> {
-        if self.count == 0 { return None }
-        self.count -= 1;
-
-        let ret = self.x;
-
-        if self.nlong > 0 {
-            self.nlong -= 1;
-            if self.nlong == 0 {
-                self.x.len -= 1;
-            }
-        }
-        if self.x.len > 0 {
-            self.x.data = unsafe {step(self.x.data, self.base_stride)};
+        if self.index < self.end {
+            self.index += 1;
+            Some(self.phase(self.index - 1))
+        } else {
+            None
         }
-        Some(ret)
     }
 
+    #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.count, Some(self.count))
+        let n = self.end - self.index;
+        (n, Some(n))
+    }
+
+    #[inline]
+    fn nth(&mut self, k: usize) -> Option<Stride<'a, T>> {
+        self.index = self.index.saturating_add(k).min(self.end);
+        self.next()
     }
 }
+impl<'a, T> FusedIterator for Substrides<'a, T> {}
@@ -0,0 +1,478 @@
+//! BLAS level-1 operations on strided vectors.
+//!
+//! These follow the reference BLAS routines of the same names, with
+//! the increments (`incx`, `incy`) given by the strides of the
+//! arguments. Dot products, and sums over contiguous vectors, are
+//! split across several accumulators, so they may differ from a
+//! naive loop in the last bits.
+//!
+//! With the `cblas` feature, `f32` and `f64` vectors are instead
+//! passed to the `cblas_*` routines of a system BLAS, which the final
+//! binary must link (for example with `-l openblas` or the
+//! `blas-src` crate). Vectors whose length or stride does not fit in
+//! a C `int` still use the pure-Rust code.
+//!
+//! ```rust
+//! use strided::{Stride, MutStride, blas1};
+//!
+//! // the columns of a 3x2 row-major matrix.
+//! let mut m = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+//! let (c0, c1) = MutStride::new(&mut m).substrides2_mut();
+//! assert_eq!(blas1::dot(&c0, &c1), 1.0 * 2.0 + 3.0 * 4.0 + 5.0 * 6.0);
+//! blas1::axpy(-2.0, &c0, c1);
+//! assert_eq!(m, [1.0, 0.0, 3.0, -2.0, 5.0, -4.0]);
+//! ```
+
+use std::ops::{Add, Div, Mul, Sub};
+use {Stride, MutStride};
+
+/// The floating point types the operations of this module work with,
+/// `f32` and `f64`.
+pub trait Float: Copy + PartialOrd + private::Sealed +
+    Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    /// Zero.
+    const ZERO: Self;
+    /// One.
+    const ONE: Self;
+    /// The absolute value.
+    fn abs(self) -> Self;
+    /// The square root.
+    fn sqrt(self) -> Self;
+    /// Returns `true` if this is NaN.
+    fn is_nan(self) -> bool;
+    /// Negative infinity.
+    const NEG_INFINITY: Self;
+    /// `e` raised to the power of this.
+    fn exp(self) -> Self;
+    /// The natural logarithm.
+    fn ln(self) -> Self;
+    /// Converts an `f64`, rounding to the nearest value.
+    fn from_f64(x: f64) -> Self;
+    /// Converts to an `f64`, exactly.
+    fn to_f64(self) -> f64;
+}
+
+mod private {
+    #[cfg(not(feature = "cblas"))]
+    pub trait Sealed {}
+    #[cfg(feature = "cblas")]
+    pub trait Sealed: ::cblas::Routines {}
+    pub trait SealedInt {}
+}
+
+// BLAS routines take one length for both vectors.
+#[track_caller]
+fn check_lengths(a: usize, b: usize, func: &str) {
+    assert!(a == b, "strided::blas1::{}: mismatched lengths {} and {}", func, a, b);
+}
+
+// returns from the enclosing function if the system BLAS did the
+// operation.
+macro_rules! try_cblas {
+    ($e: expr) => {
+        #[cfg(feature = "cblas")]
+        {
+            if let Some(x) = $e { return x }
+        }
+    }
+}
+
+macro_rules! float_impls {
+    ($($t: ident),*) => {$(
+        impl private::Sealed for $t {}
+        impl Float for $t {
+            const ZERO: $t = 0.0;
+            const ONE: $t = 1.0;
+            #[inline]
+            fn abs(self) -> $t { self.abs() }
+            #[inline]
+            fn sqrt(self) -> $t { self.sqrt() }
+            #[inline]
+            fn is_nan(self) -> bool { self.is_nan() }
+            const NEG_INFINITY: $t = $t::NEG_INFINITY;
+            #[inline]
+            fn exp(self) -> $t { self.exp() }
+            #[inline]
+            fn ln(self) -> $t { self.ln() }
+            #[inline]
+            fn from_f64(x: f64) -> $t { x as $t }
+            #[inline]
+            fn to_f64(self) -> f64 { self as f64 }
+        }
+    )*}
+}
+float_impls!(f32, f64);
+
+// the number of independent accumulators in the sums, enough for
+// LLVM to keep a full vector register of them for either type.
+const LANES: usize = 8;
+// the number of elements of strided vectors copied out at a time, to
+// be summed as contiguous ones.
+pub(crate) const BLOCK: usize = 128;
+
+// sums `f(x, y)` over pairs of elements of two equal-length slices,
+// with independent accumulators so the additions can overlap (and be
+// vectorised).
+#[inline]
+pub(crate) fn sum_slices<T: Float, F: Fn(T, T) -> T>(a: &[T], b: &[T], f: F) -> T {
+    let (a_chunks, a_rest) = a.as_chunks::<LANES>();
+    let (b_chunks, b_rest) = b.as_chunks::<LANES>();
+    let mut acc = [T::ZERO; LANES];
+    for (x, y) in a_chunks.iter().zip(b_chunks) {
+        for k in 0..LANES { acc[k] = acc[k] + f(x[k], y[k]) }
+    }
+    let mut width = LANES;
+    while width > 1 {
+        width /= 2;
+        for k in 0..width { acc[k] = acc[k] + acc[k + width] }
+    }
+    let mut sum = acc[0];
+    for (&x, &y) in a_rest.iter().zip(b_rest) { sum = sum + f(x, y) }
+    sum
+}
+
+// calls `f` on successive equal-length blocks of `x` and `y`, of at
+// most `BLOCK` elements. Vectors that are not both contiguous are
+// gathered into buffers first, which costs little next to the strided
+// loads themselves, and lets `f` run at the speed of a contiguous
+// loop.
+#[inline]
+pub(crate) fn for_blocks<T: Copy, F: FnMut(&[T], &[T])>(mut x: Stride<'_, T>, mut y: Stride<'_, T>,
+                                                          fill: T, mut f: F) {
+    if let (Some(a), Some(b)) = (x.as_slice(), y.as_slice()) {
+        for (a, b) in a.chunks(BLOCK).zip(b.chunks(BLOCK)) { f(a, b) }
+        return
+    }
+    let (mut xs, mut ys) = ([fill; BLOCK], [fill; BLOCK]);
+    while !x.is_empty() {
+        let n = x.len().min(BLOCK);
+        let ((xb, xt), (yb, yt)) = (x.split_at(n), y.split_at(n));
+        for (d, &a) in xs.iter_mut().zip(xb) { *d = a }
+        for (d, &b) in ys.iter_mut().zip(yb) { *d = b }
+        f(&xs[..n], &ys[..n]);
+        x = xt;
+        y = yt;
+    }
+}
+
+/// Returns the dot product of `x` and `y`, the sum of `x[i] * y[i]`.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn dot<T: Float>(x: &Stride<'_, T>, y: &Stride<'_, T>) -> T {
+    check_lengths(x.len(), y.len(), "dot");
+    try_cblas!(T::dot(x, y));
+    match (x.as_slice(), y.as_slice()) {
+        (Some(a), Some(b)) => sum_slices(a, b, |a, b| a * b),
+        _ => {
+            let mut sum = T::ZERO;
+            for_blocks(*x, *y, T::ZERO, |a, b| sum = sum + sum_slices(a, b, |a, b| a * b));
+            sum
+        }
+    }
+}
+
+/// Integer types whose products are summed in a wider type by
+/// `widening_dot`: `i8` and `u8` into 32 bits, and `i16` and `u16`
+/// into 64 bits.
+pub trait Widening: Copy + Default + private::SealedInt {
+    /// The type the products are summed in.
+    type Wide: Copy;
+    #[doc(hidden)]
+    fn __dot_block(a: &[Self], b: &[Self]) -> Self::Wide;
+    #[doc(hidden)]
+    fn __wrapping_add(a: Self::Wide, b: Self::Wide) -> Self::Wide;
+    #[doc(hidden)]
+    fn __checked_add(a: Self::Wide, b: Self::Wide) -> Option<Self::Wide>;
+    #[doc(hidden)]
+    const __ZERO: Self::Wide;
+}
+
+macro_rules! widening_impls {
+    ($($t: ident => $wide: ident),*) => {$(
+        impl private::SealedInt for $t {}
+        impl Widening for $t {
+            type Wide = $wide;
+            // a block of `BLOCK` products cannot overflow, even in the
+            // worst case of `128 * (-128 * -128)` or `128 * (65535 *
+            // 65535)`.
+            #[inline]
+            fn __dot_block(a: &[$t], b: &[$t]) -> $wide {
+                let (a_chunks, a_rest) = a.as_chunks::<LANES>();
+                let (b_chunks, b_rest) = b.as_chunks::<LANES>();
+                let mut acc = [0 as $wide; LANES];
+                for (x, y) in a_chunks.iter().zip(b_chunks) {
+                    for k in 0..LANES { acc[k] += x[k] as $wide * y[k] as $wide }
+                }
+                let mut sum = acc.iter().sum::<$wide>();
+                for (&x, &y) in a_rest.iter().zip(b_rest) { sum += x as $wide * y as $wide }
+                sum
+            }
+            #[inline]
+            fn __wrapping_add(a: $wide, b: $wide) -> $wide { a.wrapping_add(b) }
+            #[inline]
+            fn __checked_add(a: $wide, b: $wide) -> Option<$wide> { a.checked_add(b) }
+            const __ZERO: $wide = 0;
+        }
+    )*}
+}
+widening_impls!(i8 => i32, u8 => u32, i16 => i64, u16 => u64);
+
+/// Returns the dot product of the integers `x` and `y`, with each
+/// product and the sum computed in `T::Wide` (such as `i32` for
+/// `i8`), wrapping around on overflow.
+///
+/// The products themselves never overflow, and neither does the sum
+/// of fewer than 2<sup>17</sup> `i8` products (or 2<sup>33</sup>
+/// `i16` ones). `checked_widening_dot` detects overflow of longer sums.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+///
+/// ```rust
+/// use strided::{Stride, blas1};
+///
+/// // quantised activations, interleaved with another channel.
+/// let act = [100i8, 1, -100, 2, 127, 3];
+/// let weights = [-128i8, 127, 127];
+/// let (act, _) = Stride::new(&act).substrides2();
+/// assert_eq!(blas1::widening_dot(&act, &Stride::new(&weights)), -12800 - 12700 + 16129);
+/// ```
+#[track_caller]
+pub fn widening_dot<T: Widening>(x: &Stride<'_, T>, y: &Stride<'_, T>) -> T::Wide {
+    check_lengths(x.len(), y.len(), "widening_dot");
+    let mut sum = T::__ZERO;
+    for_blocks(*x, *y, T::default(), |a, b| sum = T::__wrapping_add(sum, T::__dot_block(a, b)));
+    sum
+}
+
+/// Returns the dot product of the integers `x` and `y`, like
+/// `widening_dot`, or `None` if the running sum overflows `T::Wide`.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn checked_widening_dot<T: Widening>(x: &Stride<'_, T>, y: &Stride<'_, T>) -> Option<T::Wide> {
+    check_lengths(x.len(), y.len(), "checked_widening_dot");
+    let mut sum = Some(T::__ZERO);
+    for_blocks(*x, *y, T::default(),
+               |a, b| sum = sum.and_then(|s| T::__checked_add(s, T::__dot_block(a, b))));
+    sum
+}
+
+/// Adds `alpha * x` to `y`, elementwise.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn axpy<T: Float>(alpha: T, x: &Stride<'_, T>, mut y: MutStride<'_, T>) {
+    check_lengths(x.len(), y.len(), "axpy");
+    try_cblas!(T::axpy(alpha, x, &mut y));
+    for (&a, b) in ::zip(*x, y.reborrow()) { *b = *b + alpha * a }
+}
+
+/// Multiplies every element of `x` by `alpha`.
+pub fn scal<T: Float>(alpha: T, mut x: MutStride<'_, T>) {
+    try_cblas!(T::scal(alpha, &mut x));
+    for a in x.iter_mut() { *a = *a * alpha }
+}
+
+/// Returns the sum of the absolute values of the elements of `x`.
+pub fn asum<T: Float>(x: &Stride<'_, T>) -> T {
+    try_cblas!(T::asum(x));
+    match x.as_slice() {
+        Some(a) => sum_slices(a, a, |a, _| a.abs()),
+        None => x.iter().fold(T::ZERO, |acc, &a| acc + a.abs()),
+    }
+}
+
+/// Returns the Euclidean norm of `x`, the square root of the sum of
+/// the squares of its elements.
+///
+/// The intermediate values are scaled, so this does not overflow or
+/// underflow unless the result does.
+pub fn nrm2<T: Float>(x: &Stride<'_, T>) -> T {
+    try_cblas!(T::nrm2(x));
+    // `scale * sqrt(ssq)` is the norm of the elements so far, with
+    // `scale` their largest absolute value.
+    let (mut scale, mut ssq) = (T::ZERO, T::ONE);
+    for &a in x {
+        if a == T::ZERO { continue }
+        let a = a.abs();
+        if scale < a {
+            let r = scale / a;
+            ssq = T::ONE + ssq * r * r;
+            scale = a;
+        } else {
+            let r = a / scale;
+            ssq = ssq + r * r;
+        }
+    }
+    scale * ssq.sqrt()
+}
+
+/// Returns the index of the first element of `x` with the largest
+/// absolute value, or `None` if `x` is empty. NaNs are never chosen
+/// over another element, except by a system BLAS.
+pub fn iamax<T: Float>(x: &Stride<'_, T>) -> Option<usize> {
+    if x.is_empty() { return None }
+    try_cblas!(T::iamax(x).map(Some));
+    let mut it = x.iter().map(|a| a.abs()).enumerate();
+    let first = it.next()?;
+    Some(it.fold(first, |best, (i, a)| if a > best.1 || best.1.is_nan() { (i, a) } else { best }).0)
+}
+
+/// Copies the elements of `x` into `y`.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn copy<T: Float>(x: &Stride<'_, T>, mut y: MutStride<'_, T>) {
+    check_lengths(x.len(), y.len(), "copy");
+    try_cblas!(T::copy(x, &mut y));
+    for (&a, b) in ::zip(*x, y.reborrow()) { *b = a }
+}
+
+/// Swaps the elements of `x` with those of `y`.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn swap<T: Float>(mut x: MutStride<'_, T>, mut y: MutStride<'_, T>) {
+    check_lengths(x.len(), y.len(), "swap");
+    try_cblas!(T::swap(&mut x, &mut y));
+    for (a, b) in ::multizip((x.reborrow(), y.reborrow())) { ::std::mem::swap(a, b) }
+}
+
+impl<'a, T: Float> Stride<'a, T> {
+    /// Returns the sum of the elements of `self`, computed by pairwise
+    /// summation over halves, down to blocks that are summed with
+    /// Kahan's compensated summation.
+    ///
+    /// The error grows only logarithmically with the length, where
+    /// that of `self.iter().sum()` grows linearly.
+    ///
+    /// ```rust
+    /// use strided::Stride;
+    ///
+    /// let v = vec![0.1f32; 2_000_000];
+    /// let (left, _) = Stride::new(&v).substrides2();
+    /// assert!((left.sum_stable() - 100_000.0).abs() < 0.01);
+    /// assert!((left.iter().sum::<f32>() - 100_000.0).abs() > 1.0);
+    /// ```
+    pub fn sum_stable(&self) -> T {
+        if self.len() <= BLOCK {
+            let (mut sum, mut c) = (T::ZERO, T::ZERO);
+            for &x in self {
+                let y = x - c;
+                let t = sum + y;
+                c = (t - sum) - y;
+                sum = t;
+            }
+            sum
+        } else {
+            let (l, r) = self.split_at(self.len() / 2);
+            l.sum_stable() + r.sum_stable()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Stride, MutStride};
+    use super::*;
+
+    #[test]
+    fn reductions() {
+        let v = (0..21).map(|i| if i % 2 == 0 { i as f64 } else { -(i as f64) }).collect::<Vec<_>>();
+        let all = Stride::new(&v);
+        let (even, odd) = all.substrides2();
+        assert_eq!(dot(&all, &all), (0..21).map(|i| (i * i) as f64).sum::<f64>());
+        assert_eq!(dot(&even.slice_to(10), &odd), -(0..10).map(|i| (4 * i * i + 2 * i) as f64).sum::<f64>());
+        assert_eq!(asum(&all), 210.0);
+        assert_eq!(asum(&odd), 100.0);
+        assert_eq!(nrm2(&Stride::new(&[3.0f32, 0.0, -4.0])), 5.0);
+        assert_eq!(nrm2(&Stride::new(&[3e300, 4e300])), 5e300);
+        assert_eq!(nrm2(&Stride::new(&[3e-300, -4e-300])), 5e-300);
+        assert_eq!(nrm2::<f64>(&Stride::empty()), 0.0);
+        assert_eq!(iamax(&odd), Some(9));
+        assert_eq!(iamax(&Stride::new(&[1.0, -3.0, 3.0])), Some(1));
+        assert_eq!(iamax(&Stride::new(&[f32::NAN, 1.0, f32::NAN])), Some(1));
+        assert_eq!(iamax::<f32>(&Stride::empty()), None);
+    }
+
+    #[test]
+    fn dot_blocks() {
+        // long enough for several blocks and a partial one, with
+        // small integers so every sum is exact.
+        let v = (0..3 * 300).map(|i| (i % 7) as f32).collect::<Vec<_>>();
+        let mut s = Stride::new(&v).substrides(3);
+        let (a, b, c) = (s.next().unwrap(), s.next().unwrap(), s.next().unwrap());
+        let naive = |x: Stride<'_, f32>, y: Stride<'_, f32>| x.iter().zip(y).map(|(a, b)| a * b).sum::<f32>();
+        for n in [0, 1, 7, 8, 9, 127, 128, 129, 300] {
+            assert_eq!(dot(&a.slice_to(n), &c.slice_to(n)), naive(a.slice_to(n), c.slice_to(n)));
+            assert_eq!(dot(&Stride::new(&v[..n]), &b.slice_to(n)), naive(Stride::new(&v[..n]), b.slice_to(n)));
+        }
+    }
+
+    #[test]
+    fn widening() {
+        let v = (0..600).map(|i| if i % 3 == 0 { -128 } else { 127 }).collect::<Vec<i8>>();
+        let (a, b) = Stride::new(&v).substrides2();
+        let naive = |x: Stride<'_, i8>, y: Stride<'_, i8>| x.iter().zip(y).map(|(&a, &b)| a as i32 * b as i32).sum::<i32>();
+        assert_eq!(widening_dot(&a, &b), naive(a, b));
+        assert_eq!(widening_dot(&Stride::new(&v[..300]), &a), naive(Stride::new(&v[..300]), a));
+        assert_eq!(checked_widening_dot(&a, &a), Some(naive(a, a)));
+        assert_eq!(widening_dot::<u16>(&Stride::empty(), &Stride::empty()), 0);
+
+        // 2^17 products of 2^14 sum to 2^31.
+        let big = vec![-128i8; 1 << 17];
+        let big = Stride::new(&big);
+        assert_eq!(widening_dot(&big, &big), i32::MIN);
+        assert_eq!(checked_widening_dot(&big, &big), None);
+        assert_eq!(checked_widening_dot(&big.slice_from(1), &big.slice_from(1)), Some(i32::MAX - (1 << 14) + 1));
+        let wide = [u16::MAX; 3];
+        assert_eq!(widening_dot(&Stride::new(&wide), &Stride::new(&wide)), 3 * 65535 * 65535);
+    }
+
+    #[test]
+    fn sum_stable() {
+        assert_eq!(Stride::<f64>::empty().sum_stable(), 0.0);
+        assert_eq!(Stride::new(&[1.0, 2.0, 3.5]).sum_stable(), 6.5);
+        // 1 + many small values that are each lost to rounding when
+        // added on their own.
+        let mut v = vec![1e-16f64; 4001];
+        v[0] = 1.0;
+        assert_eq!(Stride::new(&v).sum_stable(), 1.0 + 4000.0 * 1e-16);
+        let (_, odd) = Stride::new(&v).substrides2();
+        assert_eq!(odd.sum_stable(), 2000.0 * 1e-16);
+    }
+
+    #[test]
+    fn updates() {
+        let mut v = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        {
+            let (mut a, mut b) = MutStride::new(&mut v).substrides2_mut();
+            axpy(2.0, &a, b.reborrow());
+            eq!(b.reborrow(), [4.0, 10.0, 16.0]);
+            scal(0.5, a.reborrow());
+            swap(a.reborrow(), b.reborrow());
+            copy(&Stride::new(&[7.0]), b.slice_to_mut(1));
+        }
+        assert_eq!(v, [4.0, 7.0, 10.0, 1.5, 16.0, 2.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::blas1::dot: mismatched lengths 2 and 3")]
+    fn dot_mismatched() {
+        dot(&Stride::new(&[1.0, 2.0]), &Stride::new(&[1.0, 2.0, 3.0]));
+    }
+}
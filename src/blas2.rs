@@ -0,0 +1,173 @@
+//! BLAS level-2 operations, between matrices and vectors.
+//!
+//! The loops are ordered by the layout of the matrix, so that its
+//! elements are read along whichever of its rows or columns is closer
+//! together in memory, and the inner loops are the (vectorised)
+//! `blas1` routines.
+//!
+//! ```rust
+//! use strided::{MatStride, Stride, MutStride, blas2};
+//!
+//! let a = [1.0, 2.0, 3.0,
+//!          4.0, 5.0, 6.0];
+//! // x is interleaved with another vector.
+//! let xs = [1.0, 9.0, 0.0, 9.0, -1.0, 9.0];
+//! let (x, _) = Stride::new(&xs).substrides2();
+//! let mut y = [0.0; 2];
+//! blas2::matvec(&MatStride::new(&a, 2, 3), &x, MutStride::new(&mut y));
+//! assert_eq!(y, [-2.0, -2.0]);
+//! ```
+
+use blas1::{self, Float, BLOCK};
+use {MatStride, MutMatStride, Stride, MutStride};
+
+// `x`, or a contiguous copy of it in `buf` if it is strided and will
+// be read more than once, so that it is gathered only once.
+fn contiguous<'b, T: Copy>(x: &Stride<'b, T>, reads: usize, buf: &'b mut Vec<T>) -> Stride<'b, T> {
+    if x.is_contiguous() || reads <= 1 { return *x }
+    buf.extend(x);
+    Stride::new(buf)
+}
+
+/// Writes the product of the matrix `a` and the vector `x` into `y`,
+/// so `y[i]` is the dot product of row `i` of `a` with `x`.
+///
+/// # Panic
+///
+/// Panics if `x` does not have an element per column of `a`, or `y`
+/// one per row.
+#[track_caller]
+pub fn matvec<T: Float>(a: &MatStride<'_, T>, x: &Stride<'_, T>, mut y: MutStride<'_, T>) {
+    let (rows, cols) = a.shape();
+    assert!(x.len() == cols && y.len() == rows,
+            "strided::blas2::matvec: {}x{} matrix with vectors of {} and {} elements",
+            rows, cols, x.len(), y.len());
+    if a.col_stride() <= a.row_stride() {
+        // a dot product per row.
+        let mut buf = Vec::new();
+        let x = contiguous(x, rows, &mut buf);
+        for (row, y) in a.rows().zip(y.iter_mut()) {
+            *y = blas1::dot(&row, &x);
+        }
+    } else {
+        // a sum of scaled columns, a block of rows at a time so that
+        // the block of `y` stays in cache.
+        y.map_in_place(|y| *y = T::ZERO);
+        for start in (0..rows).step_by(BLOCK) {
+            let end = rows.min(start + BLOCK);
+            let mut y = y.reborrow().slice_mut(start, end);
+            for (col, &x) in a.submatrix(start..end, 0..cols).cols().zip(x) {
+                blas1::axpy(x, &col, y.reborrow());
+            }
+        }
+    }
+}
+
+/// Adds `alpha` times the outer product of `x` and `y` to the matrix
+/// `a`, so `a[(i, j)]` is increased by `alpha * x[i] * y[j]`.
+///
+/// # Panic
+///
+/// Panics if `x` does not have an element per row of `a`, or `y` one
+/// per column.
+///
+/// ```rust
+/// use strided::{MutMatStride, Stride, blas2};
+///
+/// let mut a = [0.0; 6];
+/// let (x, y) = ([1.0, 2.0], [1.0, 0.0, -1.0]);
+/// // update the transpose of a column-major matrix.
+/// let at = MutMatStride::from_parts(&mut a, 0, 3, 2, 1, 3).unwrap();
+/// blas2::ger(2.0, &Stride::new(&y), &Stride::new(&x), at);
+/// assert_eq!(a, [2.0, 0.0, -2.0, 4.0, 0.0, -4.0]);
+/// ```
+#[track_caller]
+pub fn ger<T: Float>(alpha: T, x: &Stride<'_, T>, y: &Stride<'_, T>, a: MutMatStride<'_, T>) {
+    let (rows, cols) = a.shape();
+    assert!(x.len() == rows && y.len() == cols,
+            "strided::blas2::ger: {}x{} matrix with vectors of {} and {} elements",
+            rows, cols, x.len(), y.len());
+    let mut buf = Vec::new();
+    if a.col_stride() <= a.row_stride() {
+        // each row gains a multiple of `y`.
+        let y = contiguous(y, rows, &mut buf);
+        for (row, &x) in a.rows_mut().zip(x) {
+            blas1::axpy(alpha * x, &y, row);
+        }
+    } else {
+        // each column gains a multiple of `x`.
+        let x = contiguous(x, cols, &mut buf);
+        for (col, &y) in a.cols_mut().zip(y) {
+            blas1::axpy(alpha * y, &x, col);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {MatStride, MutMatStride, Stride, MutStride};
+    use super::*;
+
+    // `y = a x` one element at a time.
+    fn naive(a: &MatStride<'_, f64>, x: &[f64]) -> Vec<f64> {
+        (0..a.nrows()).map(|i| (0..a.ncols()).map(|j| a[(i, j)] * x[j]).sum()).collect()
+    }
+
+    #[test]
+    fn layouts() {
+        let v: Vec<f64> = (0..600).map(|i| ((i * 7) % 23) as f64 - 11.0).collect();
+        let x: Vec<f64> = (0..1200).map(|i| (i % 5) as f64).collect();
+        let row_major = MatStride::new(&v[..600], 200, 3);
+        let col_major = MatStride::from_parts(&v, 0, 300, 2, 1, 300).unwrap();
+        let pitched = MatStride::from_parts(&v, 5, 7, 11, 20, 1).unwrap();
+        let strided = MatStride::from_parts(&v, 1, 150, 2, 2, 300).unwrap();
+        for a in [row_major, col_major, pitched, strided, row_major.transpose(), col_major.transpose()] {
+            let (rows, cols) = a.shape();
+            let xs = Stride::new(&x).slice_to(2 * cols).substrides2().0;
+            let mut y = vec![f64::NAN; 2 * rows];
+            matvec(&a, &xs, MutStride::new(&mut y).substrides2_mut().1);
+            let expected = naive(&a, &xs.iter().cloned().collect::<Vec<_>>());
+            assert_eq!(Stride::new(&y).substrides2().1, Stride::new(&expected));
+        }
+        let mut y = [1.0; 2];
+        matvec(&MatStride::new(&[], 2, 0), &Stride::empty(), MutStride::new(&mut y));
+        assert_eq!(y, [0.0; 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::blas2::matvec: 2x3 matrix with vectors of 2 and 2 elements")]
+    fn mismatched() {
+        matvec(&MatStride::new(&[0.0; 6], 2, 3), &Stride::new(&[0.0; 2]), MutStride::new(&mut [0.0; 2]));
+    }
+
+    #[test]
+    fn rank_one() {
+        let x: Vec<f64> = (0..40).map(|i| i as f64 - 3.0).collect();
+        for &(rows, cols, row_stride, col_stride) in &[(5, 7, 9, 1), (5, 7, 1, 6), (1, 7, 1, 2), (6, 1, 3, 1)] {
+            let mut a: Vec<f64> = (0..60).map(|i| i as f64).collect();
+            let before = a.clone();
+            let (xs, ys) = Stride::new(&x).substrides2();
+            let (xs, ys) = (xs.slice_to(rows), ys.slice_to(cols));
+            {
+                let m = MutMatStride::from_parts(&mut a, 2, rows, cols, row_stride, col_stride).unwrap();
+                ger(0.5, &xs, &ys, m);
+            }
+            let old = MatStride::from_parts(&before, 2, rows, cols, row_stride, col_stride).unwrap();
+            let new = MatStride::from_parts(&a, 2, rows, cols, row_stride, col_stride).unwrap();
+            for i in 0..rows {
+                for j in 0..cols {
+                    assert_eq!(new[(i, j)], old[(i, j)] + 0.5 * xs[i] * ys[j]);
+                }
+            }
+            // nothing outside the matrix is touched.
+            let touched = a.iter().zip(&before).filter(|(a, b)| a != b).count();
+            assert!(touched <= rows * cols);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::blas2::ger: 2x3 matrix with vectors of 3 and 2 elements")]
+    fn ger_mismatched() {
+        ger(1.0, &Stride::new(&[0.0; 3]), &Stride::new(&[0.0; 2]), MutMatStride::new(&mut [0.0; 6], 2, 3));
+    }
+}
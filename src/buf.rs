@@ -0,0 +1,109 @@
+use std::mem;
+use {Stride, MutStride, Strided, MutStrided};
+
+/// An owned buffer together with a strided layout over part of it.
+///
+/// This describes the elements at indices `offset`, `offset +
+/// stride`, ..., `offset + (len - 1) * stride` of `data`, and is
+/// mainly useful for generating strided inputs for tests and fuzzing,
+/// where the layout should vary along with the data.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StridedBuf<T> {
+    data: Vec<T>,
+    offset: usize,
+    stride: usize,
+    len: usize,
+}
+
+impl<T> StridedBuf<T> {
+    /// Creates a layout of `len` elements, starting at `offset` and
+    /// spaced by `stride` elements, over `data`.
+    ///
+    /// Returns `None` if `stride` is zero, if `T` is zero-sized, or
+    /// if the layout does not fit within `data`.
+    pub fn new(data: Vec<T>, offset: usize, stride: usize, len: usize) -> Option<StridedBuf<T>> {
+        if stride == 0 || mem::size_of::<T>() == 0 { return None }
+        let fits = if len == 0 {
+            offset <= data.len()
+        } else {
+            (len - 1).checked_mul(stride)
+                .and_then(|x| x.checked_add(offset))
+                .is_some_and(|last| last < data.len())
+        };
+        if fits {
+            Some(StridedBuf { data, offset, stride, len })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the underlying buffer.
+    pub fn data(&self) -> &[T] { &self.data }
+    /// Returns the index in `data()` of the first element of the
+    /// layout.
+    pub fn offset(&self) -> usize { self.offset }
+    /// Returns the stride of the layout, in elements.
+    pub fn stride(&self) -> usize { self.stride }
+    /// Returns the number of elements in the layout.
+    pub fn len(&self) -> usize { self.len }
+    /// Returns `true` if the layout has no elements.
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Decomposes `self` into `(data, offset, stride, len)`.
+    pub fn into_parts(self) -> (Vec<T>, usize, usize, usize) {
+        (self.data, self.offset, self.stride, self.len)
+    }
+
+    /// Returns a strided slice of the elements of the layout.
+    pub fn as_stride(&self) -> Stride<'_, T> {
+        Stride::new(&self.data[self.offset..]).substride(self.stride, 0).slice_to(self.len)
+    }
+
+    /// Returns a mutable strided slice of the elements of the layout.
+    pub fn as_stride_mut(&mut self) -> MutStride<'_, T> {
+        MutStride::new(&mut self.data[self.offset..])
+            .substride_mut(self.stride, 0)
+            .slice_to_mut(self.len)
+    }
+}
+
+impl<T> Strided for StridedBuf<T> {
+    type Elem = T;
+    fn as_stride(&self) -> Stride<'_, T> { StridedBuf::as_stride(self) }
+    fn stride(&self) -> usize { self.stride }
+}
+impl<T> MutStrided for StridedBuf<T> {
+    fn as_stride_mut(&mut self) -> MutStride<'_, T> { StridedBuf::as_stride_mut(self) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StridedBuf;
+
+    #[test]
+    fn new() {
+        let b = StridedBuf::new(vec![1u16, 2, 3, 4, 5, 6, 7], 1, 3, 2).unwrap();
+        assert_eq!(b.as_stride(), [2, 5]);
+        assert_eq!(b.as_stride().stride(), 3);
+        let b = StridedBuf::new(vec![1u16, 2, 3, 4, 5, 6, 7], 0, 3, 3).unwrap();
+        assert_eq!(b.as_stride(), [1, 4, 7]);
+        assert!(StridedBuf::new(vec![1u16, 2, 3, 4, 5, 6, 7], 1, 3, 3).is_none());
+
+        assert!(StridedBuf::new(vec![1u16, 2, 3], 1, 3, 2).is_none());
+        assert!(StridedBuf::new(vec![1u16, 2, 3], 0, 0, 1).is_none());
+        assert!(StridedBuf::new(vec![1u16, 2, 3], 4, 1, 0).is_none());
+        assert!(StridedBuf::new(vec![(); 3], 0, 1, 1).is_none());
+        assert!(StridedBuf::new(vec![1u16; 3], 1, usize::MAX, 2).is_none());
+
+        let mut b = StridedBuf::new(vec![1u16, 2, 3], 3, 5, 0).unwrap();
+        assert_eq!(b.as_stride(), [0u16; 0]);
+        assert_eq!(b.as_stride_mut(), [0u16; 0]);
+    }
+
+    #[test]
+    fn as_stride_mut() {
+        let mut b = StridedBuf::new(vec![0u16; 6], 2, 2, 2).unwrap();
+        for x in b.as_stride_mut() { *x = 1 }
+        assert_eq!(b.into_parts(), (vec![0, 0, 1, 0, 1, 0], 2, 2, 2));
+    }
+}
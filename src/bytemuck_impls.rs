@@ -0,0 +1,140 @@
+use std::mem;
+use bytemuck::{AnyBitPattern, Pod};
+use {Stride, MutStride, StrideError};
+
+// checks that `count` values of `T`, `byte_stride` bytes apart from
+// `byte_offset`, lie within `len` bytes.
+fn check_attribute<T>(len: usize, byte_offset: usize, byte_stride: usize, count: usize)
+                      -> Result<(), StrideError> {
+    if count > 1 && byte_stride == 0 { return Err(StrideError::ZeroStride) }
+    let end = match count {
+        0 => Some(byte_offset),
+        _ => (count - 1).checked_mul(byte_stride)
+            .and_then(|x| x.checked_add(byte_offset))
+            .and_then(|x| x.checked_add(mem::size_of::<T>())),
+    };
+    match end {
+        Some(end) if end <= len => Ok(()),
+        _ => Err(StrideError::OutOfBounds),
+    }
+}
+
+impl<'a, T: AnyBitPattern> Stride<'a, T> {
+    /// Creates a strided slice of `count` values read from `bytes`,
+    /// starting at `byte_offset` and `byte_stride` bytes apart, like
+    /// an attribute of an interleaved vertex buffer (with glTF's
+    /// `byteOffset`, `byteStride` and `count`).
+    ///
+    /// Returns `Err(ZeroStride)` if `byte_stride` is zero and `count >
+    /// 1`, `Err(OutOfBounds)` if the values do not fit in `bytes`, and
+    /// `Err(Misaligned)` if they are not aligned for `T` or
+    /// `byte_stride` is not a multiple of its size. In the last case,
+    /// `components_from_bytes` can still view each component
+    /// separately.
+    ///
+    /// ```rust
+    /// # extern crate bytemuck;
+    /// # extern crate strided;
+    /// # fn main() {
+    /// use strided::Stride;
+    ///
+    /// // a position and a normal per vertex.
+    /// let vertices = [1.0f32, 2.0, 3.0, 0.0, 0.0, 1.0,
+    ///                 4.0, 5.0, 6.0, 0.0, 1.0, 0.0];
+    /// let bytes: &[u8] = bytemuck::cast_slice(&vertices);
+    /// let normals = Stride::<[f32; 3]>::from_bytes(bytes, 12, 24, 2).unwrap();
+    /// assert_eq!(normals, [[0.0, 0.0, 1.0], [0.0, 1.0, 0.0]]);
+    /// # }
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8], byte_offset: usize, byte_stride: usize, count: usize)
+                      -> Result<Stride<'a, T>, StrideError> {
+        check_attribute::<T>(bytes.len(), byte_offset, byte_stride, count)?;
+        if count == 0 { return Ok(Stride::empty()) }
+        let data = bytes[byte_offset..].as_ptr() as *const T;
+        unsafe { Stride::from_byte_stride(data, count, byte_stride as isize) }
+    }
+
+    /// Creates a strided slice of each of the `N` components of
+    /// `count` values of type `[T; N]` read from `bytes`, like
+    /// `from_bytes::<[T; N]>`. This only requires `byte_stride` to be
+    /// a multiple of the size of `T`, as is the case for every glTF
+    /// vertex attribute.
+    ///
+    /// ```rust
+    /// # extern crate bytemuck;
+    /// # extern crate strided;
+    /// # fn main() {
+    /// use strided::Stride;
+    ///
+    /// // a position and a texture coordinate per vertex.
+    /// let vertices = [1.0f32, 2.0, 3.0, 0.0, 0.0,
+    ///                 4.0, 5.0, 6.0, 1.0, 1.0];
+    /// let bytes: &[u8] = bytemuck::cast_slice(&vertices);
+    /// let [x, y, z] = Stride::<f32>::components_from_bytes::<3>(bytes, 0, 20, 2).unwrap();
+    /// assert_eq!((x, y, z), (Stride::new(&[1.0, 4.0]), Stride::new(&[2.0, 5.0]),
+    ///                        Stride::new(&[3.0, 6.0])));
+    /// # }
+    /// ```
+    pub fn components_from_bytes<const N: usize>(bytes: &'a [u8], byte_offset: usize,
+                                                 byte_stride: usize, count: usize)
+                                                 -> Result<[Stride<'a, T>; N], StrideError> {
+        check_attribute::<[T; N]>(bytes.len(), byte_offset, byte_stride, count)?;
+        let mut components = [Stride::empty(); N];
+        for (k, c) in components.iter_mut().enumerate() {
+            *c = Stride::from_bytes(bytes, byte_offset + k * mem::size_of::<T>(), byte_stride, count)?;
+        }
+        Ok(components)
+    }
+}
+
+impl<'a, T: Pod> MutStride<'a, T> {
+    /// Creates a mutable strided slice of `count` values in `bytes`,
+    /// starting at `byte_offset` and `byte_stride` bytes apart, like
+    /// `Stride::from_bytes`.
+    pub fn from_bytes_mut(bytes: &'a mut [u8], byte_offset: usize, byte_stride: usize, count: usize)
+                          -> Result<MutStride<'a, T>, StrideError> {
+        check_attribute::<T>(bytes.len(), byte_offset, byte_stride, count)?;
+        if count == 0 { return Ok(MutStride::empty()) }
+        let data = bytes[byte_offset..].as_mut_ptr() as *mut T;
+        unsafe { MutStride::from_byte_stride(data, count, byte_stride as isize) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck;
+    use {Stride, MutStride};
+    use StrideError::*;
+
+    #[test]
+    fn from_bytes() {
+        // position, normal and uv per vertex.
+        let mut v = (0..24).map(|i| i as f32).collect::<Vec<_>>();
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut v);
+        {
+            let pos = Stride::<[f32; 4]>::from_bytes(bytes, 0, 32, 3).unwrap();
+            assert_eq!(pos, [[0.0, 1.0, 2.0, 3.0], [8.0, 9.0, 10.0, 11.0], [16.0, 17.0, 18.0, 19.0]]);
+            let [x, y, z] = Stride::<f32>::components_from_bytes::<3>(bytes, 0, 32, 3).unwrap();
+            assert_eq!((x, y, z), (Stride::new(&[0.0, 8.0, 16.0]), Stride::new(&[1.0, 9.0, 17.0]),
+                                   Stride::new(&[2.0, 10.0, 18.0])));
+            assert_eq!(Stride::<f32>::components_from_bytes::<3>(bytes, 84, 32, 3), Err(OutOfBounds));
+            assert_eq!(Stride::<f32>::components_from_bytes::<3>(bytes, 2, 32, 3), Err(Misaligned));
+            assert_eq!(Stride::<[f32; 2]>::from_bytes(bytes, 24, 32, 3).unwrap(),
+                       [[6.0, 7.0], [14.0, 15.0], [22.0, 23.0]]);
+            assert_eq!(Stride::<f32>::from_bytes(bytes, 16, 32, 3).unwrap(), [4.0, 12.0, 20.0]);
+            assert_eq!(Stride::<f32>::from_bytes(bytes, 96, 32, 0).unwrap(), [0.0; 0]);
+
+            assert_eq!(Stride::<[f32; 3]>::from_bytes(bytes, 0, 32, 3), Err(Misaligned));
+            assert_eq!(Stride::<f32>::from_bytes(bytes, 2, 32, 3), Err(Misaligned));
+            assert_eq!(Stride::<f32>::from_bytes(bytes, 0, 0, 3), Err(ZeroStride));
+            assert_eq!(Stride::<[f32; 4]>::from_bytes(bytes, 0, 32, 4), Err(OutOfBounds));
+            assert_eq!(Stride::<[f32; 2]>::from_bytes(bytes, 28, 32, 3), Err(OutOfBounds));
+            assert_eq!(Stride::<f32>::from_bytes(bytes, 97, 32, 0), Err(OutOfBounds));
+            assert_eq!(Stride::<f32>::from_bytes(bytes, 0, usize::MAX, 3), Err(OutOfBounds));
+        }
+        for uv in MutStride::<[f32; 2]>::from_bytes_mut(bytes, 24, 32, 3).unwrap() {
+            *uv = [0.0, 1.0];
+        }
+        assert_eq!(v[22..], [0.0, 1.0]);
+    }
+}
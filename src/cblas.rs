@@ -0,0 +1,103 @@
+// Dispatching `blas1` to a system CBLAS, which must be linked by the
+// final binary (for example with `-l openblas` or the `blas-src`
+// crate).
+
+use std::convert::TryFrom;
+use std::os::raw::c_int;
+use {Stride, MutStride};
+
+extern "C" {
+    fn cblas_sdot(n: c_int, x: *const f32, incx: c_int, y: *const f32, incy: c_int) -> f32;
+    fn cblas_ddot(n: c_int, x: *const f64, incx: c_int, y: *const f64, incy: c_int) -> f64;
+    fn cblas_saxpy(n: c_int, alpha: f32, x: *const f32, incx: c_int, y: *mut f32, incy: c_int);
+    fn cblas_daxpy(n: c_int, alpha: f64, x: *const f64, incx: c_int, y: *mut f64, incy: c_int);
+    fn cblas_sscal(n: c_int, alpha: f32, x: *mut f32, incx: c_int);
+    fn cblas_dscal(n: c_int, alpha: f64, x: *mut f64, incx: c_int);
+    fn cblas_sasum(n: c_int, x: *const f32, incx: c_int) -> f32;
+    fn cblas_dasum(n: c_int, x: *const f64, incx: c_int) -> f64;
+    fn cblas_snrm2(n: c_int, x: *const f32, incx: c_int) -> f32;
+    fn cblas_dnrm2(n: c_int, x: *const f64, incx: c_int) -> f64;
+    fn cblas_isamax(n: c_int, x: *const f32, incx: c_int) -> usize;
+    fn cblas_idamax(n: c_int, x: *const f64, incx: c_int) -> usize;
+    fn cblas_scopy(n: c_int, x: *const f32, incx: c_int, y: *mut f32, incy: c_int);
+    fn cblas_dcopy(n: c_int, x: *const f64, incx: c_int, y: *mut f64, incy: c_int);
+    fn cblas_sswap(n: c_int, x: *mut f32, incx: c_int, y: *mut f32, incy: c_int);
+    fn cblas_dswap(n: c_int, x: *mut f64, incx: c_int, y: *mut f64, incy: c_int);
+}
+
+/// A strided vector as a BLAS routine takes it, `(x, n, incx)`, or
+/// `None` if the length or stride does not fit in a `c_int`, in which
+/// case the pure-Rust code is used instead.
+fn vector<T>(s: &Stride<'_, T>) -> Option<(*const T, c_int, c_int)> {
+    match (c_int::try_from(s.len()), c_int::try_from(s.stride())) {
+        (Ok(n), Ok(inc)) => Some((s.as_ptr(), n, inc)),
+        _ => None,
+    }
+}
+fn vector_mut<T>(s: &mut MutStride<'_, T>) -> Option<(*mut T, c_int, c_int)> {
+    vector(&**s).map(|(_, n, inc)| (s.as_mut_ptr(), n, inc))
+}
+
+/// The level-1 routines for one element type. Each returns `None`,
+/// without doing anything, if an argument cannot be passed to BLAS.
+/// The lengths must already have been checked to be equal.
+pub trait Routines: Sized {
+    fn dot(x: &Stride<'_, Self>, y: &Stride<'_, Self>) -> Option<Self>;
+    fn axpy(alpha: Self, x: &Stride<'_, Self>, y: &mut MutStride<'_, Self>) -> Option<()>;
+    fn scal(alpha: Self, x: &mut MutStride<'_, Self>) -> Option<()>;
+    fn asum(x: &Stride<'_, Self>) -> Option<Self>;
+    fn nrm2(x: &Stride<'_, Self>) -> Option<Self>;
+    fn iamax(x: &Stride<'_, Self>) -> Option<usize>;
+    fn copy(x: &Stride<'_, Self>, y: &mut MutStride<'_, Self>) -> Option<()>;
+    fn swap(x: &mut MutStride<'_, Self>, y: &mut MutStride<'_, Self>) -> Option<()>;
+}
+
+macro_rules! routines {
+    ($t: ident: $dot: ident, $axpy: ident, $scal: ident, $asum: ident, $nrm2: ident,
+     $iamax: ident, $copy: ident, $swap: ident) => {
+        // the pointers come from live strided slices of length `n`
+        // and increment `inc`, which is all BLAS reads or writes.
+        impl Routines for $t {
+            fn dot(x: &Stride<'_, $t>, y: &Stride<'_, $t>) -> Option<$t> {
+                let ((x, n, incx), (y, _, incy)) = (vector(x)?, vector(y)?);
+                Some(unsafe { $dot(n, x, incx, y, incy) })
+            }
+            fn axpy(alpha: $t, x: &Stride<'_, $t>, y: &mut MutStride<'_, $t>) -> Option<()> {
+                let ((x, n, incx), (y, _, incy)) = (vector(x)?, vector_mut(y)?);
+                unsafe { $axpy(n, alpha, x, incx, y, incy) };
+                Some(())
+            }
+            fn scal(alpha: $t, x: &mut MutStride<'_, $t>) -> Option<()> {
+                let (x, n, incx) = vector_mut(x)?;
+                unsafe { $scal(n, alpha, x, incx) };
+                Some(())
+            }
+            fn asum(x: &Stride<'_, $t>) -> Option<$t> {
+                let (x, n, incx) = vector(x)?;
+                Some(unsafe { $asum(n, x, incx) })
+            }
+            fn nrm2(x: &Stride<'_, $t>) -> Option<$t> {
+                let (x, n, incx) = vector(x)?;
+                Some(unsafe { $nrm2(n, x, incx) })
+            }
+            fn iamax(x: &Stride<'_, $t>) -> Option<usize> {
+                let (x, n, incx) = vector(x)?;
+                Some(unsafe { $iamax(n, x, incx) })
+            }
+            fn copy(x: &Stride<'_, $t>, y: &mut MutStride<'_, $t>) -> Option<()> {
+                let ((x, n, incx), (y, _, incy)) = (vector(x)?, vector_mut(y)?);
+                unsafe { $copy(n, x, incx, y, incy) };
+                Some(())
+            }
+            fn swap(x: &mut MutStride<'_, $t>, y: &mut MutStride<'_, $t>) -> Option<()> {
+                let ((x, n, incx), (y, _, incy)) = (vector_mut(x)?, vector_mut(y)?);
+                unsafe { $swap(n, x, incx, y, incy) };
+                Some(())
+            }
+        }
+    }
+}
+routines!(f32: cblas_sdot, cblas_saxpy, cblas_sscal, cblas_sasum, cblas_snrm2,
+          cblas_isamax, cblas_scopy, cblas_sswap);
+routines!(f64: cblas_ddot, cblas_daxpy, cblas_dscal, cblas_dasum, cblas_dnrm2,
+          cblas_idamax, cblas_dcopy, cblas_dswap);
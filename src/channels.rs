@@ -0,0 +1,108 @@
+//! Channel planes of `image` pixel buffers.
+//!
+//! An `ImageBuffer` stores its pixels' channels interleaved, so each
+//! channel (red, alpha, ...) is a strided view with a stride of the
+//! pixel's channel count. For a raw interleaved slice with `n`
+//! channels, `Stride::new(data).substrides(n)` gives the same views.
+//!
+//! ```rust
+//! # extern crate image;
+//! # extern crate strided;
+//! # fn main() {
+//! use image::RgbaImage;
+//!
+//! let mut img = RgbaImage::from_pixel(4, 4, image::Rgba([10, 20, 30, 255]));
+//! for a in strided::channels::channel_mut(&mut img, 3) { *a = 0 }
+//! assert_eq!(img.get_pixel(2, 1).0, [10, 20, 30, 0]);
+//! # }
+//! ```
+
+use std::ops::{Deref, DerefMut};
+use image::{ImageBuffer, Pixel};
+use {Stride, MutStride, Substrides, MutSubstrides};
+
+// the container may be longer than the image, so only the prefix
+// holding its pixels is viewed.
+fn subpixels<P, C>(img: &ImageBuffer<P, C>) -> usize
+    where P: Pixel, C: Deref<Target = [P::Subpixel]>
+{
+    img.width() as usize * img.height() as usize * P::CHANNEL_COUNT as usize
+}
+
+/// Returns the channels of `img`, in the order of `P`'s channels.
+pub fn channels<P, C>(img: &ImageBuffer<P, C>) -> Substrides<'_, P::Subpixel>
+    where P: Pixel, C: Deref<Target = [P::Subpixel]>
+{
+    Stride::new(&(**img)[..subpixels(img)]).substrides(P::CHANNEL_COUNT as usize)
+}
+/// Returns the channels of `img` as mutable views, in the order of
+/// `P`'s channels.
+pub fn channels_mut<P, C>(img: &mut ImageBuffer<P, C>) -> MutSubstrides<'_, P::Subpixel>
+    where P: Pixel, C: DerefMut<Target = [P::Subpixel]>
+{
+    let len = subpixels(img);
+    MutStride::new(&mut (**img)[..len]).substrides_mut(P::CHANNEL_COUNT as usize)
+}
+
+/// Returns channel `i` of `img`.
+///
+/// # Panic
+///
+/// Panics if `i` is not less than `P::CHANNEL_COUNT`.
+#[track_caller]
+pub fn channel<P, C>(img: &ImageBuffer<P, C>, i: usize) -> Stride<'_, P::Subpixel>
+    where P: Pixel, C: Deref<Target = [P::Subpixel]>
+{
+    Stride::new(&(**img)[..subpixels(img)]).substride(P::CHANNEL_COUNT as usize, i)
+}
+/// Returns channel `i` of `img` as a mutable view.
+///
+/// # Panic
+///
+/// Panics if `i` is not less than `P::CHANNEL_COUNT`.
+#[track_caller]
+pub fn channel_mut<P, C>(img: &mut ImageBuffer<P, C>, i: usize) -> MutStride<'_, P::Subpixel>
+    where P: Pixel, C: DerefMut<Target = [P::Subpixel]>
+{
+    let len = subpixels(img);
+    MutStride::new(&mut (**img)[..len]).substride_mut(P::CHANNEL_COUNT as usize, i)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{GrayImage, ImageBuffer, Rgb, RgbImage};
+    use super::{channel, channel_mut, channels, channels_mut};
+
+    #[test]
+    fn planes() {
+        let img = RgbImage::from_fn(3, 2, |x, y| Rgb([x as u8, y as u8, 9]));
+        let planes = channels(&img).collect::<Vec<_>>();
+        assert_eq!(planes, [[0, 1, 2, 0, 1, 2], [0, 0, 0, 1, 1, 1], [9; 6]]);
+        assert_eq!(channel(&img, 1), [0, 0, 0, 1, 1, 1]);
+
+        let gray = GrayImage::from_raw(2, 1, vec![5, 6]).unwrap();
+        assert_eq!(channels(&gray).collect::<Vec<_>>(), [[5, 6]]);
+    }
+
+    #[test]
+    fn oversized_container() {
+        let img = ImageBuffer::<Rgb<u16>, _>::from_raw(1, 2, vec![1u16, 2, 3, 4, 5, 6, 7]).unwrap();
+        assert_eq!(channel(&img, 0), [1, 4]);
+    }
+
+    #[test]
+    fn mutation() {
+        let mut img = RgbImage::from_pixel(2, 2, Rgb([100, 100, 100]));
+        for r in channel_mut(&mut img, 0) { *r /= 2 }
+        let mut planes = channels_mut(&mut img);
+        planes.next();
+        for g in planes.next().unwrap() { *g = 0 }
+        assert_eq!(img.get_pixel(1, 1).0, [50, 0, 100]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn channel_out_of_range() {
+        channel(&RgbImage::new(1, 1), 3);
+    }
+}
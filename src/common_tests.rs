@@ -16,11 +16,16 @@ macro_rules! eq {
         assert_eq!(_stride.len(), e.len());
         let mut iter = _stride.$method();
         assert_eq!(iter.size_hint(),(e.len(), Some(e.len())));
+        assert_eq!(iter.len(), e.len());
         let vals = iter.by_ref().map(|s| *s).collect::<Vec<_>>();
-        if vals != e {
+        // comparing slices of the same type means `[]` needs no
+        // annotation, even with extra `PartialEq` impls in scope.
+        fn same<T: PartialEq>(a: &[T], b: &[T]) -> bool { a == b }
+        if !same(&vals, e) {
             panic!("mismatched: {:?}, {:?}", vals, e);
         }
         assert_eq!(iter.size_hint(),(0, Some(0)));
+        assert_eq!(iter.len(), 0);
     }}
 }
 
@@ -68,9 +73,11 @@ macro_rules! get {
 
 
 macro_rules! make_tests {
-    ($substrides2: ident, $substrides: ident,
-     $slice: ident, $slice_to: ident, $slice_from: ident,
-     $split_at: ident, $get: ident, $iter: ident,
+    ($substrides2: ident, $substrides3: ident, $substrides4: ident,
+     $substrides: ident, $substride: ident,
+     $slice: ident, $slice_to: ident, $slice_from: ident, $slice_clamped: ident,
+     $slice_unchecked: ident, $split_at: ident, $split_at_unchecked: ident,
+     $take_first: ident, $take_last: ident, $take_prefix: ident, $take_suffix: ident, $get: ident, $iter: ident,
      $($mut_:tt)*) => {
         #[test]
         #[should_panic]
@@ -80,6 +87,21 @@ macro_rules! make_tests {
             Stride::new(v);
         }
 
+        #[test]
+        fn empty() {
+            const EMPTY: Stride<'static, u16> = Stride::empty();
+            let mut e = EMPTY;
+            eq!(e.reborrow(), []);
+            assert_eq!(e.stride(), 1);
+            assert!(e.$get(0).is_none());
+            let (l, r) = e.$substrides2();
+            eq!(l, []);
+            eq!(r, []);
+
+            let d: Stride<u16> = Default::default();
+            eq!(d, []);
+        }
+
         #[test]
         fn stride_len() {
             let v = &mut [1u16, 2, 3, 4, 5];
@@ -129,6 +151,18 @@ macro_rules! make_tests {
                        "[]");
         }
 
+        #[test]
+        fn display() {
+            let v = &mut [1u16, 2, 3, 4, 5];
+            let (l, r) = Stride::new(v).$substrides2();
+            assert_eq!(format!("{}", l), "[1, 3, 5]");
+            assert_eq!(format!("{:#}", l), "1, 3, 5");
+            assert_eq!(format!("{:>2}", r), "[ 2,  4]");
+            assert_eq!(format!("{}", Stride::<u16>::new(&mut [])), "[]");
+            assert_eq!(format!("{:#}", Stride::<u16>::new(&mut [])), "");
+            assert_eq!(format!("{:.1}", Stride::new(&mut [1.0f64, 2.25])), "[1.0, 2.2]");
+        }
+
         #[test]
         fn comparisons() {
             use std::f64;
@@ -158,6 +192,25 @@ macro_rules! make_tests {
             assert_eq!(s.partial_cmp(&s), None);
         }
 
+        #[test]
+        fn hash() {
+            use std::collections::hash_map::DefaultHasher;
+            use std::hash::{Hash, Hasher};
+            fn hash<H: Hash>(x: &H) -> u64 {
+                let mut h = DefaultHasher::new();
+                x.hash(&mut h);
+                h.finish()
+            }
+
+            let v = &mut [1u16, 2, 3, 4, 2];
+            let w = &mut [1u16, 3, 2];
+            let (l, r) = Stride::new(v).$substrides2();
+            let t = Stride::new(w);
+            assert_eq!(hash(&l), hash(&t));
+            assert!(hash(&r) != hash(&t));
+            assert!(hash(&r.$slice_to(0)) != hash(&t.$slice_to(1)));
+        }
+
         #[test]
         fn slice_split() {
             let v = &mut [1u16, 2, 3, 4, 5, 6, 7];
@@ -188,6 +241,74 @@ macro_rules! make_tests {
             }
         }
 
+        #[test]
+        fn slice_clamped() {
+            let v = &mut [1u16, 2, 3, 4, 5, 6, 7];
+            let s = Stride::new(v);
+            let (mut l, _) = s.$substrides2();
+            eq!(l.reborrow().$slice_clamped(1, 3), [3, 5]);
+            eq!(l.reborrow().$slice_clamped(0, 4), [1, 3, 5, 7]);
+            eq!(l.reborrow().$slice_clamped(2, 100), [5, 7]);
+            eq!(l.reborrow().$slice_clamped(4, 100), []);
+            eq!(l.reborrow().$slice_clamped(100, 200), []);
+            eq!(l.reborrow().$slice_clamped(3, 1), []);
+        }
+
+        #[test]
+        fn unchecked() {
+            let v = &mut [1u16, 2, 3, 4, 5, 6, 7];
+            let s = Stride::new(v);
+            let (mut l, mut r) = s.$substrides2();
+            unsafe {
+                eq!(l.reborrow().$slice_unchecked(1, 3), [3, 5]);
+                eq!(l.reborrow().$slice_unchecked(4, 4), []);
+
+                let (rl, rr) = r.reborrow().$split_at_unchecked(1);
+                eq!(rl, [2]);
+                eq!(rr, [4, 6]);
+                let (rl, rr) = r.reborrow().$split_at_unchecked(3);
+                eq!(rl, [2, 4, 6]);
+                eq!(rr, []);
+            }
+        }
+
+        #[test]
+        fn take() {
+            let v = &mut [1u16, 2, 3, 4, 5, 6, 7];
+            let s = Stride::new(v);
+            let (mut l, _) = s.$substrides2();
+            assert_eq!(l.$take_first().map(|x| *x), Some(1));
+            eq!(l.reborrow(), [3, 5, 7]);
+            assert_eq!(l.$take_last().map(|x| *x), Some(7));
+            eq!(l.reborrow(), [3, 5]);
+
+            assert!(l.$take_prefix(3).is_none());
+            assert!(l.$take_suffix(3).is_none());
+            eq!(l.reborrow(), [3, 5]);
+
+            eq!(l.$take_suffix(1).unwrap(), [5]);
+            eq!(l.reborrow(), [3]);
+            eq!(l.$take_prefix(1).unwrap(), [3]);
+            eq!(l.reborrow(), []);
+
+            assert!(l.$take_first().is_none());
+            assert!(l.$take_last().is_none());
+            eq!(l.$take_prefix(0).unwrap(), []);
+        }
+
+        #[test]
+        fn as_slice() {
+            let v = &mut [1u16, 2, 3, 4, 5];
+            let mut s = Stride::new(v);
+            assert_eq!(s.as_slice(), Some(&[1, 2, 3, 4, 5][..]));
+            assert_eq!(s.reborrow().$slice(1, 3).as_slice(), Some(&[2, 3][..]));
+
+            let (l, r) = s.$substrides2();
+            assert_eq!(l.as_slice(), None);
+            assert_eq!(l.$slice(1, 2).as_slice(), Some(&[3][..]));
+            assert_eq!(r.$slice(2, 2).as_slice(), Some(&[][..]));
+        }
+
         #[test]
         fn iter() {
             let v = &mut [1u16, 2, 3, 4, 5];
@@ -206,6 +327,93 @@ macro_rules! make_tests {
             assert_eq!(n, 3)
         }
 
+        #[test]
+        fn iter_len() {
+            let v = &mut [1u16, 2, 3, 4, 5, 6, 7];
+            let s = Stride::new(v);
+            let (mut l, _) = s.$substrides2();
+            let mut it = l.$iter();
+            assert_eq!(it.len(), 4);
+            it.next();
+            assert_eq!(it.len(), 3);
+            it.next_back();
+            assert_eq!(it.len(), 2);
+            assert_eq!(it.rev().take(5).len(), 2);
+        }
+
+        #[test]
+        fn iter_nth() {
+            let v = &mut [1u16, 2, 3, 4, 5, 6, 7, 8, 9];
+            let s = Stride::new(v);
+            let (mut l, _) = s.$substrides2();
+            assert_eq!(l.$iter().count(), 5);
+            assert_eq!(l.$iter().last().map(|x| *x), Some(9));
+
+            let mut it = l.$iter();
+            assert_eq!(it.nth(1).map(|x| *x), Some(3));
+            assert_eq!(it.nth_back(1).map(|x| *x), Some(7));
+            assert_eq!(it.len(), 1);
+            assert_eq!(it.nth(0).map(|x| *x), Some(5));
+            assert!(it.nth(0).is_none());
+
+            let mut it = l.$iter();
+            assert!(it.nth(5).is_none());
+            assert!(it.next().is_none());
+            let mut it = l.$iter();
+            assert!(it.nth_back(5).is_none());
+            assert!(it.next_back().is_none());
+            assert_eq!(l.$iter().nth(4).map(|x| *x), Some(9));
+        }
+
+        #[test]
+        fn iter_fold() {
+            let v = &mut [1u16, 2, 3, 4, 5, 6, 7, 8, 9];
+            let s = Stride::new(v);
+            let (mut l, _) = s.$substrides2();
+            assert_eq!(l.$iter().fold(0, |a, b| a + *b), 1 + 3 + 5 + 7 + 9);
+            assert_eq!(l.$iter().rfold(vec![], |mut a, b| { a.push(*b); a }),
+                       [9, 7, 5, 3, 1]);
+
+            let mut it = l.$iter();
+            it.next();
+            it.next_back();
+            assert_eq!(it.by_ref().map(|x| *x).sum::<u16>(), 3 + 5 + 7);
+            assert!(it.next().is_none());
+
+            let mut it = l.$iter();
+            assert_eq!(it.position(|x| *x == 5), Some(2));
+            assert_eq!(it.next().map(|x| *x), Some(7));
+            assert_eq!(it.try_fold(0u16, |a, b| a.checked_add(*b)), Some(9));
+            assert!(it.next().is_none());
+
+            let mut n = 0;
+            l.$iter().for_each(|_| n += 1);
+            assert_eq!(n, 5);
+        }
+
+        #[test]
+        fn iter_copied() {
+            let v = &mut [1u16, 2, 3, 4, 5];
+            let s = Stride::new(v);
+            let (l, _) = s.$substrides2();
+            assert_eq!(l.iter_copied().collect::<Vec<_>>(), [1, 3, 5]);
+            assert_eq!(l.iter_cloned().rev().collect::<Vec<_>>(), [5, 3, 1]);
+            assert_eq!(l.iter_copied().len(), 3);
+        }
+
+        #[test]
+        fn fused() {
+            fn check<I: ::std::iter::FusedIterator>(mut it: I) {
+                while it.next().is_some() {}
+                assert!(it.next().is_none());
+                assert!(it.next().is_none());
+            }
+            let v = &mut [1u16, 2, 3, 4, 5];
+            let mut s = Stride::new(v);
+            check(s.$iter());
+            check(s.$substrides(2));
+        }
+
         #[test]
         fn substrides2() {
             substrides2!($substrides2, [1, 2, 3, 4, 5], [1, 3, 5], [2, 4]);
@@ -216,6 +424,30 @@ macro_rules! make_tests {
             substrides2!($substrides2, [], [], []);
         }
 
+        #[test]
+        fn substrides3_4() {
+            let v = &mut [1u16, 2, 3, 4, 5, 6, 7];
+            let mut s = Stride::new(v);
+            let (a, b, c) = s.reborrow().$substrides3();
+            eq!(a, [1, 4, 7]);
+            eq!(b, [2, 5]);
+            assert_eq!(c.stride(), 3);
+            eq!(c, [3, 6]);
+
+            let (a, b, c, d) = s.reborrow().$substrides4();
+            eq!(a, [1, 5]);
+            eq!(b, [2, 6]);
+            eq!(c, [3, 7]);
+            assert_eq!(d.stride(), 4);
+            eq!(d, [4]);
+
+            let (a, b, c, d) = s.$slice_to(2).$substrides4();
+            eq!(a, [1]);
+            eq!(b, [2]);
+            eq!(c, []);
+            eq!(d, []);
+        }
+
         #[test]
         fn substrides() {
             substrides!($substrides, 3, [1, 2, 3, 4, 5, 6, 7], [[1, 4, 7], [2, 5], [3, 6]]);
@@ -231,6 +463,32 @@ macro_rules! make_tests {
             substrides!($substrides, 1, [1, 2, 3], [[1, 2, 3]])
         }
 
+        #[test]
+        fn substride() {
+            let v = &mut [1u16, 2, 3, 4, 5, 6, 7];
+            let mut s = Stride::new(v);
+            eq!(s.reborrow().$substride(3, 0), [1, 4, 7]);
+            eq!(s.reborrow().$substride(3, 2), [3, 6]);
+            eq!(s.reborrow().$substride(1, 0), [1, 2, 3, 4, 5, 6, 7]);
+            eq!(s.reborrow().$substride(10, 6), [7]);
+            eq!(s.reborrow().$substride(10, 9), []);
+            assert_eq!(s.reborrow().$substride(3, 1).stride(), 3);
+
+            let mut it = s.reborrow().$substrides(4);
+            eq!(it.nth(1).unwrap(), [2, 6]);
+            assert_eq!(it.size_hint(), (2, Some(2)));
+            eq!(it.nth(1).unwrap(), [4]);
+            assert!(it.nth(0).is_none());
+            assert!(s.$substrides(4).nth(4).is_none());
+        }
+
+        #[test]
+        #[should_panic]
+        fn substride_out_of_bounds() {
+            let v = &mut [1u16, 2, 3];
+            Stride::new(v).$substride(3, 3);
+        }
+
         #[test]
         fn get() {
             let v: &mut [u16] = &mut [1, 2, 3, 4, 5, 6];
@@ -0,0 +1,96 @@
+use std::cmp::Ordering;
+use {Stride, MutStride, ConstStride, MutConstStride};
+
+macro_rules! cmp_impls {
+    ($Stride: ident $(, $S: ident)*) => {
+        impl<'a, A: PartialEq<B>, B $(, const $S: usize)*> PartialEq<[B]>
+            for $Stride<'a, A $(, $S)*>
+        {
+            fn eq(&self, other: &[B]) -> bool {
+                if let Some(s) = self.as_slice() { return s == other }
+                self.len() == other.len() && self.iter().eq(other.iter())
+            }
+        }
+        impl<'a, 'b, A: PartialEq<B>, B $(, const $S: usize)*> PartialEq<&'b [B]>
+            for $Stride<'a, A $(, $S)*>
+        {
+            fn eq(&self, other: &&'b [B]) -> bool { *self == **other }
+        }
+        impl<'a, A: PartialEq<B>, B, const N: usize $(, const $S: usize)*> PartialEq<[B; N]>
+            for $Stride<'a, A $(, $S)*>
+        {
+            fn eq(&self, other: &[B; N]) -> bool { *self == other[..] }
+        }
+        impl<'a, A: PartialEq<B>, B $(, const $S: usize)*> PartialEq<Vec<B>>
+            for $Stride<'a, A $(, $S)*>
+        {
+            fn eq(&self, other: &Vec<B>) -> bool { *self == other[..] }
+        }
+
+        impl<'a, T: PartialOrd $(, const $S: usize)*> PartialOrd<[T]> for $Stride<'a, T $(, $S)*> {
+            fn partial_cmp(&self, other: &[T]) -> Option<Ordering> {
+                if let Some(s) = self.as_slice() { return s.partial_cmp(other) }
+                self.iter().partial_cmp(other.iter())
+            }
+        }
+        impl<'a, 'b, T: PartialOrd $(, const $S: usize)*> PartialOrd<&'b [T]>
+            for $Stride<'a, T $(, $S)*>
+        {
+            fn partial_cmp(&self, other: &&'b [T]) -> Option<Ordering> {
+                self.partial_cmp(*other)
+            }
+        }
+        impl<'a, T: PartialOrd, const N: usize $(, const $S: usize)*> PartialOrd<[T; N]>
+            for $Stride<'a, T $(, $S)*>
+        {
+            fn partial_cmp(&self, other: &[T; N]) -> Option<Ordering> {
+                self.partial_cmp(&other[..])
+            }
+        }
+        impl<'a, T: PartialOrd $(, const $S: usize)*> PartialOrd<Vec<T>>
+            for $Stride<'a, T $(, $S)*>
+        {
+            fn partial_cmp(&self, other: &Vec<T>) -> Option<Ordering> {
+                self.partial_cmp(&other[..])
+            }
+        }
+    }
+}
+
+cmp_impls!(Stride);
+cmp_impls!(MutStride);
+cmp_impls!(ConstStride, S);
+cmp_impls!(MutConstStride, S);
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering::{Less, Equal, Greater};
+    use {Stride, MutStride};
+
+    #[test]
+    fn stride() {
+        let v = [1u16, 2, 3, 4, 5];
+        let (l, _) = Stride::new(&v).substrides2();
+        assert_eq!(l, [1, 3, 5]);
+        assert_eq!(l, &[1, 3, 5][..]);
+        assert_eq!(l, vec![1, 3, 5]);
+        assert!(l == [1, 3, 5][..]);
+        assert!(l != [1, 3]);
+        assert!(l != [1, 3, 4]);
+
+        assert_eq!(l.partial_cmp(&[1, 3, 5]), Some(Equal));
+        assert_eq!(l.partial_cmp(&vec![1, 3]), Some(Greater));
+        assert_eq!(l.partial_cmp(&&[1, 4][..]), Some(Less));
+        assert!(l < [2]);
+    }
+
+    #[test]
+    fn mut_stride() {
+        let v = &mut [1u16, 2, 3, 4, 5];
+        let (_, r) = MutStride::new(v).substrides2_mut();
+        assert_eq!(r, [2, 4]);
+        assert_eq!(r, vec![2, 4]);
+        assert!(r != [2, 4, 6]);
+        assert!(r > [2, 3, 100]);
+    }
+}
@@ -0,0 +1,74 @@
+use std::mem;
+use num_complex::Complex;
+use base::Stride as Base;
+use {Stride, MutStride};
+
+// `Complex<T>` is `#[repr(C)]` with `re` then `im`, so both parts are
+// `T`s at a fixed offset and with the same (byte) stride as the
+// complex numbers themselves.
+
+// the part at `byte_offset` within each complex number of `s`, which
+// must be `0` or `size_of::<T>()`.
+unsafe fn part<'a, T>(s: Base<'a, Complex<T>>, byte_offset: usize) -> Base<'a, T> {
+    let data = (s.as_mut_ptr() as *mut u8).wrapping_add(byte_offset) as *mut T;
+    Base::new(data, s.len(), s.stride() / mem::size_of::<T>())
+}
+
+impl<'a, T> Stride<'a, Complex<T>> {
+    /// Returns strided slices of the real and the imaginary parts of
+    /// the complex numbers of `self`.
+    ///
+    /// ```rust
+    /// # extern crate num_complex;
+    /// # extern crate strided;
+    /// # fn main() {
+    /// use num_complex::Complex;
+    /// use strided::Stride;
+    ///
+    /// let v = [Complex::new(1, 2), Complex::new(3, 4)];
+    /// let (re, im) = Stride::new(&v).re_im();
+    /// assert_eq!((re, im), (Stride::new(&[1, 3]), Stride::new(&[2, 4])));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn re_im(&self) -> (Stride<'a, T>, Stride<'a, T>) {
+        unsafe {
+            (Stride::new_raw(part(self.base, 0)),
+             Stride::new_raw(part(self.base, mem::size_of::<T>())))
+        }
+    }
+}
+
+impl<'a, T> MutStride<'a, Complex<T>> {
+    /// Returns mutable strided slices of the real and the imaginary
+    /// parts of the complex numbers of `self`.
+    #[inline]
+    pub fn re_im_mut(self) -> (MutStride<'a, T>, MutStride<'a, T>) {
+        unsafe {
+            (MutStride::new_raw(part(self.base, 0)),
+             MutStride::new_raw(part(self.base, mem::size_of::<T>())))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+    use {Stride, MutStride};
+
+    #[test]
+    fn re_im() {
+        let mut v = (0..6).map(|i| Complex::new(i as f64, -i as f64)).collect::<Vec<_>>();
+        let (re, im) = Stride::new(&v).substrides2().1.re_im();
+        eq!(re, [1.0, 3.0, 5.0]);
+        eq!(im, [-1.0, -3.0, -5.0]);
+        assert_eq!(re.stride(), 4);
+        eq!(Stride::<Complex<u8>>::empty().re_im().0, []);
+
+        let (re, im) = MutStride::new(&mut v).substrides_mut(3).next().unwrap().re_im_mut();
+        for x in im { *x = 0.0 }
+        for x in re { *x *= 2.0 }
+        assert_eq!(v[..4], [Complex::new(0.0, 0.0), Complex::new(1.0, -1.0),
+                            Complex::new(2.0, -2.0), Complex::new(6.0, 0.0)]);
+    }
+}
@@ -0,0 +1,1572 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::iter::{self, FusedIterator};
+use std::marker;
+use std::mem;
+use std::ops::{AddAssign, Index, IndexMut, Range};
+use std::ptr::{self, NonNull};
+use base::Stride as Base;
+use {Stride, MutStride, Substrides, MutSubstrides, StrideError};
+use {ChunksWithHop, DisplaySeparated, WithParentIndices};
+
+// A `ConstStride` is a `Base` whose stride is always `S` elements, even
+// with at most one element (where a `Base` may have any stride), so
+// that slicing the `Base` keeps it so. Element access and iteration
+// compute offsets from `S` itself, which the compiler sees as a
+// constant, rather than from the stride stored in the `Base`.
+
+struct Layout<T, const S: usize>(marker::PhantomData<T>);
+impl<T, const S: usize> Layout<T, S> {
+    const CHECK: () = {
+        assert!(S != 0, "strided::ConstStride: the stride must be non-zero");
+        assert!(match S.checked_mul(mem::size_of::<T>()) {
+                    Some(bytes) => bytes <= isize::MAX as usize,
+                    None => false,
+                },
+                "strided::ConstStride: the stride must be at most isize::MAX bytes");
+    };
+}
+
+// the `len` elements `S` apart at `data`, which must all lie within a
+// single allocation (or be no elements at all).
+#[inline(always)]
+fn const_base<'a, T, const S: usize>(data: *mut T, len: usize) -> Base<'a, T> {
+    #[allow(clippy::let_unit_value)]
+    let () = Layout::<T, S>::CHECK;
+    Base::new(data, len, S)
+}
+
+// `base`, with its stride stored as `S`, if that is its stride.
+fn with_stride<T, const S: usize>(base: Base<'_, T>) -> Result<Base<'_, T>, StrideError> {
+    let found = base.stride() / mem::size_of::<T>();
+    if base.len() > 1 && found != S {
+        return Err(StrideError::WrongStride { expected: S, found })
+    }
+    Ok(const_base::<T, S>(base.as_mut_ptr(), base.len()))
+}
+
+/// A shared strided slice whose stride is the compile-time constant
+/// `S`, such as one channel of interleaved stereo audio or of RGBA
+/// pixels.
+///
+/// This behaves like a `Stride`, but since the offset of each element
+/// is known up to its index, loops over it can be unrolled and
+/// vectorised, where the same loop over a `Stride` steps by a runtime
+/// stride one element at a time. It has the same methods as `Stride`:
+/// those that keep the stride, such as `slice` and `split_at`, return
+/// `ConstStride`s, while those that multiply it, such as `substrides`
+/// and `chunks_with_hop`, return `Stride`s. `ConstStride::try_from`
+/// converts a `Stride` back.
+///
+/// Using a stride of zero, or of more than `isize::MAX` bytes, fails
+/// to compile:
+///
+/// ```compile_fail
+/// let s = strided::ConstStride::<u8, 0>::new(&[1, 2]);
+/// ```
+///
+/// ```compile_fail
+/// let s = strided::ConstStride::<u64, { usize::MAX / 4 }>::new(&[]);
+/// ```
+///
+/// ```rust
+/// use strided::ConstStride;
+///
+/// let rgba = [10u8, 20, 30, 255, 40, 50, 60, 128];
+/// let alpha = ConstStride::<_, 4>::new(&rgba[3..]);
+/// assert_eq!(alpha, [255, 128]);
+/// assert_eq!(alpha.iter().map(|&a| a as u32).sum::<u32>(), 383);
+/// assert_eq!(alpha.as_stride().stride(), 4);
+/// ```
+#[repr(transparent)]
+pub struct ConstStride<'a, T: 'a, const S: usize> {
+    base: Base<'a, T>,
+}
+impl<'a, T, const S: usize> Copy for ConstStride<'a, T, S> {}
+impl<'a, T, const S: usize> Clone for ConstStride<'a, T, S> {
+    fn clone(&self) -> ConstStride<'a, T, S> { *self }
+}
+
+unsafe impl<'a, T: Sync, const S: usize> Sync for ConstStride<'a, T, S> {}
+unsafe impl<'a, T: Sync, const S: usize> Send for ConstStride<'a, T, S> {}
+
+impl<'a, T, const S: usize> Default for ConstStride<'a, T, S> {
+    fn default() -> ConstStride<'a, T, S> { ConstStride::empty() }
+}
+
+impl<'a, T: Debug, const S: usize> Debug for ConstStride<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Formats like `Stride`: `{:#}` omits the brackets.
+impl<'a, T: Display, const S: usize> Display for ConstStride<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.base, f)
+    }
+}
+
+/// Hashes like the equivalent `Stride`.
+impl<'a, T: Hash, const S: usize> Hash for ConstStride<'a, T, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.base.hash(state)
+    }
+}
+
+impl<'a, T, const S: usize> ConstStride<'a, T, S> {
+    /// Creates a new empty strided slice, not pointing to any data.
+    #[inline(always)]
+    pub fn empty() -> ConstStride<'a, T, S> {
+        ConstStride { base: const_base::<T, S>(NonNull::dangling().as_ptr(), 0) }
+    }
+
+    /// Creates a strided slice of every `S`th element of `data`,
+    /// starting with the first.
+    #[inline]
+    pub fn new(data: &'a [T]) -> ConstStride<'a, T, S> {
+        // the last element is at `(len - 1) * S < data.len()`.
+        let len = data.len().div_ceil(S);
+        ConstStride { base: const_base::<T, S>(data.as_ptr() as *mut T, len) }
+    }
+
+    /// Creates a strided slice of the `len` elements of `data` at
+    /// indices `offset`, `offset + S`, ....
+    ///
+    /// Returns `Err(OutOfBounds)` if the layout does not fit within
+    /// `data`.
+    pub fn from_parts(data: &'a [T], offset: usize, len: usize)
+                      -> Result<ConstStride<'a, T, S>, StrideError> {
+        Stride::from_parts(data, offset, S, len)
+            .map(|s| ConstStride { base: const_base::<T, S>(s.base.as_mut_ptr(), len) })
+    }
+
+    /// Creates a strided slice of `len` elements, `S` elements apart,
+    /// starting at `data`.
+    ///
+    /// Returns `Err(TooLarge)` if the elements would span more than
+    /// `isize::MAX` bytes.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be non-null and aligned, and all `len` elements
+    /// must lie within a single allocation, be initialised, and not be
+    /// mutated for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(data: *const T, len: usize)
+                                 -> Result<ConstStride<'a, T, S>, StrideError> {
+        Stride::from_raw_parts(data, len, S)
+            .map(|_| ConstStride { base: const_base::<T, S>(data as *mut T, len) })
+    }
+
+    #[cold]
+    #[track_caller]
+    fn fail(&self, method: &str, e: StrideError) -> ! {
+        panic!("ConstStride.{}: {} (stride {})", method, e, S)
+    }
+
+    /// Returns the number of elements accessible in `self`.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.base.len() }
+    /// Returns `true` if `self` has no elements.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.base.is_empty() }
+    /// Returns the offset between successive elements, `S`.
+    #[inline(always)]
+    pub const fn stride(&self) -> usize { S }
+    /// Returns a pointer to the first element of this strided slice.
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const T { self.base.as_mut_ptr() as *const T }
+    /// Returns the range of memory spanned by this strided slice, from
+    /// the first element to just past the last.
+    #[inline]
+    pub fn as_ptr_range(&self) -> Range<*const T> { self.as_stride().as_ptr_range() }
+    /// Returns the offset in bytes of the element at index `i` from
+    /// the first element, that is, `i * S * size_of::<T>()`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn byte_offset_of(&self, i: usize) -> usize {
+        if i >= self.len() {
+            self.fail("byte_offset_of", StrideError::IndexOutOfBounds { index: i, len: self.len() })
+        }
+        i * S * mem::size_of::<T>()
+    }
+
+    /// Returns the equivalent `Stride`, with the full lifetime `'a`.
+    #[inline(always)]
+    pub fn as_stride(&self) -> Stride<'a, T> {
+        Stride::new_raw(self.base)
+    }
+
+    /// Returns `true` if any element of `self` shares memory with
+    /// any element of `other`, like `Stride::overlaps`.
+    #[inline]
+    pub fn overlaps<U, const R: usize>(&self, other: &ConstStride<'_, U, R>) -> bool {
+        self.base.overlaps(&other.base)
+    }
+    /// Returns the index of the element of `self` that `x` refers to,
+    /// or `None` if `x` is not one of them, comparing addresses like
+    /// `Stride::index_of_ref`.
+    #[inline]
+    pub fn index_of_ref(&self, x: &T) -> Option<usize> {
+        self.base.index_of(x)
+    }
+    /// Returns `true` if `x` refers to an element of `self`, comparing
+    /// addresses like `index_of_ref`.
+    #[inline]
+    pub fn contains_ref(&self, x: &T) -> bool {
+        self.index_of_ref(x).is_some()
+    }
+    /// Returns `true` if `parts` exactly tile `self`, like
+    /// `Stride::validate_partition`.
+    pub fn validate_partition(&self, parts: &[Stride<'_, T>]) -> bool {
+        self.as_stride().validate_partition(parts)
+    }
+
+    /// Returns `true` if the elements of `self` are adjacent in
+    /// memory, that is, if `S == 1` or if `self` has at most one
+    /// element.
+    #[inline]
+    pub fn is_contiguous(&self) -> bool {
+        self.base.is_contiguous()
+    }
+    /// Returns a conventional slice of the elements of `self` if they
+    /// are adjacent in memory, and `None` otherwise.
+    #[inline]
+    pub fn as_slice(&self) -> Option<&'a [T]> {
+        self.base.as_slice()
+    }
+
+    /// Fuses two strided slices of `self` back into one, if `b` starts
+    /// immediately after the last element of `a`, and the result
+    /// consists only of elements of `self`, like `Stride::try_merge`.
+    ///
+    /// ```rust
+    /// use strided::ConstStride;
+    ///
+    /// let v = [1u8, 2, 3, 4, 5, 6];
+    /// let s = ConstStride::<_, 2>::new(&v);
+    /// let (a, b) = s.split_at(1);
+    /// assert_eq!(s.try_merge(a, b), Some(s));
+    /// assert_eq!(s.try_merge(b, a), None);
+    /// ```
+    pub fn try_merge(&self, a: ConstStride<'a, T, S>, b: ConstStride<'a, T, S>)
+                     -> Option<ConstStride<'a, T, S>> {
+        let merged = self.as_stride().try_merge(a.as_stride(), b.as_stride())?;
+        ConstStride::try_from(merged).ok()
+    }
+
+    /// Breaks this strided slice into two strided slices pointing to
+    /// alternate elements, with stride `2 * S`, like
+    /// `Stride::substrides2`.
+    #[inline]
+    #[track_caller]
+    pub fn substrides2(&self) -> (Stride<'a, T>, Stride<'a, T>) {
+        self.as_stride().substrides2()
+    }
+    /// Breaks this strided slice into three strided slices, each
+    /// pointing to every third element, like `Stride::substrides3`.
+    #[inline]
+    #[track_caller]
+    pub fn substrides3(&self) -> (Stride<'a, T>, Stride<'a, T>, Stride<'a, T>) {
+        self.as_stride().substrides3()
+    }
+    /// Breaks this strided slice into four strided slices, each
+    /// pointing to every fourth element, like `Stride::substrides4`.
+    #[inline]
+    #[track_caller]
+    pub fn substrides4(&self) -> (Stride<'a, T>, Stride<'a, T>, Stride<'a, T>, Stride<'a, T>) {
+        self.as_stride().substrides4()
+    }
+    /// Returns an iterator over `n` strided subslices of `self` each
+    /// pointing to every `n`th element, starting at successive
+    /// offsets, like `Stride::substrides`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `n` is zero.
+    #[inline]
+    #[track_caller]
+    pub fn substrides(&self, n: usize) -> Substrides<'a, T> {
+        match self.try_substrides(n) {
+            Ok(s) => s,
+            Err(e) => self.fail("substrides", e),
+        }
+    }
+    /// Returns an iterator over `n` strided subslices of `self`, like
+    /// `substrides`, or `Err(ZeroStride)` if `n` is zero and
+    /// `Err(TooLarge)` if the new stride overflows.
+    #[inline]
+    pub fn try_substrides(&self, n: usize) -> Result<Substrides<'a, T>, StrideError> {
+        self.as_stride().try_substrides(n)
+    }
+    /// Returns the `i`th of the `n` strided subslices that
+    /// `substrides(n)` would yield.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= n`.
+    #[inline]
+    #[track_caller]
+    pub fn substride(&self, n: usize, i: usize) -> Stride<'a, T> {
+        match self.try_substride(n, i) {
+            Ok(s) => s,
+            Err(e) => self.fail("substride", e),
+        }
+    }
+    /// Returns the `i`th of the `n` strided subslices, like
+    /// `substride`, or an error instead of panicking if `i >= n`.
+    #[inline]
+    pub fn try_substride(&self, n: usize, i: usize) -> Result<Stride<'a, T>, StrideError> {
+        self.as_stride().try_substride(n, i)
+    }
+    /// Returns an iterator over the frames of `frame_len` consecutive
+    /// elements of `self` starting at every `hop`th element, like
+    /// `Stride::chunks_with_hop`. A hop of 1 gives sliding windows and
+    /// a hop of `frame_len` gives adjacent chunks.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `frame_len` or `hop` is zero.
+    #[inline]
+    #[track_caller]
+    pub fn chunks_with_hop(&self, frame_len: usize, hop: usize) -> ChunksWithHop<'a, T> {
+        assert!(frame_len != 0, "ConstStride.chunks_with_hop: frame_len must be non-zero");
+        assert!(hop != 0, "ConstStride.chunks_with_hop: hop must be non-zero");
+        self.as_stride().chunks_with_hop(frame_len, hop)
+    }
+
+    /// Returns a reference to the `n`th element of `self`, or `None`
+    /// if `n` is out-of-bounds.
+    #[inline]
+    pub fn get(&self, n: usize) -> Option<&'a T> {
+        if n < self.len() { unsafe { Some(self.get_unchecked(n)) } } else { None }
+    }
+    /// Returns a reference to the `n`th element of `self`, without
+    /// checking that it is in bounds.
+    ///
+    /// # Safety
+    ///
+    /// `n` must be less than `self.len()`.
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, n: usize) -> &'a T {
+        debug_assert!(n < self.len());
+        &*self.base.as_mut_ptr().add(n * S)
+    }
+    /// Returns the first element, or `None` if `self` is empty.
+    #[inline]
+    pub fn first(&self) -> Option<&'a T> { self.get(0) }
+    /// Returns the last element, or `None` if `self` is empty.
+    #[inline]
+    pub fn last(&self) -> Option<&'a T> {
+        self.len().checked_sub(1).and_then(|n| self.get(n))
+    }
+
+    /// Returns an iterator over references to each successive element
+    /// of `self`.
+    #[inline]
+    pub fn iter(&self) -> ConstItems<'a, T, S> {
+        ConstItems { data: self.base_ptr(), front: 0, back: self.len(), _marker: marker::PhantomData }
+    }
+    /// Returns an iterator over copies of each successive element of
+    /// `self`.
+    #[inline]
+    pub fn iter_copied(&self) -> iter::Copied<ConstItems<'a, T, S>>
+        where T: Copy
+    {
+        self.iter().copied()
+    }
+    /// Returns an iterator over clones of each successive element of
+    /// `self`.
+    #[inline]
+    pub fn iter_cloned(&self) -> iter::Cloned<ConstItems<'a, T, S>>
+        where T: Clone
+    {
+        self.iter().cloned()
+    }
+    /// Returns an object that formats the elements of `self` with
+    /// `Display`, separated by `sep`, like
+    /// `Stride::display_separated`.
+    #[inline]
+    pub fn display_separated<'s>(&self, sep: &'s str) -> DisplaySeparated<'a, 's, T> {
+        self.as_stride().display_separated(sep)
+    }
+    /// Returns an iterator over pairs `(i, x)` where `x` is each
+    /// successive element of `self` and `i` is the index of `x` in
+    /// `parent`, like `Stride::iter_with_parent_indices`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the elements of `self` do not lie within `parent`.
+    #[track_caller]
+    pub fn iter_with_parent_indices(&self, parent: &[T]) -> WithParentIndices<'a, T> {
+        self.as_stride().iter_with_parent_indices(parent)
+    }
+
+    #[inline(always)]
+    fn base_ptr(&self) -> NonNull<T> {
+        unsafe { NonNull::new_unchecked(self.base.as_mut_ptr()) }
+    }
+
+    /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive).
+    ///
+    /// # Panic
+    ///
+    /// Panics if `from > to` or if `to > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn slice(&self, from: usize, to: usize) -> ConstStride<'a, T, S> {
+        match self.try_slice(from, to) {
+            Ok(s) => s,
+            Err(e) => self.fail("slice", e),
+        }
+    }
+    /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive), or
+    /// `Err(InvalidRange)` if `from > to` and `Err(IndexOutOfBounds)`
+    /// if `to > self.len()`.
+    #[inline]
+    pub fn try_slice(&self, from: usize, to: usize) -> Result<ConstStride<'a, T, S>, StrideError> {
+        self.base.try_slice(from, to).map(|base| ConstStride { base })
+    }
+    /// Returns a strided slice containing only the elements from
+    /// index `from` (inclusive), or an error if `from > self.len()`.
+    #[inline]
+    pub fn try_slice_from(&self, from: usize) -> Result<ConstStride<'a, T, S>, StrideError> {
+        self.base.try_slice_from(from).map(|base| ConstStride { base })
+    }
+    /// Returns a strided slice containing only the elements to index
+    /// `to` (exclusive), or an error if `to > self.len()`.
+    #[inline]
+    pub fn try_slice_to(&self, to: usize) -> Result<ConstStride<'a, T, S>, StrideError> {
+        self.try_slice(0, to)
+    }
+    /// Returns a strided slice containing only the elements from
+    /// index `from` onwards.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `from > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn slice_from(&self, from: usize) -> ConstStride<'a, T, S> {
+        match self.try_slice_from(from) {
+            Ok(s) => s,
+            Err(e) => self.fail("slice_from", e),
+        }
+    }
+    /// Returns a strided slice containing only the elements up to
+    /// index `to`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `to > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn slice_to(&self, to: usize) -> ConstStride<'a, T, S> {
+        match self.try_slice_to(to) {
+            Ok(s) => s,
+            Err(e) => self.fail("slice_to", e),
+        }
+    }
+    /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive), after clamping
+    /// both to `self.len()`, so that this never panics.
+    #[inline]
+    pub fn slice_clamped(&self, from: usize, to: usize) -> ConstStride<'a, T, S> {
+        ConstStride { base: self.base.slice_clamped(from, to) }
+    }
+    /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive), without
+    /// checking the bounds.
+    ///
+    /// # Safety
+    ///
+    /// `from <= to` and `to <= self.len()` must hold, otherwise the
+    /// result points outside of `self`.
+    #[inline]
+    pub unsafe fn slice_unchecked(&self, from: usize, to: usize) -> ConstStride<'a, T, S> {
+        ConstStride { base: self.base.slice_unchecked(from, to) }
+    }
+    /// Returns two strided slices, the first with elements up to
+    /// `idx` and the second with elements from `idx`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `idx > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn split_at(&self, idx: usize) -> (ConstStride<'a, T, S>, ConstStride<'a, T, S>) {
+        match self.try_split_at(idx) {
+            Ok(s) => s,
+            Err(e) => self.fail("split_at", e),
+        }
+    }
+    /// Returns two strided slices split at `idx`, like `split_at`, or
+    /// `Err(IndexOutOfBounds)` if `idx > self.len()`.
+    #[inline]
+    pub fn try_split_at(&self, idx: usize)
+                        -> Result<(ConstStride<'a, T, S>, ConstStride<'a, T, S>), StrideError> {
+        let (l, r) = self.base.try_split_at(idx)?;
+        Ok((ConstStride { base: l }, ConstStride { base: r }))
+    }
+    /// Returns two strided slices split at `idx`, without checking the
+    /// bounds.
+    ///
+    /// # Safety
+    ///
+    /// `idx <= self.len()` must hold, otherwise the second slice
+    /// points outside of `self`.
+    #[inline]
+    pub unsafe fn split_at_unchecked(&self, idx: usize)
+                                     -> (ConstStride<'a, T, S>, ConstStride<'a, T, S>) {
+        let (l, r) = self.base.split_at_unchecked(idx);
+        (ConstStride { base: l }, ConstStride { base: r })
+    }
+
+    /// Removes the first element from `self` and returns a reference
+    /// to it, or returns `None` if `self` is empty.
+    #[inline]
+    pub fn take_first(&mut self) -> Option<&'a T> {
+        self.take_prefix(1).and_then(|s| s.get(0))
+    }
+    /// Removes the last element from `self` and returns a reference
+    /// to it, or returns `None` if `self` is empty.
+    #[inline]
+    pub fn take_last(&mut self) -> Option<&'a T> {
+        self.take_suffix(1).and_then(|s| s.get(0))
+    }
+    /// Removes the first `n` elements from `self` and returns them as
+    /// a strided slice, or returns `None` (leaving `self` untouched)
+    /// if `n > self.len()`.
+    #[inline]
+    pub fn take_prefix(&mut self, n: usize) -> Option<ConstStride<'a, T, S>> {
+        self.base.take_prefix(n).map(|base| ConstStride { base })
+    }
+    /// Removes the last `n` elements from `self` and returns them as
+    /// a strided slice, or returns `None` (leaving `self` untouched)
+    /// if `n > self.len()`.
+    #[inline]
+    pub fn take_suffix(&mut self, n: usize) -> Option<ConstStride<'a, T, S>> {
+        self.base.take_suffix(n).map(|base| ConstStride { base })
+    }
+}
+
+impl<'a, T, const S: usize> TryFrom<Stride<'a, T>> for ConstStride<'a, T, S> {
+    type Error = StrideError;
+    /// Converts `s`, which must have stride `S` unless it has at most
+    /// one element, failing with `Err(WrongStride)` otherwise.
+    fn try_from(s: Stride<'a, T>) -> Result<ConstStride<'a, T, S>, StrideError> {
+        with_stride::<T, S>(s.base).map(|base| ConstStride { base })
+    }
+}
+impl<'a, T, const S: usize> From<ConstStride<'a, T, S>> for Stride<'a, T> {
+    fn from(s: ConstStride<'a, T, S>) -> Stride<'a, T> { s.as_stride() }
+}
+
+impl<'a, T, const S: usize> Index<usize> for ConstStride<'a, T, S> {
+    type Output = T;
+    #[track_caller]
+    fn index(&self, n: usize) -> &T {
+        match self.get(n) {
+            Some(x) => x,
+            None => self.fail("index", StrideError::IndexOutOfBounds { index: n, len: self.len() }),
+        }
+    }
+}
+
+impl<'a, T, const S: usize> IntoIterator for ConstStride<'a, T, S> {
+    type Item = &'a T;
+    type IntoIter = ConstItems<'a, T, S>;
+    #[inline]
+    fn into_iter(self) -> ConstItems<'a, T, S> { self.iter() }
+}
+impl<'a, T, const S: usize> IntoIterator for &ConstStride<'a, T, S> {
+    type Item = &'a T;
+    type IntoIter = ConstItems<'a, T, S>;
+    #[inline]
+    fn into_iter(self) -> ConstItems<'a, T, S> { self.iter() }
+}
+
+/// A mutable strided slice whose stride is the compile-time constant
+/// `S`. This is to `ConstStride` as `MutStride` is to `Stride`, and
+/// has the same methods as `MutStride`, with the shared ones
+/// borrowing `self` rather than going through `Deref`; `as_const`
+/// gives a `ConstStride` for the rest.
+///
+/// ```rust
+/// use strided::MutConstStride;
+///
+/// let mut stereo = [0.5f32, -0.5, 1.0, -1.0, 0.25, -0.25];
+/// let mut left = MutConstStride::<_, 2>::new(&mut stereo);
+/// for x in left.iter_mut() { *x *= 2.0 }
+/// assert_eq!(stereo, [1.0, -0.5, 2.0, -1.0, 0.5, -0.25]);
+/// ```
+///
+/// Shared references into it borrow it, like those into a `&mut [T]`:
+///
+/// ```compile_fail,E0502
+/// let mut v = [1, 2, 3, 4];
+/// let mut m = strided::MutConstStride::<_, 2>::new(&mut v);
+/// let r = m.get(0).unwrap();
+/// *m.get_mut(0).unwrap() = 5;
+/// println!("{}", r);
+/// ```
+#[repr(transparent)]
+pub struct MutConstStride<'a, T: 'a, const S: usize> {
+    base: Base<'a, T>,
+    _marker: marker::PhantomData<&'a mut T>,
+}
+
+unsafe impl<'a, T: Sync, const S: usize> Sync for MutConstStride<'a, T, S> {}
+unsafe impl<'a, T: Send, const S: usize> Send for MutConstStride<'a, T, S> {}
+
+impl<'a, T, const S: usize> Default for MutConstStride<'a, T, S> {
+    fn default() -> MutConstStride<'a, T, S> { MutConstStride::empty() }
+}
+
+impl<'a, T: Debug, const S: usize> Debug for MutConstStride<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_const().fmt(f)
+    }
+}
+impl<'a, T: Display, const S: usize> Display for MutConstStride<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.as_const(), f)
+    }
+}
+impl<'a, T: Hash, const S: usize> Hash for MutConstStride<'a, T, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.base.hash(state)
+    }
+}
+
+impl<'a, T, const S: usize> MutConstStride<'a, T, S> {
+    #[inline(always)]
+    fn from_checked(base: Base<'a, T>) -> MutConstStride<'a, T, S> {
+        MutConstStride { base, _marker: marker::PhantomData }
+    }
+
+    /// Creates a new empty mutable strided slice, not pointing to any
+    /// data.
+    #[inline(always)]
+    pub fn empty() -> MutConstStride<'a, T, S> {
+        MutConstStride::from_checked(const_base::<T, S>(NonNull::dangling().as_ptr(), 0))
+    }
+
+    /// Creates a mutable strided slice of every `S`th element of
+    /// `data`, starting with the first.
+    #[inline]
+    pub fn new(data: &'a mut [T]) -> MutConstStride<'a, T, S> {
+        let len = data.len().div_ceil(S);
+        MutConstStride::from_checked(const_base::<T, S>(data.as_mut_ptr(), len))
+    }
+
+    /// Creates a mutable strided slice of the `len` elements of
+    /// `data` at indices `offset`, `offset + S`, ....
+    ///
+    /// Returns `Err(OutOfBounds)` if the layout does not fit within
+    /// `data`.
+    pub fn from_parts(data: &'a mut [T], offset: usize, len: usize)
+                      -> Result<MutConstStride<'a, T, S>, StrideError> {
+        MutStride::from_parts(data, offset, S, len)
+            .map(|s| MutConstStride::from_checked(const_base::<T, S>(s.base.as_mut_ptr(), len)))
+    }
+
+    /// Creates a mutable strided slice of `len` elements, `S` elements
+    /// apart, starting at `data`.
+    ///
+    /// Returns `Err(TooLarge)` if the elements would span more than
+    /// `isize::MAX` bytes.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be non-null and aligned, and all `len` elements
+    /// must lie within a single allocation, be initialised, and not be
+    /// accessed through any other pointer for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(data: *mut T, len: usize)
+                                 -> Result<MutConstStride<'a, T, S>, StrideError> {
+        MutStride::from_raw_parts(data, len, S)
+            .map(|_| MutConstStride::from_checked(const_base::<T, S>(data, len)))
+    }
+
+    #[cold]
+    #[track_caller]
+    fn fail(&self, method: &str, e: StrideError) -> ! {
+        panic!("MutConstStride.{}: {} (stride {})", method, e, S)
+    }
+
+    /// Creates a temporary copy of this strided slice, to allow the
+    /// by-value `self` methods to be used without losing access to
+    /// it.
+    #[inline(always)]
+    pub fn reborrow(&mut self) -> MutConstStride<'_, T, S> {
+        MutConstStride::from_checked(self.base)
+    }
+
+    /// Returns a shared view of the elements, borrowed from `self`.
+    #[inline(always)]
+    pub fn as_const(&self) -> ConstStride<'_, T, S> {
+        ConstStride { base: self.base }
+    }
+
+    /// Converts this into a shared strided slice with the full
+    /// lifetime `'a`, consuming `self`.
+    #[inline(always)]
+    pub fn into_const(self) -> ConstStride<'a, T, S> {
+        ConstStride { base: self.base }
+    }
+
+    /// Converts this into the equivalent `MutStride`, consuming
+    /// `self`.
+    #[inline(always)]
+    pub fn into_stride_mut(self) -> MutStride<'a, T> {
+        MutStride::new_raw(self.base)
+    }
+
+    /// Converts this into a shared strided slice of `Cell`s, like
+    /// `MutStride::as_cell_stride`, consuming `self`.
+    #[inline]
+    pub fn as_cell_stride(self) -> ConstStride<'a, Cell<T>, S> {
+        // `Cell<T>` has the same layout as `T`, and the exclusive
+        // borrow is handed over to the cells.
+        ConstStride { base: unsafe { self.base.cast() } }
+    }
+
+    /// Returns the number of elements accessible in `self`.
+    #[inline(always)]
+    pub fn len(&self) -> usize { self.base.len() }
+    /// Returns `true` if `self` has no elements.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.base.is_empty() }
+    /// Returns the offset between successive elements, `S`.
+    #[inline(always)]
+    pub const fn stride(&self) -> usize { S }
+    /// Returns a pointer to the first element of this strided slice.
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut T { self.base.as_mut_ptr() }
+    /// Returns the range of memory spanned by this strided slice, like
+    /// `ConstStride::as_ptr_range`, as mutable pointers.
+    #[inline]
+    pub fn as_mut_ptr_range(&mut self) -> Range<*mut T> { self.base.ptr_range() }
+
+    /// Returns `true` if the elements of `self` are adjacent in
+    /// memory, that is, if `S == 1` or if `self` has at most one
+    /// element.
+    #[inline]
+    pub fn is_contiguous(&self) -> bool { self.base.is_contiguous() }
+    /// Returns a conventional slice of the elements of `self` if they
+    /// are adjacent in memory, and `None` otherwise.
+    #[inline]
+    pub fn as_slice(&self) -> Option<&[T]> { self.as_const().as_slice() }
+    /// Returns a conventional mutable slice of the elements of `self`
+    /// if they are adjacent in memory, and `None` otherwise.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> Option<&mut [T]> {
+        self.base.as_mut_slice().map(|s| &mut *s)
+    }
+
+    /// Fuses two mutable strided slices back into one, like
+    /// `MutStride::try_merge`, if the result still has stride `S`. If
+    /// the slices cannot be merged, they are returned unchanged in the
+    /// `Err` variant.
+    ///
+    /// # Safety
+    ///
+    /// `a` and `b` must have been split from the same mutable strided
+    /// slice (or slice), such as by `split_at_mut`.
+    pub unsafe fn try_merge(a: MutConstStride<'a, T, S>, b: MutConstStride<'a, T, S>)
+                            -> Result<MutConstStride<'a, T, S>,
+                                      (MutConstStride<'a, T, S>, MutConstStride<'a, T, S>)> {
+        match Base::try_merge(a.base, b.base).map(with_stride::<T, S>) {
+            Some(Ok(base)) => Ok(MutConstStride::from_checked(base)),
+            _ => Err((a, b)),
+        }
+    }
+
+    /// Breaks this strided slice into two mutable strided slices
+    /// pointing to alternate elements, like `MutStride::substrides2_mut`.
+    #[inline]
+    #[track_caller]
+    pub fn substrides2_mut(self) -> (MutStride<'a, T>, MutStride<'a, T>) {
+        self.into_stride_mut().substrides2_mut()
+    }
+    /// Breaks this strided slice into three mutable strided slices,
+    /// like `MutStride::substrides3_mut`.
+    #[inline]
+    #[track_caller]
+    pub fn substrides3_mut(self) -> (MutStride<'a, T>, MutStride<'a, T>, MutStride<'a, T>) {
+        self.into_stride_mut().substrides3_mut()
+    }
+    /// Breaks this strided slice into four mutable strided slices,
+    /// like `MutStride::substrides4_mut`.
+    #[inline]
+    #[track_caller]
+    pub fn substrides4_mut(self)
+                           -> (MutStride<'a, T>, MutStride<'a, T>, MutStride<'a, T>, MutStride<'a, T>) {
+        self.into_stride_mut().substrides4_mut()
+    }
+    /// Returns an iterator over `n` mutable strided subslices of
+    /// `self`, like `MutStride::substrides_mut`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `n` is zero.
+    #[inline]
+    #[track_caller]
+    pub fn substrides_mut(self, n: usize) -> MutSubstrides<'a, T> {
+        match MutStride::new_raw(self.base).try_substrides_mut(n) {
+            Ok(s) => s,
+            Err(e) => self.fail("substrides_mut", e),
+        }
+    }
+    /// Returns an iterator over `n` mutable strided subslices of
+    /// `self`, or an error instead of panicking, like
+    /// `MutStride::try_substrides_mut`.
+    #[inline]
+    pub fn try_substrides_mut(self, n: usize) -> Result<MutSubstrides<'a, T>, StrideError> {
+        self.into_stride_mut().try_substrides_mut(n)
+    }
+    /// Returns the `i`th of the `n` mutable strided subslices that
+    /// `substrides_mut(n)` would yield.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= n`.
+    #[inline]
+    #[track_caller]
+    pub fn substride_mut(self, n: usize, i: usize) -> MutStride<'a, T> {
+        match MutStride::new_raw(self.base).try_substride_mut(n, i) {
+            Ok(s) => s,
+            Err(e) => self.fail("substride_mut", e),
+        }
+    }
+    /// Returns the `i`th of the `n` mutable strided subslices, like
+    /// `substride_mut`, or an error instead of panicking if `i >= n`.
+    #[inline]
+    pub fn try_substride_mut(self, n: usize, i: usize) -> Result<MutStride<'a, T>, StrideError> {
+        self.into_stride_mut().try_substride_mut(n, i)
+    }
+
+    /// Returns a reference to the `n`th element of `self`, or `None`
+    /// if `n` is out-of-bounds.
+    #[inline]
+    pub fn get(&self, n: usize) -> Option<&T> {
+        self.as_const().get(n)
+    }
+    /// Returns the first element, or `None` if `self` is empty.
+    #[inline]
+    pub fn first(&self) -> Option<&T> { self.get(0) }
+    /// Returns the last element, or `None` if `self` is empty.
+    #[inline]
+    pub fn last(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|n| self.get(n))
+    }
+    /// Returns an iterator over references to each successive element
+    /// of `self`.
+    #[inline]
+    pub fn iter(&self) -> ConstItems<'_, T, S> {
+        self.as_const().iter()
+    }
+    /// Returns an iterator over copies of each successive element of
+    /// `self`.
+    #[inline]
+    pub fn iter_copied(&self) -> iter::Copied<ConstItems<'_, T, S>>
+        where T: Copy
+    {
+        self.iter().copied()
+    }
+
+    /// Returns a mutable reference to the `n`th element of `self`, or
+    /// `None` if `n` is out-of-bounds.
+    #[inline]
+    pub fn get_mut(&mut self, n: usize) -> Option<&mut T> {
+        if n < self.len() { unsafe { Some(self.get_unchecked_mut(n)) } } else { None }
+    }
+    /// Returns a mutable reference to the `n`th element of `self`,
+    /// without checking that it is in bounds.
+    ///
+    /// # Safety
+    ///
+    /// `n` must be less than `self.len()`.
+    #[inline(always)]
+    pub unsafe fn get_unchecked_mut(&mut self, n: usize) -> &mut T {
+        debug_assert!(n < self.len());
+        &mut *self.base.as_mut_ptr().add(n * S)
+    }
+    /// Returns a mutable reference to the first element, or `None` if
+    /// `self` is empty.
+    #[inline]
+    pub fn first_mut(&mut self) -> Option<&mut T> { self.get_mut(0) }
+    /// Returns a mutable reference to the last element, or `None` if
+    /// `self` is empty.
+    #[inline]
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.len().checked_sub(1).and_then(move |n| self.get_mut(n))
+    }
+
+    /// Swaps the elements at indices `a` and `b`, like `slice::swap`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `a` or `b` is out-of-bounds.
+    #[inline]
+    #[track_caller]
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let len = self.len();
+        for &i in &[a, b] {
+            if i >= len { self.fail("swap", StrideError::IndexOutOfBounds { index: i, len }) }
+        }
+        // `ptr::swap` allows `a == b`.
+        unsafe {
+            let p = self.base.as_mut_ptr();
+            ptr::swap(p.add(a * S), p.add(b * S))
+        }
+    }
+
+    /// Returns an iterator over mutable references to each successive
+    /// element of `self`.
+    #[inline]
+    pub fn iter_mut(&mut self) -> MutConstItems<'_, T, S> {
+        self.reborrow().into_iter()
+    }
+
+    /// Overwrites the elements of `self` in order with those taken
+    /// from `iter`, like `MutStride::assign_from_iter`, returning the
+    /// number written, or `Err(IndexOutOfBounds)` if `iter` has more
+    /// elements than `self`.
+    pub fn assign_from_iter<I: IntoIterator<Item = T>>(&mut self, iter: I)
+                                                       -> Result<usize, StrideError> {
+        let len = self.len();
+        let mut iter = iter.into_iter();
+        let n = self.iter_mut().zip(&mut iter).map(|(x, v)| *x = v).count();
+        if n == len && iter.next().is_some() {
+            Err(StrideError::IndexOutOfBounds { index: len, len })
+        } else {
+            Ok(n)
+        }
+    }
+
+    /// Calls `f` on each element of `self`, in order, in a counted
+    /// loop with a constant step.
+    #[inline]
+    pub fn map_in_place<F: FnMut(&mut T)>(&mut self, mut f: F) {
+        for i in 0..self.len() {
+            unsafe { f(self.get_unchecked_mut(i)) }
+        }
+    }
+
+    /// Replaces each element of `self` after the first with a running
+    /// total, by calling `f(prev, x)`, like `MutStride::scan_in_place`.
+    pub fn scan_in_place<F: FnMut(&T, &mut T)>(&mut self, mut f: F) {
+        let mut iter = self.iter_mut();
+        if let Some(mut prev) = iter.next() {
+            for x in iter {
+                f(prev, x);
+                prev = x;
+            }
+        }
+    }
+
+    /// Replaces each element of `self` with the sum of it and all the
+    /// elements before it, a prefix sum.
+    pub fn cumsum_in_place(&mut self) where T: Copy + AddAssign {
+        self.scan_in_place(|&prev, x| *x += prev)
+    }
+
+    /// Calls `f` on each element of `self` and the corresponding
+    /// element of `other`, in order, like `map_in_place`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `self` and `other` have different lengths.
+    #[inline]
+    #[track_caller]
+    pub fn zip_apply<U, F: FnMut(&mut T, &U), const R: usize>(&mut self, other: ConstStride<'_, U, R>,
+                                                              mut f: F) {
+        assert!(self.len() == other.len(), "MutConstStride.zip_apply: mismatched lengths {} and {}",
+                self.len(), other.len());
+        for i in 0..self.len() {
+            unsafe { f(self.get_unchecked_mut(i), other.get_unchecked(i)) }
+        }
+    }
+
+    /// Sets every element to `value`.
+    #[inline]
+    pub fn fill(&mut self, value: T) where T: Clone {
+        for x in self.iter_mut() { x.clone_from(&value) }
+    }
+
+    /// Copies the elements of `src` into `self`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `self` and `src` have different lengths.
+    #[inline]
+    #[track_caller]
+    pub fn copy_from(&mut self, src: &ConstStride<'_, T, S>) where T: Copy {
+        assert!(self.len() == src.len(), "MutConstStride.copy_from: mismatched lengths {} and {}",
+                self.len(), src.len());
+        for (x, y) in self.iter_mut().zip(src) { *x = *y }
+    }
+
+    /// Swaps each element of `self` with the corresponding element of
+    /// `other`, like `MutStride::swap_with_stride`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `self` and `other` have different lengths.
+    #[inline]
+    #[track_caller]
+    pub fn swap_with_stride<const R: usize>(&mut self, other: &mut MutConstStride<'_, T, R>) {
+        assert!(self.len() == other.len(),
+                "MutConstStride.swap_with_stride: mismatched lengths {} and {}", self.len(), other.len());
+        for (x, y) in self.iter_mut().zip(other.iter_mut()) { mem::swap(x, y) }
+    }
+
+    /// Returns a mutable strided slice containing only the elements
+    /// from indices `from` (inclusive) to `to` (exclusive), consuming
+    /// `self`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `from > to` or if `to > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn slice_mut(self, from: usize, to: usize) -> MutConstStride<'a, T, S> {
+        match self.base.try_slice(from, to) {
+            Ok(base) => MutConstStride::from_checked(base),
+            Err(e) => self.fail("slice_mut", e),
+        }
+    }
+    /// Returns a mutable strided slice containing only the elements
+    /// from indices `from` (inclusive) to `to` (exclusive), or an
+    /// error instead of panicking, consuming `self`.
+    #[inline]
+    pub fn try_slice_mut(self, from: usize, to: usize) -> Result<MutConstStride<'a, T, S>, StrideError> {
+        self.base.try_slice(from, to).map(MutConstStride::from_checked)
+    }
+    /// Returns a mutable strided slice containing only the elements
+    /// from index `from`, or an error if `from > self.len()`.
+    #[inline]
+    pub fn try_slice_from_mut(self, from: usize) -> Result<MutConstStride<'a, T, S>, StrideError> {
+        self.base.try_slice_from(from).map(MutConstStride::from_checked)
+    }
+    /// Returns a mutable strided slice containing only the elements to
+    /// index `to`, or an error if `to > self.len()`.
+    #[inline]
+    pub fn try_slice_to_mut(self, to: usize) -> Result<MutConstStride<'a, T, S>, StrideError> {
+        self.try_slice_mut(0, to)
+    }
+    /// Returns a mutable strided slice containing only the elements
+    /// from index `from` onwards, consuming `self`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `from > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn slice_from_mut(self, from: usize) -> MutConstStride<'a, T, S> {
+        match self.base.try_slice_from(from) {
+            Ok(base) => MutConstStride::from_checked(base),
+            Err(e) => self.fail("slice_from_mut", e),
+        }
+    }
+    /// Returns a mutable strided slice containing only the elements
+    /// up to index `to`, consuming `self`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `to > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn slice_to_mut(self, to: usize) -> MutConstStride<'a, T, S> {
+        match self.base.try_slice(0, to) {
+            Ok(base) => MutConstStride::from_checked(base),
+            Err(e) => self.fail("slice_to_mut", e),
+        }
+    }
+    /// Returns a mutable strided slice containing only the elements
+    /// from indices `from` (inclusive) to `to` (exclusive), after
+    /// clamping both to `self.len()`, so that this never panics.
+    #[inline]
+    pub fn slice_clamped_mut(self, from: usize, to: usize) -> MutConstStride<'a, T, S> {
+        MutConstStride::from_checked(self.base.slice_clamped(from, to))
+    }
+    /// Returns a mutable strided slice containing only the elements
+    /// from indices `from` (inclusive) to `to` (exclusive), without
+    /// checking the bounds.
+    ///
+    /// # Safety
+    ///
+    /// `from <= to` and `to <= self.len()` must hold.
+    #[inline]
+    pub unsafe fn slice_unchecked_mut(self, from: usize, to: usize) -> MutConstStride<'a, T, S> {
+        MutConstStride::from_checked(self.base.slice_unchecked(from, to))
+    }
+    /// Returns two mutable strided slices, the first with elements up
+    /// to `idx` and the second with elements from `idx`, consuming
+    /// `self`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `idx > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn split_at_mut(self, idx: usize) -> (MutConstStride<'a, T, S>, MutConstStride<'a, T, S>) {
+        match self.base.try_split_at(idx) {
+            Ok((l, r)) => (MutConstStride::from_checked(l), MutConstStride::from_checked(r)),
+            Err(e) => self.fail("split_at_mut", e),
+        }
+    }
+    /// Returns two mutable strided slices split at `idx`, like
+    /// `split_at_mut`, or `Err(IndexOutOfBounds)` if
+    /// `idx > self.len()`.
+    #[inline]
+    pub fn try_split_at_mut(self, idx: usize)
+                            -> Result<(MutConstStride<'a, T, S>, MutConstStride<'a, T, S>),
+                                      StrideError> {
+        let (l, r) = self.base.try_split_at(idx)?;
+        Ok((MutConstStride::from_checked(l), MutConstStride::from_checked(r)))
+    }
+    /// Returns two mutable strided slices split at `idx`, without
+    /// checking the bounds.
+    ///
+    /// # Safety
+    ///
+    /// `idx <= self.len()` must hold.
+    #[inline]
+    pub unsafe fn split_at_unchecked_mut(self, idx: usize)
+                                         -> (MutConstStride<'a, T, S>, MutConstStride<'a, T, S>) {
+        let (l, r) = self.base.split_at_unchecked(idx);
+        (MutConstStride::from_checked(l), MutConstStride::from_checked(r))
+    }
+
+    /// Removes the first element from `self` and returns a reference
+    /// to it, or returns `None` if `self` is empty.
+    #[inline]
+    pub fn take_first_mut(&mut self) -> Option<&'a mut T> {
+        self.base.take_prefix(1).and_then(|mut s| s.get_mut(0))
+    }
+    /// Removes the last element from `self` and returns a reference
+    /// to it, or returns `None` if `self` is empty.
+    #[inline]
+    pub fn take_last_mut(&mut self) -> Option<&'a mut T> {
+        self.base.take_suffix(1).and_then(|mut s| s.get_mut(0))
+    }
+    /// Removes the first `n` elements from `self` and returns them as
+    /// a mutable strided slice, or returns `None` (leaving `self`
+    /// untouched) if `n > self.len()`.
+    #[inline]
+    pub fn take_prefix_mut(&mut self, n: usize) -> Option<MutConstStride<'a, T, S>> {
+        self.base.take_prefix(n).map(MutConstStride::from_checked)
+    }
+    /// Removes the last `n` elements from `self` and returns them as
+    /// a mutable strided slice, or returns `None` (leaving `self`
+    /// untouched) if `n > self.len()`.
+    #[inline]
+    pub fn take_suffix_mut(&mut self, n: usize) -> Option<MutConstStride<'a, T, S>> {
+        self.base.take_suffix(n).map(MutConstStride::from_checked)
+    }
+}
+
+impl<'a, T, const S: usize> TryFrom<MutStride<'a, T>> for MutConstStride<'a, T, S> {
+    type Error = StrideError;
+    /// Converts `s`, which must have stride `S` unless it has at most
+    /// one element, failing with `Err(WrongStride)` otherwise.
+    fn try_from(s: MutStride<'a, T>) -> Result<MutConstStride<'a, T, S>, StrideError> {
+        with_stride::<T, S>(s.base).map(MutConstStride::from_checked)
+    }
+}
+impl<'a, T, const S: usize> From<MutConstStride<'a, T, S>> for MutStride<'a, T> {
+    fn from(s: MutConstStride<'a, T, S>) -> MutStride<'a, T> { s.into_stride_mut() }
+}
+
+impl<'a, T, const S: usize> Index<usize> for MutConstStride<'a, T, S> {
+    type Output = T;
+    #[track_caller]
+    fn index(&self, n: usize) -> &T {
+        match self.get(n) {
+            Some(x) => x,
+            None => self.fail("index", StrideError::IndexOutOfBounds { index: n, len: self.len() }),
+        }
+    }
+}
+impl<'a, T, const S: usize> IndexMut<usize> for MutConstStride<'a, T, S> {
+    #[track_caller]
+    fn index_mut(&mut self, n: usize) -> &mut T {
+        let len = self.len();
+        if n >= len {
+            self.fail("index_mut", StrideError::IndexOutOfBounds { index: n, len })
+        }
+        unsafe { self.get_unchecked_mut(n) }
+    }
+}
+
+impl<'a, T, const S: usize> IntoIterator for MutConstStride<'a, T, S> {
+    type Item = &'a mut T;
+    type IntoIter = MutConstItems<'a, T, S>;
+    #[inline]
+    fn into_iter(self) -> MutConstItems<'a, T, S> {
+        let data = unsafe { NonNull::new_unchecked(self.base.as_mut_ptr()) };
+        MutConstItems { data, front: 0, back: self.len(), _marker: marker::PhantomData }
+    }
+}
+impl<'a, 'b, T, const S: usize> IntoIterator for &'b mut MutConstStride<'a, T, S> {
+    type Item = &'b mut T;
+    type IntoIter = MutConstItems<'b, T, S>;
+    #[inline]
+    fn into_iter(self) -> MutConstItems<'b, T, S> { self.iter_mut() }
+}
+
+impl<'a, 'b, A: PartialEq<B>, B, const S: usize, const R: usize> PartialEq<ConstStride<'b, B, R>>
+    for ConstStride<'a, A, S>
+{
+    fn eq(&self, other: &ConstStride<'b, B, R>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+impl<'a, T: Eq, const S: usize> Eq for ConstStride<'a, T, S> {}
+impl<'a, T: PartialOrd, const S: usize> PartialOrd for ConstStride<'a, T, S> {
+    fn partial_cmp(&self, other: &ConstStride<'a, T, S>) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+impl<'a, T: Ord, const S: usize> Ord for ConstStride<'a, T, S> {
+    fn cmp(&self, other: &ConstStride<'a, T, S>) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+impl<'a, 'b, A: PartialEq<B>, B, const S: usize, const R: usize> PartialEq<MutConstStride<'b, B, R>>
+    for MutConstStride<'a, A, S>
+{
+    fn eq(&self, other: &MutConstStride<'b, B, R>) -> bool { self.as_const() == other.as_const() }
+}
+impl<'a, T: Eq, const S: usize> Eq for MutConstStride<'a, T, S> {}
+impl<'a, T: PartialOrd, const S: usize> PartialOrd for MutConstStride<'a, T, S> {
+    fn partial_cmp(&self, other: &MutConstStride<'a, T, S>) -> Option<Ordering> {
+        self.as_const().partial_cmp(&other.as_const())
+    }
+}
+impl<'a, T: Ord, const S: usize> Ord for MutConstStride<'a, T, S> {
+    fn cmp(&self, other: &MutConstStride<'a, T, S>) -> Ordering {
+        self.as_const().cmp(&other.as_const())
+    }
+}
+
+macro_rules! iterator {
+    ($name: ident -> $elem: ty, $marker: ty, $($mut_: tt)*) => {
+        impl<'a, T, const S: usize> Iterator for $name<'a, T, S> {
+            type Item = $elem;
+            #[inline]
+            fn next(&mut self) -> Option<$elem> {
+                if self.front == self.back { return None }
+                let i = self.front;
+                self.front += 1;
+                unsafe { Some(&$($mut_)* *self.data.as_ptr().add(i * S)) }
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = self.back - self.front;
+                (n, Some(n))
+            }
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<$elem> {
+                self.front += n.min(self.back - self.front);
+                self.next()
+            }
+            // a counted loop with a constant step, which the compiler
+            // can unroll and vectorise.
+            #[inline]
+            fn fold<B, F>(self, init: B, mut f: F) -> B
+                where F: FnMut(B, $elem) -> B
+            {
+                let mut acc = init;
+                for i in self.front..self.back {
+                    acc = f(acc, unsafe { &$($mut_)* *self.data.as_ptr().add(i * S) });
+                }
+                acc
+            }
+        }
+        impl<'a, T, const S: usize> DoubleEndedIterator for $name<'a, T, S> {
+            #[inline]
+            fn next_back(&mut self) -> Option<$elem> {
+                if self.front == self.back { return None }
+                self.back -= 1;
+                unsafe { Some(&$($mut_)* *self.data.as_ptr().add(self.back * S)) }
+            }
+            #[inline]
+            fn rfold<B, F>(self, init: B, mut f: F) -> B
+                where F: FnMut(B, $elem) -> B
+            {
+                let mut acc = init;
+                for i in (self.front..self.back).rev() {
+                    acc = f(acc, unsafe { &$($mut_)* *self.data.as_ptr().add(i * S) });
+                }
+                acc
+            }
+        }
+        impl<'a, T, const S: usize> ExactSizeIterator for $name<'a, T, S> {}
+        impl<'a, T, const S: usize> Debug for $name<'a, T, S> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("len", &(self.back - self.front))
+                    .field("stride", &S)
+                    .field("ptr", &unsafe { self.data.as_ptr().add(self.front * S) })
+                    .finish()
+            }
+        }
+        impl<'a, T, const S: usize> FusedIterator for $name<'a, T, S> {}
+
+        /// An iterator over the elements of a strided slice with a
+        /// compile-time stride.
+        pub struct $name<'a, T: 'a, const S: usize> {
+            // elements `front..back` remain, element `i` being `i * S`
+            // elements after `data`.
+            data: NonNull<T>,
+            front: usize,
+            back: usize,
+            _marker: marker::PhantomData<$marker>,
+        }
+    }
+}
+
+iterator!(ConstItems -> &'a T, &'a T, );
+iterator!(MutConstItems -> &'a mut T, &'a mut T, mut);
+
+impl<'a, T, const S: usize> Clone for ConstItems<'a, T, S> {
+    fn clone(&self) -> ConstItems<'a, T, S> {
+        ConstItems { data: self.data, front: self.front, back: self.back, _marker: marker::PhantomData }
+    }
+}
+
+unsafe impl<'a, T: Sync, const S: usize> Sync for ConstItems<'a, T, S> {}
+unsafe impl<'a, T: Sync, const S: usize> Send for ConstItems<'a, T, S> {}
+unsafe impl<'a, T: Sync, const S: usize> Sync for MutConstItems<'a, T, S> {}
+unsafe impl<'a, T: Send, const S: usize> Send for MutConstItems<'a, T, S> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::convert::TryFrom;
+    use std::hash::{Hash, Hasher};
+    use {Stride, MutStride, StrideError};
+    use super::{ConstStride, MutConstStride};
+
+    fn hash<H: Hash>(x: H) -> u64 {
+        let mut h = DefaultHasher::new();
+        x.hash(&mut h);
+        h.finish()
+    }
+
+    #[test]
+    fn new_and_access() {
+        let v = [1u16, 2, 3, 4, 5, 6, 7];
+        let s = ConstStride::<_, 3>::new(&v);
+        assert_eq!(s, [1, 4, 7]);
+        assert_eq!((s.len(), s.stride()), (3, 3));
+        assert_eq!((s[1], s.get(3), s.first(), s.last()), (4, None, Some(&1), Some(&7)));
+        assert_eq!(ConstStride::<_, 3>::new(&v[1..]), [2, 5]);
+        assert_eq!(ConstStride::<u16, 2>::new(&[]).len(), 0);
+        assert_eq!(ConstStride::<_, 2>::from_parts(&v, 1, 3).unwrap(), [2, 4, 6]);
+        assert_eq!(ConstStride::<_, 2>::from_parts(&v, 2, 3).unwrap(), [3, 5, 7]);
+        assert_eq!(ConstStride::<_, 2>::from_parts(&v, 3, 3), Err(StrideError::OutOfBounds));
+        assert_eq!(format!("{:?}", s), "[1, 4, 7]");
+    }
+
+    #[test]
+    fn iter() {
+        let v = [1u32, 2, 3, 4, 5, 6, 7, 8];
+        let s = ConstStride::<_, 2>::new(&v);
+        assert_eq!(s.iter().sum::<u32>(), 16);
+        assert_eq!(s.iter().rev().copied().collect::<Vec<_>>(), [7, 5, 3, 1]);
+        let mut it = s.iter();
+        assert_eq!((it.next(), it.next_back(), it.len()), (Some(&1), Some(&7), 2));
+        assert_eq!(it.clone().fold(0, |acc, &x| acc * 10 + x), 35);
+        assert_eq!(it.clone().rfold(0, |acc, &x| acc * 10 + x), 53);
+        assert_eq!(it.nth(1), Some(&5));
+        assert_eq!((it.next(), it.next_back()), (None, None));
+        assert_eq!(s.iter().nth(10), None);
+    }
+
+    #[test]
+    fn slicing() {
+        let v = [0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let s = ConstStride::<_, 2>::new(&v);
+        assert_eq!(s.slice(1, 3), [2, 4]);
+        assert_eq!(s.slice_from(3), [6, 8]);
+        assert_eq!(s.slice_to(1), [0]);
+        let (l, r) = s.split_at(2);
+        assert_eq!((l, r.stride()), (ConstStride::<_, 2>::new(&v[..4]), 2));
+        assert_eq!(r.as_stride(), [4, 6, 8]);
+        assert_eq!(s.slice(5, 5).iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ConstStride.slice: index 6 out of bounds for length 5 (stride 2)")]
+    fn slice_out_of_bounds() {
+        ConstStride::<_, 2>::new(&[0u8; 10]).slice(1, 6);
+    }
+
+    #[test]
+    fn conversions() {
+        let v = [1u16, 2, 3, 4, 5, 6];
+        let (l, _) = Stride::new(&v).substrides2();
+        let c = ConstStride::<_, 2>::try_from(l).unwrap();
+        assert_eq!(c, [1, 3, 5]);
+        assert_eq!(Stride::from(c), l);
+        assert_eq!(ConstStride::<_, 3>::try_from(l),
+                   Err(StrideError::WrongStride { expected: 3, found: 2 }));
+        // a single element has any stride.
+        let one = ConstStride::<_, 3>::try_from(l.slice(1, 2)).unwrap();
+        assert_eq!(one, ConstStride::<_, 7>::new(&[3]));
+        assert_eq!(one.as_stride().stride(), 3);
+        assert_eq!(StrideError::WrongStride { expected: 3, found: 2 }.to_string(),
+                   "stride 2 does not match the expected 3");
+    }
+
+    #[test]
+    fn mutation() {
+        let mut v = [1i32, 2, 3, 4, 5, 6];
+        {
+            let mut s = MutConstStride::<_, 2>::new(&mut v[1..]);
+            s[0] = 20;
+            *s.get_mut(2).unwrap() = 60;
+            assert!(s.get_mut(3).is_none());
+            for x in s.reborrow().slice_mut(1, 2) { *x *= 10 }
+            assert_eq!(s, [20, 40, 60]);
+            assert_eq!(s.iter().sum::<i32>(), 120);
+            assert_eq!((s.len(), s.stride(), s.get(1), s.get(3)), (3, 2, Some(&40), None));
+            assert_eq!(s.as_const().slice_from(2), [60]);
+        }
+        assert_eq!(v, [1, 20, 3, 40, 5, 60]);
+
+        let mut s = MutConstStride::<_, 2>::new(&mut v);
+        s.fill(0);
+        let (mut a, b) = s.split_at_mut(1);
+        a.copy_from(&ConstStride::new(&[9]));
+        assert_eq!(b.into_const(), [0, 0]);
+        assert_eq!(v, [9, 20, 0, 40, 0, 60]);
+
+        let (_, r) = MutStride::new(&mut v).substrides2_mut();
+        let mut c = MutConstStride::<_, 2>::try_from(r).unwrap();
+        c.iter_mut().rev().enumerate().for_each(|(i, x)| *x = i as i32);
+        let r: MutStride<_> = c.into();
+        assert_eq!(r, [2, 1, 0]);
+        assert_eq!(v, [9, 2, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "MutConstStride.copy_from: mismatched lengths 2 and 1")]
+    fn copy_from_mismatched() {
+        MutConstStride::<_, 2>::new(&mut [0; 4]).copy_from(&ConstStride::new(&[1]));
+    }
+
+    #[test]
+    fn shared_methods() {
+        let v = [1u8, 2, 3, 4, 5, 6, 7, 8, 9];
+        let s = ConstStride::<_, 2>::new(&v);
+        let d = s.as_stride();
+        let (a, b) = s.substrides2();
+        assert_eq!((a, b), d.substrides2());
+        assert_eq!(s.substrides(3).collect::<Vec<_>>(), d.substrides(3).collect::<Vec<_>>());
+        assert_eq!(s.substride(2, 1), [3, 7]);
+        assert_eq!(s.try_substrides(0).err(), Some(StrideError::ZeroStride));
+        assert_eq!(s.chunks_with_hop(2, 3).collect::<Vec<_>>(), [[1, 3], [7, 9]]);
+        assert_eq!(s.iter_copied().collect::<Vec<_>>(), [1, 3, 5, 7, 9]);
+        assert_eq!((s.as_slice(), ConstStride::<_, 1>::new(&v[..2]).as_slice()), (None, Some(&v[..2])));
+        assert!(s.slice(1, 2).is_contiguous());
+        assert_eq!(s.slice(1, 2).as_slice(), Some(&[3][..]));
+        assert_eq!((s.index_of_ref(&v[4]), s.index_of_ref(&v[3])), (Some(2), None));
+        assert!(s.overlaps(&ConstStride::<_, 3>::new(&v[4..])));
+        assert_eq!(s.byte_offset_of(4), 8);
+        assert_eq!(s.try_slice(2, 1), Err(StrideError::InvalidRange { from: 2, to: 1 }));
+        assert_eq!((s.slice_clamped(3, 10), s.try_slice_to(6).is_err()), (s.slice_from(3), true));
+
+        let (l, r) = s.split_at(2);
+        assert_eq!(s.try_merge(l, r), Some(s));
+        assert_eq!(s.try_merge(r, l), None);
+        let mut t = s;
+        assert_eq!((t.take_first(), t.take_last()), (Some(&1), Some(&9)));
+        assert_eq!(t.take_prefix(1).unwrap(), [3]);
+        assert_eq!((t, t.take_suffix(3)), (ConstStride::new(&v[4..7]), None));
+
+        assert_eq!(format!("{}", s), "[1, 3, 5, 7, 9]");
+        assert_eq!(format!("{:#}", s.slice_to(2)), format!("{:#}", d.slice_to(2)));
+        assert_eq!(s.display_separated(" ").to_string(), "1 3 5 7 9");
+        assert_eq!(hash(s), hash(d));
+        assert!(s.slice_to(2) < s.slice_from(1));
+        assert_eq!(s.max(s.slice_to(4)), s);
+        assert!(s < [1, 4] && s > vec![1, 3]);
+        assert_eq!(s, &[1, 3, 5, 7, 9][..]);
+        assert_eq!(ConstStride::<u8, 4>::default(), ConstStride::<u8, 4>::empty());
+    }
+
+    #[test]
+    fn mut_methods() {
+        let mut v = [1i32, 0, 2, 0, 3, 0, 4, 0];
+        let mut s = MutConstStride::<_, 2>::new(&mut v);
+        s.swap(0, 3);
+        assert_eq!(s, [4, 2, 3, 1]);
+        s.map_in_place(|x| *x *= 10);
+        s.reborrow().slice_from_mut(2).cumsum_in_place();
+        s.reborrow().slice_to_mut(1).zip_apply(ConstStride::<_, 5>::new(&[1]), |x, y| *x += y);
+        assert_eq!(s, vec![41, 20, 30, 40]);
+        assert_eq!(s.assign_from_iter(5..), Err(StrideError::IndexOutOfBounds { index: 4, len: 4 }));
+        assert_eq!((s.first(), s.last(), s.iter_copied().sum::<i32>()), (Some(&5), Some(&8), 26));
+        assert_eq!((s.as_slice(), s.is_contiguous(), format!("{}", s)), (None, false, "[5, 6, 7, 8]".into()));
+
+        let (mut a, mut b) = s.reborrow().split_at_mut(2);
+        a.swap_with_stride(&mut b);
+        let merged = unsafe { MutConstStride::try_merge(a, b) };
+        assert_eq!(merged.ok().unwrap(), [7, 8, 5, 6]);
+        let (x, y) = s.reborrow().substrides2_mut();
+        assert_eq!((x, y), (MutStride::new(&mut [7, 5]), MutStride::new(&mut [8, 6])));
+        s.reborrow().substride_mut(2, 1).fill(0);
+        assert_eq!(s.reborrow().substrides_mut(4).count(), 4);
+        *s.take_first_mut().unwrap() = -1;
+        assert_eq!(s.take_suffix_mut(2).unwrap(), [5, 0]);
+        assert_eq!((s.len(), hash(&s), s.cmp(&s)), (1, hash(Stride::new(&[0])), ::std::cmp::Ordering::Equal));
+        assert_eq!(v, [-1, 0, 0, 0, 5, 0, 0, 0]);
+
+        let mut c = [0u8; 3];
+        let cells = MutConstStride::<_, 1>::new(&mut c).as_cell_stride();
+        cells[1].set(7);
+        assert_eq!(c, [0, 7, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "MutConstStride.swap: index 4 out of bounds for length 2 (stride 3)")]
+    fn swap_out_of_bounds() {
+        MutConstStride::<_, 3>::new(&mut [0; 6]).swap(1, 4);
+    }
+
+    #[test]
+    fn iterator_debug() {
+        let v = [1u16, 2, 3, 4, 5];
+        let mut it = ConstStride::<_, 2>::new(&v).iter();
+        it.next();
+        assert_eq!(format!("{:?}", it),
+                   format!("ConstItems {{ len: 2, stride: 2, ptr: {:?} }}", &v[2] as *const u16));
+        let mut w = [0u8; 2];
+        let p = w.as_ptr();
+        assert_eq!(format!("{:?}", MutConstStride::<_, 1>::new(&mut w).into_iter()),
+                   format!("MutConstItems {{ len: 2, stride: 1, ptr: {:?} }}", p));
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert<T: Send + Sync>() {}
+        assert::<ConstStride<'static, u8, 4>>();
+        assert::<MutConstStride<'static, u8, 4>>();
+        assert::<super::ConstItems<'static, u8, 4>>();
+        assert::<super::MutConstItems<'static, u8, 4>>();
+    }
+}
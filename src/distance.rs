@@ -0,0 +1,116 @@
+//! Distances between strided vectors, such as embeddings stored
+//! interleaved with other data.
+//!
+//! Each is computed in a single pass over both vectors, in blocks, as
+//! for `blas1::dot`.
+//!
+//! ```rust
+//! use strided::{Stride, distance};
+//!
+//! // two 2-dimensional embeddings, each followed by an id.
+//! let store = [3.0, 0.0, 1.0, 0.0, 4.0, 2.0];
+//! let mut rows = store.chunks(3).map(|r| Stride::new(&r[..2]));
+//! let (a, b) = (rows.next().unwrap(), rows.next().unwrap());
+//! assert_eq!(distance::euclidean(&a, &b), 5.0);
+//! assert_eq!(distance::manhattan(&a, &b), 7.0);
+//! assert_eq!(distance::cosine_similarity(&a, &b), 0.0);
+//! ```
+
+use blas1::{for_blocks, sum_slices, Float};
+use Stride;
+
+#[track_caller]
+fn check_lengths(a: usize, b: usize, func: &str) {
+    assert!(a == b, "strided::distance::{}: mismatched lengths {} and {}", func, a, b);
+}
+
+// sums `f(a, b)` over corresponding elements of `x` and `y`.
+#[inline]
+fn sum_pairs<T: Float, F: Fn(T, T) -> T + Copy>(x: &Stride<'_, T>, y: &Stride<'_, T>, f: F) -> T {
+    let mut sum = T::ZERO;
+    for_blocks(*x, *y, T::ZERO, |a, b| sum = sum + sum_slices(a, b, f));
+    sum
+}
+
+/// Returns the squared Euclidean distance between `x` and `y`, the
+/// sum of `(x[i] - y[i])²`.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn squared_euclidean<T: Float>(x: &Stride<'_, T>, y: &Stride<'_, T>) -> T {
+    check_lengths(x.len(), y.len(), "squared_euclidean");
+    sum_pairs(x, y, |a, b| (a - b) * (a - b))
+}
+
+/// Returns the Euclidean distance between `x` and `y`, the square
+/// root of `squared_euclidean`.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn euclidean<T: Float>(x: &Stride<'_, T>, y: &Stride<'_, T>) -> T {
+    check_lengths(x.len(), y.len(), "euclidean");
+    sum_pairs(x, y, |a, b| (a - b) * (a - b)).sqrt()
+}
+
+/// Returns the Manhattan distance between `x` and `y`, the sum of
+/// `|x[i] - y[i]|`.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn manhattan<T: Float>(x: &Stride<'_, T>, y: &Stride<'_, T>) -> T {
+    check_lengths(x.len(), y.len(), "manhattan");
+    sum_pairs(x, y, |a, b| (a - b).abs())
+}
+
+/// Returns the cosine of the angle between `x` and `y`, their dot
+/// product divided by the product of their norms. This is NaN if
+/// either is zero.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn cosine_similarity<T: Float>(x: &Stride<'_, T>, y: &Stride<'_, T>) -> T {
+    check_lengths(x.len(), y.len(), "cosine_similarity");
+    let (mut xy, mut xx, mut yy) = (T::ZERO, T::ZERO, T::ZERO);
+    for_blocks(*x, *y, T::ZERO, |a, b| {
+        for (&a, &b) in a.iter().zip(b) {
+            xy = xy + a * b;
+            xx = xx + a * a;
+            yy = yy + b * b;
+        }
+    });
+    xy / (xx * yy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use Stride;
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let v = (0..600).map(|i| (i % 5) as f64).collect::<Vec<_>>();
+        let (x, y) = Stride::new(&v).substrides2();
+        let pairs = || x.iter().zip(y).map(|(&a, &b)| a - b);
+        assert_eq!(squared_euclidean(&x, &y), pairs().map(|d| d * d).sum::<f64>());
+        assert_eq!(euclidean(&x, &y), pairs().map(|d| d * d).sum::<f64>().sqrt());
+        assert_eq!(manhattan(&x, &y), pairs().map(f64::abs).sum::<f64>());
+        assert_eq!(euclidean(&x, &x), 0.0);
+        assert_eq!(cosine_similarity(&Stride::new(&[1.0f32, 1.0]), &Stride::new(&[-2.0, -2.0])), -1.0);
+        assert!(cosine_similarity(&x.slice_to(1), &y.slice_to(1)).is_nan());
+        assert_eq!(manhattan::<f32>(&Stride::empty(), &Stride::empty()), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::distance::manhattan: mismatched lengths 1 and 0")]
+    fn mismatched() {
+        manhattan(&Stride::new(&[1.0]), &Stride::empty());
+    }
+}
@@ -0,0 +1,66 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// The ways in which a requested strided layout can be invalid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StrideError {
+    /// The stride was zero.
+    ZeroStride,
+    /// The stride was negative.
+    NegativeStride,
+    /// The layout extends past the end of the data.
+    OutOfBounds,
+    /// The layout spans more than `isize::MAX` bytes, or its size
+    /// overflows a `usize`.
+    TooLarge,
+    /// The data pointer or the stride was not suitably aligned for
+    /// the element type.
+    Misaligned,
+    /// An index was larger than the length it was checked against.
+    IndexOutOfBounds {
+        /// The offending index.
+        index: usize,
+        /// The length (or count) the index must not exceed.
+        len: usize,
+    },
+    /// A range started after it ended.
+    InvalidRange {
+        /// The start of the range.
+        from: usize,
+        /// The end of the range.
+        to: usize,
+    },
+    /// The rows and columns of a mutable two-dimensional layout were
+    /// not laid out one after another, so might overlap.
+    Overlapping,
+    /// The stride differed from the one required, such as the
+    /// compile-time stride of a `ConstStride`.
+    WrongStride {
+        /// The required stride, in elements.
+        expected: usize,
+        /// The actual stride, in elements.
+        found: usize,
+    },
+}
+
+impl Display for StrideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            StrideError::ZeroStride => f.write_str("stride must be non-zero"),
+            StrideError::NegativeStride => f.write_str("stride must not be negative"),
+            StrideError::OutOfBounds => f.write_str("strided layout extends past the end of the data"),
+            StrideError::TooLarge => f.write_str("strided layout spans more than isize::MAX bytes"),
+            StrideError::Misaligned => f.write_str("strided layout is misaligned for the element type"),
+            StrideError::IndexOutOfBounds { index, len } =>
+                write!(f, "index {} out of bounds for length {}", index, len),
+            StrideError::InvalidRange { from, to } =>
+                write!(f, "range starts at {} but ends at {}", from, to),
+            StrideError::Overlapping => f.write_str("strided layout may refer to an element more than once"),
+            StrideError::WrongStride { expected, found } =>
+                write!(f, "stride {} does not match the expected {}", found, expected),
+        }
+    }
+}
+
+impl Error for StrideError {}
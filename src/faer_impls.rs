@@ -0,0 +1,144 @@
+use std::convert::TryFrom;
+use faer::{ColMut, ColRef, MatMut, MatRef, RowMut, RowRef};
+use base::positive_stride;
+use {Stride, MutStride, MatStride, MutMatStride, StrideError};
+
+// Strides convert into both columns and rows: faer stores matrices
+// column-major, so rows are the usually-strided ones, but either can
+// have any stride.
+macro_rules! faer_impls {
+    ($Ref: ident, $Mut: ident, $len: ident, $stride: ident) => {
+        impl<'a, T> From<Stride<'a, T>> for $Ref<'a, T> {
+            fn from(s: Stride<'a, T>) -> $Ref<'a, T> {
+                unsafe { $Ref::from_raw_parts(s.as_ptr(), s.len(), s.stride() as isize) }
+            }
+        }
+        impl<'a, T> From<MutStride<'a, T>> for $Mut<'a, T> {
+            fn from(mut s: MutStride<'a, T>) -> $Mut<'a, T> {
+                unsafe { $Mut::from_raw_parts_mut(s.as_mut_ptr(), s.len(), s.stride() as isize) }
+            }
+        }
+
+        /// Fails if the view has more than one element and a negative
+        /// or zero stride.
+        impl<'a, T> TryFrom<$Ref<'a, T>> for Stride<'a, T> {
+            type Error = StrideError;
+            fn try_from(v: $Ref<'a, T>) -> Result<Stride<'a, T>, StrideError> {
+                let stride = positive_stride(v.$stride(), v.$len())?;
+                unsafe { Stride::from_raw_parts(v.as_ptr(), v.$len(), stride) }
+            }
+        }
+        /// Fails if the view has more than one element and a negative
+        /// or zero stride.
+        impl<'a, T> TryFrom<$Mut<'a, T>> for MutStride<'a, T> {
+            type Error = StrideError;
+            fn try_from(v: $Mut<'a, T>) -> Result<MutStride<'a, T>, StrideError> {
+                let stride = positive_stride(v.$stride(), v.$len())?;
+                unsafe { MutStride::from_raw_parts(v.as_ptr_mut(), v.$len(), stride) }
+            }
+        }
+    }
+}
+faer_impls!(ColRef, ColMut, nrows, row_stride);
+faer_impls!(RowRef, RowMut, ncols, col_stride);
+
+impl<'a, T> From<MatStride<'a, T>> for MatRef<'a, T> {
+    fn from(m: MatStride<'a, T>) -> MatRef<'a, T> {
+        unsafe {
+            MatRef::from_raw_parts(m.as_ptr(), m.nrows(), m.ncols(),
+                                   m.row_stride() as isize, m.col_stride() as isize)
+        }
+    }
+}
+impl<'a, T> From<MutMatStride<'a, T>> for MatMut<'a, T> {
+    fn from(mut m: MutMatStride<'a, T>) -> MatMut<'a, T> {
+        unsafe {
+            MatMut::from_raw_parts_mut(m.as_mut_ptr(), m.nrows(), m.ncols(),
+                                       m.row_stride() as isize, m.col_stride() as isize)
+        }
+    }
+}
+
+/// Fails if the view has a negative or zero stride along an extent of
+/// more than one.
+impl<'a, T> TryFrom<MatRef<'a, T>> for MatStride<'a, T> {
+    type Error = StrideError;
+    fn try_from(v: MatRef<'a, T>) -> Result<MatStride<'a, T>, StrideError> {
+        let (rows, cols) = (v.nrows(), v.ncols());
+        let rs = positive_stride(v.row_stride(), rows)?;
+        let cs = positive_stride(v.col_stride(), cols)?;
+        unsafe { MatStride::from_raw_parts(v.as_ptr(), rows, cols, rs, cs) }
+    }
+}
+/// Fails if the view has a negative or zero stride along an extent of
+/// more than one.
+impl<'a, T> TryFrom<MatMut<'a, T>> for MutMatStride<'a, T> {
+    type Error = StrideError;
+    fn try_from(v: MatMut<'a, T>) -> Result<MutMatStride<'a, T>, StrideError> {
+        let (rows, cols) = (v.nrows(), v.ncols());
+        let rs = positive_stride(v.row_stride(), rows)?;
+        let cs = positive_stride(v.col_stride(), cols)?;
+        unsafe { MutMatStride::from_raw_parts(v.as_ptr_mut(), rows, cols, rs, cs) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use faer::{mat, ColRef, MatMut, MatRef, RowMut};
+    use {Stride, MutStride, MatStride, MutMatStride, StrideError};
+
+    #[test]
+    fn conversions() {
+        let m = mat![[1.0, 2.0, 3.0],
+                     [4.0, 5.0, 6.0f64]];
+        let row = Stride::try_from(m.row(1)).unwrap();
+        eq!(row, [4.0, 5.0, 6.0]);
+        assert_eq!(row.stride() as isize, m.col_stride());
+        eq!(Stride::try_from(m.col(2)).unwrap(), [3.0, 6.0]);
+        assert_eq!(Stride::try_from(m.row(1).reverse_cols()), Err(StrideError::NegativeStride));
+
+        let v = [1.0, 2.0, 3.0, 4.0, 5.0f64];
+        let c = ColRef::from(Stride::new(&v).substride(2, 0));
+        assert_eq!(c.nrows(), 3);
+        assert_eq!(c.row_stride(), 2);
+        assert_eq!(c.norm_l1(), 9.0);
+
+        let mut w = [1.0, 2.0, 3.0, 4.0f64];
+        {
+            let mut r = RowMut::from(MutStride::new(&mut w).substride_mut(2, 1));
+            r[1] = 40.0;
+            let mut s = MutStride::try_from(r).unwrap();
+            s[0] = 20.0;
+        }
+        assert_eq!(w, [1.0, 20.0, 3.0, 40.0]);
+    }
+
+    #[test]
+    fn matrices() {
+        let m = mat![[1.0, 2.0, 3.0],
+                     [4.0, 5.0, 6.0f64]];
+        let s = MatStride::try_from(m.as_ref()).unwrap();
+        assert_eq!(s.shape(), (2, 3));
+        assert_eq!((s.row_stride() as isize, s.col_stride() as isize), (m.row_stride(), m.col_stride()));
+        eq!(s.row(1), [4.0, 5.0, 6.0]);
+        eq!(MatStride::try_from(m.transpose()).unwrap().col(1), [4.0, 5.0, 6.0]);
+        eq!(MatStride::try_from(m.subcols(1, 2)).unwrap().row(0), [2.0, 3.0]);
+        assert_eq!(MatStride::try_from(m.reverse_rows()), Err(StrideError::NegativeStride));
+        eq!(MatStride::try_from(m.subrows(0, 1).reverse_rows()).unwrap().row(0), [1.0, 2.0, 3.0]);
+
+        let v: Vec<f64> = (0..12).map(|x| x as f64).collect();
+        let r = MatRef::from(MatStride::from_parts(&v, 1, 2, 3, 6, 2).unwrap());
+        assert_eq!((r.nrows(), r.ncols(), r.row_stride(), r.col_stride()), (2, 3, 6, 2));
+        assert_eq!(r[(1, 2)], 11.0);
+
+        let mut w = [0.0f64; 6];
+        {
+            let mut a = MatMut::from(MutMatStride::new(&mut w, 2, 3));
+            a[(0, 1)] = 1.0;
+            let mut b = MutMatStride::try_from(a.transpose_mut()).unwrap();
+            b[(2, 1)] = 2.0;
+        }
+        assert_eq!(w, [0.0, 1.0, 0.0, 0.0, 0.0, 2.0]);
+    }
+}
@@ -0,0 +1,186 @@
+use std::slice;
+use {Stride, MutStride};
+
+#[track_caller]
+fn check_frames(len: usize, n: usize, func: &str) {
+    assert!(len.is_multiple_of(n),
+            "strided::{}: buffer of {} elements is not a whole number of {}-element frames",
+            func, len, n);
+}
+
+/// An interleaved buffer viewed both as a slice of `N`-element frames
+/// and as `N` strided channels.
+///
+/// ```rust
+/// use strided::Frames;
+///
+/// let buf = [1, 10, 2, 20, 3, 30];
+/// let frames = Frames::<_, 2>::new(&buf);
+/// assert_eq!(frames.get(1), Some(&[2, 20]));
+/// assert_eq!(frames.channel::<1>(), [10, 20, 30]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Frames<'a, T: 'a, const N: usize> {
+    frames: &'a [[T; N]],
+}
+
+impl<'a, T, const N: usize> Copy for Frames<'a, T, N> {}
+impl<'a, T, const N: usize> Clone for Frames<'a, T, N> {
+    fn clone(&self) -> Frames<'a, T, N> { *self }
+}
+
+impl<'a, T, const N: usize> Frames<'a, T, N> {
+    /// Views `buf` as consecutive frames of `N` elements.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `buf.len()` is not a multiple of `N`. Using `N == 0`
+    /// is a compile-time error.
+    #[track_caller]
+    pub fn new(buf: &'a [T]) -> Frames<'a, T, N> {
+        const { assert!(N != 0, "strided::Frames: frames must be non-empty") }
+        check_frames(buf.len(), N, "Frames::new");
+        Frames { frames: buf.as_chunks().0 }
+    }
+    /// Views the frames of `frames`.
+    pub fn from_frames(frames: &'a [[T; N]]) -> Frames<'a, T, N> {
+        Frames { frames }
+    }
+
+    /// Returns the number of frames.
+    pub fn len(&self) -> usize { self.frames.len() }
+    /// Returns `true` if there are no frames.
+    pub fn is_empty(&self) -> bool { self.frames.is_empty() }
+    /// Returns the frames as a slice.
+    pub fn as_slice(&self) -> &'a [[T; N]] { self.frames }
+    /// Returns frame `i`, or `None` if it is out of bounds.
+    pub fn get(&self, i: usize) -> Option<&'a [T; N]> { self.frames.get(i) }
+    /// Returns an iterator over the frames.
+    pub fn iter(&self) -> slice::Iter<'a, [T; N]> { self.frames.iter() }
+
+    /// Returns channel `K`, the `K`th element of every frame. Using
+    /// `K >= N` is a compile-time error:
+    ///
+    /// ```rust,compile_fail
+    /// let frames = strided::Frames::<u8, 2>::new(&[0; 4]);
+    /// frames.channel::<2>();
+    /// ```
+    pub fn channel<const K: usize>(&self) -> Stride<'a, T> {
+        const { assert!(K < N, "strided::Frames::channel: channel out of range") }
+        Stride::new(self.frames.as_flattened()).substride(N, K)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for Frames<'a, T, N> {
+    type Item = &'a [T; N];
+    type IntoIter = slice::Iter<'a, [T; N]>;
+    fn into_iter(self) -> slice::Iter<'a, [T; N]> { self.frames.iter() }
+}
+
+/// A mutable interleaved buffer viewed both as a slice of `N`-element
+/// frames and as `N` strided channels.
+///
+/// ```rust
+/// use strided::MutFrames;
+///
+/// let mut buf = [1, 10, 2, 20, 3, 30];
+/// let mut frames = MutFrames::<_, 2>::new(&mut buf);
+/// for f in frames.iter_mut() { f.swap(0, 1) }
+/// for x in frames.channel_mut::<0>() { *x += 1 }
+/// assert_eq!(buf, [11, 1, 21, 2, 31, 3]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct MutFrames<'a, T: 'a, const N: usize> {
+    frames: &'a mut [[T; N]],
+}
+
+impl<'a, T, const N: usize> MutFrames<'a, T, N> {
+    /// Views `buf` as consecutive frames of `N` elements.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `buf.len()` is not a multiple of `N`. Using `N == 0`
+    /// is a compile-time error.
+    #[track_caller]
+    pub fn new(buf: &'a mut [T]) -> MutFrames<'a, T, N> {
+        const { assert!(N != 0, "strided::MutFrames: frames must be non-empty") }
+        check_frames(buf.len(), N, "MutFrames::new");
+        MutFrames { frames: buf.as_chunks_mut().0 }
+    }
+    /// Views the frames of `frames`.
+    pub fn from_frames(frames: &'a mut [[T; N]]) -> MutFrames<'a, T, N> {
+        MutFrames { frames }
+    }
+
+    /// Returns a shorter-lived view of the same frames, so that
+    /// `self` can be used again afterwards.
+    pub fn reborrow(&mut self) -> MutFrames<'_, T, N> {
+        MutFrames { frames: self.frames }
+    }
+    /// Returns a shared view of the frames.
+    pub fn as_frames(&self) -> Frames<'_, T, N> {
+        Frames { frames: self.frames }
+    }
+
+    /// Returns the number of frames.
+    pub fn len(&self) -> usize { self.frames.len() }
+    /// Returns `true` if there are no frames.
+    pub fn is_empty(&self) -> bool { self.frames.is_empty() }
+    /// Returns the frames as a mutable slice.
+    pub fn into_slice(self) -> &'a mut [[T; N]] { self.frames }
+    /// Returns frame `i`, or `None` if it is out of bounds.
+    pub fn get_mut(self, i: usize) -> Option<&'a mut [T; N]> { self.frames.get_mut(i) }
+    /// Returns an iterator over the frames.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, [T; N]> { self.frames.iter_mut() }
+
+    /// Returns channel `K`, the `K`th element of every frame. Using
+    /// `K >= N` is a compile-time error.
+    pub fn channel_mut<const K: usize>(self) -> MutStride<'a, T> {
+        const { assert!(K < N, "strided::MutFrames::channel_mut: channel out of range") }
+        MutStride::new(self.frames.as_flattened_mut()).substride_mut(N, K)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for MutFrames<'a, T, N> {
+    type Item = &'a mut [T; N];
+    type IntoIter = slice::IterMut<'a, [T; N]>;
+    fn into_iter(self) -> slice::IterMut<'a, [T; N]> { self.frames.iter_mut() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frames, MutFrames};
+
+    #[test]
+    fn frames() {
+        let buf = [1, 2, 3, 4, 5, 6];
+        let f = Frames::<_, 3>::new(&buf);
+        assert_eq!(f.len(), 2);
+        assert_eq!(f.iter().collect::<Vec<_>>(), [&[1, 2, 3], &[4, 5, 6]]);
+        assert_eq!(f.channel::<0>(), [1, 4]);
+        assert_eq!(f.channel::<2>(), [3, 6]);
+        assert_eq!(f.get(2), None);
+
+        let e = Frames::<u8, 4>::new(&[]);
+        assert!(e.is_empty());
+        assert_eq!(e.channel::<3>(), [0; 0]);
+        assert_eq!(Frames::from_frames(&[[1, 2]]).channel::<1>(), [2]);
+    }
+
+    #[test]
+    fn mut_frames() {
+        let mut buf = [0; 6];
+        let mut f = MutFrames::<_, 2>::new(&mut buf);
+        for (i, x) in f.reborrow().channel_mut::<1>().into_iter().enumerate() { *x = i }
+        assert_eq!(f.as_frames().channel::<1>(), [0, 1, 2]);
+        for frame in f.iter_mut() { frame[0] = frame[1] * 10 }
+        *f.reborrow().get_mut(0).unwrap() = [7, 7];
+        assert_eq!(f.into_slice(), [[7, 7], [10, 1], [20, 2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::MutFrames::new: buffer of 5 elements is not a whole number of 2-element frames")]
+    fn partial_frame() {
+        MutFrames::<_, 2>::new(&mut [0; 5]);
+    }
+}
@@ -1,38 +1,66 @@
-use std::fmt::{self, Debug};
+use std::fmt::{self, Debug, Display};
+use std::iter::{self, FusedIterator};
 use std::mem;
-use std::ops::Index;
+use std::ops::{Index, Range};
 use base;
 use base::Stride as Base;
+use StrideError;
 
 /// A shared strided slice. This is equivalent to a `&[T]` that only
 /// refers to every `n`th `T`.
-#[repr(C)]
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Like `&'a [T]`, this is covariant in both `'a` and `T`, so a
+/// `Stride<'static, &'static str>` can be used anywhere a
+/// `Stride<'a, &'a str>` is expected.
+#[repr(transparent)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Stride<'a,T: 'a> {
-    base: Base<'a, T>,
+    pub(crate) base: Base<'a, T>,
 }
 impl<'a, T> Copy for Stride<'a, T> {}
 impl<'a, T> Clone for Stride<'a, T> {
     fn clone(&self) -> Stride<'a, T> { *self }
 }
 
+// A `Stride` only ever hands out `&T`, exactly like `&'a [T]`, so it
+// can be shared or sent between threads whenever `T: Sync`.
 unsafe impl<'a, T: Sync> Sync for Stride<'a, T> {}
 unsafe impl<'a, T: Sync> Send for Stride<'a, T> {}
 
+impl<'a, T> Default for Stride<'a, T> {
+    fn default() -> Stride<'a, T> { Stride::empty() }
+}
+
 impl<'a, T: Debug> Debug for Stride<'a, T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.base.fmt(f)
+    }
+}
+
+/// Formats as `[a, b, c]`, or, with the alternate flag (`{:#}`), as
+/// just `a, b, c`. Any other formatting parameters are applied to
+/// each element.
+impl<'a, T: Display> Display for Stride<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.base.fmt(f)
     }
 }
 
 impl<'a, T> Stride<'a, T> {
     #[inline(always)]
-    fn new_raw(base: Base<'a, T>) -> Stride<'a, T> {
+    pub(crate) fn new_raw(base: Base<'a, T>) -> Stride<'a, T> {
         Stride {
-            base: base,
+            base,
         }
     }
 
+    /// Views a `&Base` as a `&Stride`, which is sound because of the
+    /// `repr(transparent)`.
+    #[inline(always)]
+    pub(crate) fn from_base_ref<'b>(base: &'b Base<'a, T>) -> &'b Stride<'a, T> {
+        unsafe { &*(base as *const Base<'a, T> as *const Stride<'a, T>) }
+    }
+
     /// Creates a new strided slice directly from a conventional
     /// slice. The return value has stride 1.
     #[inline(always)]
@@ -40,11 +68,105 @@ impl<'a, T> Stride<'a, T> {
         Stride::new_raw(Base::new(x.as_ptr() as *mut _, x.len(), 1))
     }
 
+    /// Creates a strided slice of the `len` elements of `data` at
+    /// indices `offset`, `offset + stride`, ....
+    ///
+    /// Returns `Err(ZeroStride)` if `stride` is zero, and
+    /// `Err(OutOfBounds)` if the layout does not fit within `data`.
+    ///
+    /// ```rust
+    /// use strided::{Stride, StrideError};
+    /// let v = [1, 2, 3, 4, 5, 6];
+    /// let s = Stride::from_parts(&v, 1, 2, 3).unwrap();
+    /// assert_eq!(s, [2, 4, 6]);
+    /// assert_eq!(Stride::from_parts(&v, 2, 2, 3), Err(StrideError::OutOfBounds));
+    /// ```
+    pub fn from_parts(data: &'a [T], offset: usize, stride: usize, len: usize)
+                      -> Result<Stride<'a, T>, StrideError> {
+        Base::with_layout(data.as_ptr() as *mut _, data.len(), offset, stride, len).map(Stride::new_raw)
+    }
+
+    /// Creates a strided slice of `len` elements, `stride` elements
+    /// apart, starting at `data`.
+    ///
+    /// Returns `Err(ZeroStride)` if `stride` is zero, and
+    /// `Err(TooLarge)` if the elements would span more than
+    /// `isize::MAX` bytes.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be non-null and aligned, and all `len` elements
+    /// must lie within a single allocation, be initialised, and not be
+    /// mutated for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(data: *const T, len: usize, stride: usize)
+                                 -> Result<Stride<'a, T>, StrideError> {
+        Base::from_raw_parts(data as *mut _, len, stride).map(Stride::new_raw)
+    }
+
+    /// Creates a strided slice of `len` elements, `byte_stride` bytes
+    /// apart, starting at `data`, such as a one-dimensional NumPy
+    /// array or a buffer exported through Python's buffer protocol.
+    ///
+    /// Returns `Err(NegativeStride)` or `Err(ZeroStride)` for such
+    /// strides when `len > 1`, and `Err(Misaligned)` if `data` is
+    /// not aligned for `T` or `byte_stride` is not a multiple of its
+    /// size. An empty slice ignores `data` entirely.
+    ///
+    /// # Safety
+    ///
+    /// All `len` elements must lie within a single allocation, be
+    /// initialised, and not be mutated for the lifetime `'a`.
+    pub unsafe fn from_byte_stride(data: *const T, len: usize, byte_stride: isize)
+                                   -> Result<Stride<'a, T>, StrideError> {
+        Base::from_byte_stride(data as *mut _, len, byte_stride).map(Stride::new_raw)
+    }
+
+    /// Creates a strided slice of the field at `byte_offset` within
+    /// each element of `slice`, such as the `x` coordinates of a slice
+    /// of points. The `project_field!` macro does this safely, given
+    /// the name of the field.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the field does not fit within an `S`, if `S` is
+    /// zero-sized, or if the size of `S` is not a multiple of that of
+    /// `T`.
+    ///
+    /// # Safety
+    ///
+    /// `byte_offset` must be the offset of a field of type `T` within
+    /// `S`, such as one given by `std::mem::offset_of!`.
+    #[track_caller]
+    pub unsafe fn project_field<S>(slice: &'a [S], byte_offset: usize) -> Stride<'a, T> {
+        Stride::new_raw(Base::project_field(slice.as_ptr() as *mut S, slice.len(), byte_offset))
+    }
+
+    #[doc(hidden)]
+    #[track_caller]
+    pub unsafe fn __project_field<S>(slice: &'a [S], byte_offset: usize, _: fn(&S) -> &T)
+                                     -> Stride<'a, T> {
+        Stride::project_field(slice, byte_offset)
+    }
+
+    /// Creates a new empty strided slice (with stride 1), not
+    /// pointing to any data.
+    #[inline(always)]
+    pub const fn empty() -> Stride<'a, T> {
+        Stride {
+            base: Base::empty(),
+        }
+    }
+
     /// Returns the number of elements accessible in `self`.
     #[inline(always)]
     pub fn len(&self) -> usize {
         self.base.len()
     }
+    /// Returns `true` if `self` has no elements.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.base.is_empty()
+    }
     /// Returns the offset between successive elements of `self` as a
     /// count of *elements*, not bytes.
     #[inline(always)]
@@ -61,6 +183,127 @@ impl<'a, T> Stride<'a, T> {
     pub fn as_ptr(&self) -> *const T {
         self.base.as_mut_ptr() as *const T
     }
+    /// Returns the range of memory spanned by this strided slice,
+    /// from the start of its first element to the end of its last.
+    ///
+    /// The range also covers the elements in between, which are not
+    /// part of `self`. If `self` is empty, the range is empty and
+    /// starts at `as_ptr()`.
+    ///
+    /// ```rust
+    /// use strided::Stride;
+    /// let v = [1u16, 2, 3, 4, 5, 6];
+    /// let (_, r) = Stride::new(&v).substrides2();
+    /// let range = r.as_ptr_range();
+    /// assert_eq!(range, (&v[1] as *const u16)..v[6..].as_ptr());
+    /// ```
+    #[inline]
+    pub fn as_ptr_range(&self) -> Range<*const T> {
+        let r = self.base.ptr_range();
+        r.start as *const T..r.end as *const T
+    }
+    /// Returns the offset in bytes of the element at index `i` from
+    /// the first element, that is, `i * stride * size_of::<T>()`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn byte_offset_of(&self, i: usize) -> usize {
+        if i >= self.len() {
+            self.base.fail("byte_offset_of", StrideError::IndexOutOfBounds { index: i, len: self.len() })
+        }
+        i * self.base.stride()
+    }
+
+    /// Returns `true` if any element of `self` shares memory with
+    /// any element of `other`.
+    ///
+    /// This is exact, not just a comparison of the address ranges:
+    /// interleaved views such as those from `substrides2` do not
+    /// overlap, even though their ranges do.
+    ///
+    /// ```rust
+    /// use strided::Stride;
+    /// let v = [1u16, 2, 3, 4, 5, 6];
+    /// let (l, r) = Stride::new(&v).substrides2();
+    /// assert!(!l.overlaps(&r));
+    /// assert!(l.overlaps(&Stride::new(&v[4..])));
+    /// ```
+    #[inline]
+    pub fn overlaps<U>(&self, other: &Stride<'_, U>) -> bool {
+        self.base.overlaps(&other.base)
+    }
+
+    /// Returns the index of the element of `self` that `x` refers to,
+    /// or `None` if `x` is not one of them.
+    ///
+    /// This compares addresses, not values, so is useful for
+    /// recovering the position of a reference obtained from `self`.
+    ///
+    /// ```rust
+    /// use strided::Stride;
+    /// let v = [1u16, 2, 3, 4, 5];
+    /// let (l, r) = Stride::new(&v).substrides2();
+    /// assert_eq!(l.index_of_ref(&v[4]), Some(2));
+    /// assert_eq!(l.index_of_ref(&v[3]), None);
+    /// assert_eq!(r.index_of_ref(&3), None);
+    /// ```
+    #[inline]
+    pub fn index_of_ref(&self, x: &T) -> Option<usize> {
+        self.base.index_of(x)
+    }
+    /// Returns `true` if `x` refers to an element of `self`, comparing
+    /// addresses like `index_of_ref`.
+    #[inline]
+    pub fn contains_ref(&self, x: &T) -> bool {
+        self.index_of_ref(x).is_some()
+    }
+
+    /// Returns `true` if `parts` exactly tile `self`: every element of
+    /// each part is an element of `self`, and every element of `self`
+    /// is in exactly one part.
+    ///
+    /// This takes time and memory proportional to `self.len()`.
+    ///
+    /// ```rust
+    /// use strided::Stride;
+    /// let v = [1u16, 2, 3, 4, 5, 6, 7];
+    /// let s = Stride::new(&v);
+    /// let parts = s.substrides(3).collect::<Vec<_>>();
+    /// assert!(s.validate_partition(&parts));
+    /// assert!(!s.validate_partition(&parts[..2]));
+    /// // the right number of elements, but one is repeated
+    /// assert!(!s.validate_partition(&[s.slice_to(4), s.slice(3, 6)]));
+    /// ```
+    pub fn validate_partition(&self, parts: &[Stride<'_, T>]) -> bool {
+        let total = parts.iter().try_fold(0usize, |n, p| n.checked_add(p.len()));
+        if total != Some(self.len()) { return false }
+        let mut seen = vec![false; self.len()];
+        parts.iter().flat_map(|p| p.iter()).all(|x| {
+            match self.base.index_of(x) {
+                Some(i) if !seen[i] => { seen[i] = true; true }
+                _ => false,
+            }
+        })
+    }
+
+    /// Returns `true` if the elements of `self` are adjacent in
+    /// memory, that is, if `self.stride() == 1` or if `self` has at
+    /// most one element.
+    #[inline]
+    pub fn is_contiguous(&self) -> bool {
+        self.base.is_contiguous()
+    }
+
+    /// Returns a conventional slice of the elements of `self` if they
+    /// are adjacent in memory, that is, if `self.stride() == 1` (or
+    /// if `self` has at most one element), and `None` otherwise.
+    #[inline]
+    pub fn as_slice(&self) -> Option<&'a [T]> {
+        self.base.as_slice()
+    }
 
     /// Creates a temporary copy of this strided slice.
     ///
@@ -78,6 +321,40 @@ impl<'a, T> Stride<'a, T> {
     }
 
 
+    /// Fuses two strided slices of `self` back into one, if they tile
+    /// a common parent.
+    ///
+    /// This is the inverse of both `split_at` and `substrides2`: if
+    /// `b` starts immediately after the last element of `a` (with the
+    /// same stride), the result has the elements of `a` followed by
+    /// those of `b`; if `a` and `b` point to alternate elements of
+    /// some strided slice (starting with `a`), the result is that
+    /// slice. An empty slice merges with anything, returning the
+    /// other.
+    ///
+    /// Adjacent addresses alone do not show that `a` and `b` come from
+    /// the same allocation, so the result must also consist only of
+    /// elements of `self`, such as the slice `a` and `b` were split
+    /// from. If any of this fails, `None` is returned.
+    ///
+    /// ```rust
+    /// use strided::Stride;
+    ///
+    /// let v = [1u8, 2, 3, 4, 5];
+    /// let s = Stride::new(&v);
+    /// let (evens, odds) = s.substrides2();
+    /// assert_eq!(s.try_merge(evens, odds), Some(s));
+    ///
+    /// let (start, end) = evens.split_at(1);
+    /// assert_eq!(s.try_merge(start, end), Some(evens));
+    /// assert_eq!(s.try_merge(end, start), None);
+    /// // `evens` does not contain the odd elements.
+    /// assert_eq!(evens.try_merge(evens, odds), None);
+    /// ```
+    pub fn try_merge(&self, a: Stride<'a, T>, b: Stride<'a, T>) -> Option<Stride<'a, T>> {
+        self.base.try_merge_within(a.base, b.base).map(Stride::new_raw)
+    }
+
     /// Breaks this strided slice into two strided slices pointing to
     /// alternate elements.
     ///
@@ -87,11 +364,39 @@ impl<'a, T> Stride<'a, T> {
     /// succeed even if `self.len()` is odd, and even if `self`
     /// has only zero or one elements.
     #[inline]
+    #[track_caller]
     pub fn substrides2(&self) -> (Stride<'a, T>, Stride<'a, T>) {
         let (l, r) = self.base.substrides2();
         (Stride::new_raw(l), Stride::new_raw(r))
     }
 
+    /// Breaks this strided slice into three strided slices, each
+    /// pointing to every third element, starting at offsets 0, 1 and
+    /// 2 respectively.
+    ///
+    /// This is a tuple-returning shorthand for `substrides(3)`,
+    /// convenient for destructuring, e.g. the channels of RGB data.
+    #[inline]
+    #[track_caller]
+    pub fn substrides3(&self) -> (Stride<'a, T>, Stride<'a, T>, Stride<'a, T>) {
+        let (a, b, c) = self.base.substrides3();
+        (Stride::new_raw(a), Stride::new_raw(b), Stride::new_raw(c))
+    }
+
+    /// Breaks this strided slice into four strided slices, each
+    /// pointing to every fourth element, starting at offsets 0, 1, 2
+    /// and 3 respectively.
+    ///
+    /// This is a tuple-returning shorthand for `substrides(4)`,
+    /// convenient for destructuring, e.g. the channels of RGBA data.
+    #[inline]
+    #[track_caller]
+    pub fn substrides4(&self)
+                        -> (Stride<'a, T>, Stride<'a, T>, Stride<'a, T>, Stride<'a, T>) {
+        let (a, b, c, d) = self.base.substrides4();
+        (Stride::new_raw(a), Stride::new_raw(b), Stride::new_raw(c), Stride::new_raw(d))
+    }
+
     /// Returns an iterator over `n` strided subslices of `self` each
     /// pointing to every `n`th element, starting at successive
     /// offsets.
@@ -102,11 +407,69 @@ impl<'a, T> Stride<'a, T> {
     /// (return `n` strided slices) even if `self` has fewer than `n`
     /// elements and if `self.len()` is not a multiple of `n`.
     #[inline]
+    #[track_caller]
     pub fn substrides(&self, n: usize) -> Substrides<'a, T> {
         Substrides {
             base: self.base.substrides(n),
         }
     }
+    /// Returns an iterator over `n` strided subslices of `self`, like
+    /// `substrides`, or `Err(ZeroStride)` if `n` is zero and
+    /// `Err(TooLarge)` if the new stride overflows.
+    #[inline]
+    pub fn try_substrides(&self, n: usize) -> Result<Substrides<'a, T>, StrideError> {
+        self.base.try_substrides(n).map(|base| Substrides { base })
+    }
+    /// Returns an iterator over the frames of `frame_len` consecutive
+    /// elements of `self` starting at every `hop`th element, that is,
+    /// at indices `0`, `hop`, `2 * hop`, ....
+    ///
+    /// The frames overlap when `hop < frame_len`, and skip elements
+    /// when `hop > frame_len`, so a hop of 1 gives sliding windows and
+    /// a hop of `frame_len` gives adjacent chunks. Only complete
+    /// frames are yielded: a trailing partial frame is not.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `frame_len` or `hop` is zero.
+    ///
+    /// ```rust
+    /// use strided::Stride;
+    ///
+    /// let v = [1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6, 0];
+    /// let (left, _) = Stride::new(&v).substrides2();
+    /// let frames = left.chunks_with_hop(3, 2).collect::<Vec<_>>();
+    /// assert_eq!(frames, [[1, 2, 3], [3, 4, 5]]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn chunks_with_hop(&self, frame_len: usize, hop: usize) -> ChunksWithHop<'a, T> {
+        assert!(frame_len != 0, "Stride.chunks_with_hop: frame_len must be non-zero");
+        assert!(hop != 0, "Stride.chunks_with_hop: hop must be non-zero");
+        ChunksWithHop { rest: *self, frame_len, hop }
+    }
+
+    /// Returns the `i`th of the `n` strided subslices that
+    /// `substrides(n)` would yield, that is, the one pointing to
+    /// every `n`th element starting at offset `i`.
+    ///
+    /// This computes the subslice directly, without creating the
+    /// others.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= n`.
+    #[inline]
+    #[track_caller]
+    pub fn substride(&self, n: usize, i: usize) -> Stride<'a, T> {
+        Stride::new_raw(self.base.substride(n, i))
+    }
+    /// Returns the `i`th of the `n` strided subslices, like
+    /// `substride`, or an error instead of panicking if `i >= n`.
+    #[inline]
+    pub fn try_substride(&self, n: usize, i: usize) -> Result<Stride<'a, T>, StrideError> {
+        self.base.try_substride(n, i).map(Stride::new_raw)
+    }
     /// Returns a reference to the `n`th element of `self`, or `None`
     /// if `n` is out-of-bounds.
     #[inline]
@@ -125,6 +488,88 @@ impl<'a, T> Stride<'a, T> {
         self.base.iter()
     }
 
+    /// Returns an iterator over copies of each successive element of
+    /// `self`.
+    ///
+    /// This is equivalent to `self.iter().copied()`, and similarly
+    /// is double-ended and has an exact length.
+    #[inline]
+    pub fn iter_copied(&self) -> iter::Copied<::Items<'a, T>>
+        where T: Copy
+    {
+        self.iter().copied()
+    }
+
+    /// Returns an iterator over clones of each successive element of
+    /// `self`.
+    ///
+    /// This is equivalent to `self.iter().cloned()`, and similarly
+    /// is double-ended and has an exact length.
+    #[inline]
+    pub fn iter_cloned(&self) -> iter::Cloned<::Items<'a, T>>
+        where T: Clone
+    {
+        self.iter().cloned()
+    }
+
+    /// Returns an object that formats the elements of `self` with
+    /// `Display`, separated by `sep`, without allocating.
+    ///
+    /// Formatting parameters (such as width or precision) are
+    /// applied to each element.
+    ///
+    /// ```rust
+    /// use strided::Stride;
+    ///
+    /// let v = [1.0, 2.5, 3.0, 4.25];
+    /// let evens = Stride::new(&v).substrides2().0;
+    /// assert_eq!(format!("{:.2}", evens.display_separated(";")), "1.00;3.00");
+    /// ```
+    #[inline]
+    pub fn display_separated<'s>(&self, sep: &'s str) -> DisplaySeparated<'a, 's, T> {
+        DisplaySeparated {
+            stride: *self,
+            sep,
+        }
+    }
+
+    /// Returns an iterator over pairs `(i, x)` where `x` is each
+    /// successive element of `self` and `i` is the index of `x` in
+    /// `parent`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the elements of `self` do not lie within `parent`.
+    ///
+    /// ```rust
+    /// use strided::Stride;
+    ///
+    /// let v = [1u8, 2, 3, 4, 5, 6];
+    /// let s = Stride::new(&v).substrides(3).nth(1).unwrap();
+    /// let pairs = s.iter_with_parent_indices(&v).collect::<Vec<_>>();
+    /// assert_eq!(pairs, [(1, &2), (4, &5)]);
+    /// ```
+    #[track_caller]
+    pub fn iter_with_parent_indices(&self, parent: &[T]) -> WithParentIndices<'a, T> {
+        let size = mem::size_of::<T>();
+        let start = parent.as_ptr() as usize;
+        let end = start + mem::size_of_val(parent);
+        let first = self.as_ptr() as usize;
+        let offset = if self.is_empty() {
+            0
+        } else {
+            let last = first + (self.len() - 1) * self.base.stride();
+            assert!(start <= first && last < end,
+                    "Stride.iter_with_parent_indices: elements not within parent");
+            (first - start) / size
+        };
+        WithParentIndices {
+            iter: self.iter().enumerate(),
+            offset,
+            stride: self.stride(),
+        }
+    }
+
     /// Returns a strided slice containing only the elements from
     /// indices `from` (inclusive) to `to` (exclusive).
     ///
@@ -132,16 +577,46 @@ impl<'a, T> Stride<'a, T> {
     ///
     /// Panics if `from > to` or if `to > self.len()`.
     #[inline]
+    #[track_caller]
     pub fn slice(&self, from: usize, to: usize) -> Stride<'a, T> {
         Stride::new_raw(self.base.slice(from, to))
     }
     /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive), or
+    /// `Err(InvalidRange)` if `from > to` and `Err(IndexOutOfBounds)`
+    /// if `to > self.len()`.
+    ///
+    /// ```rust
+    /// use strided::{Stride, StrideError};
+    /// let v = [1, 2, 3];
+    /// let s = Stride::new(&v);
+    /// assert_eq!(s.try_slice(1, 3), Ok(Stride::new(&v[1..])));
+    /// assert_eq!(s.try_slice(1, 4), Err(StrideError::IndexOutOfBounds { index: 4, len: 3 }));
+    /// ```
+    #[inline]
+    pub fn try_slice(&self, from: usize, to: usize) -> Result<Stride<'a, T>, StrideError> {
+        self.base.try_slice(from, to).map(Stride::new_raw)
+    }
+    /// Returns a strided slice containing only the elements from
+    /// index `from` (inclusive), or an error if `from > self.len()`.
+    #[inline]
+    pub fn try_slice_from(&self, from: usize) -> Result<Stride<'a, T>, StrideError> {
+        self.base.try_slice_from(from).map(Stride::new_raw)
+    }
+    /// Returns a strided slice containing only the elements to index
+    /// `to` (exclusive), or an error if `to > self.len()`.
+    #[inline]
+    pub fn try_slice_to(&self, to: usize) -> Result<Stride<'a, T>, StrideError> {
+        self.base.try_slice(0, to).map(Stride::new_raw)
+    }
+    /// Returns a strided slice containing only the elements from
     /// index `from` (inclusive).
     ///
     /// # Panic
     ///
     /// Panics if `from > self.len()`.
     #[inline]
+    #[track_caller]
     pub fn slice_from(&self, from: usize) -> Stride<'a, T> {
         Stride::new_raw(self.base.slice_from(from))
     }
@@ -152,9 +627,32 @@ impl<'a, T> Stride<'a, T> {
     ///
     /// Panics if `to > self.len()`.
     #[inline]
+    #[track_caller]
     pub fn slice_to(&self, to: usize) -> Stride<'a, T> {
         Stride::new_raw(self.base.slice_to(to))
     }
+    /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive), after clamping
+    /// both to `self.len()`.
+    ///
+    /// This never panics: out-of-bounds ends are truncated, and if
+    /// `from >= to` (after clamping) the result is empty.
+    #[inline]
+    pub fn slice_clamped(&self, from: usize, to: usize) -> Stride<'a, T> {
+        Stride::new_raw(self.base.slice_clamped(from, to))
+    }
+    /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive), without
+    /// checking the bounds.
+    ///
+    /// # Safety
+    ///
+    /// `from <= to` and `to <= self.len()` must hold, otherwise the
+    /// result points outside of `self`.
+    #[inline]
+    pub unsafe fn slice_unchecked(&self, from: usize, to: usize) -> Stride<'a, T> {
+        Stride::new_raw(self.base.slice_unchecked(from, to))
+    }
     /// Returns two strided slices, the first with elements up to
     /// `idx` (exclusive) and the second with elements from `idx`.
     ///
@@ -165,19 +663,159 @@ impl<'a, T> Stride<'a, T> {
     ///
     /// Panics if `idx > self.len()`.
     #[inline]
+    #[track_caller]
     pub fn split_at(&self, idx: usize) -> (Stride<'a, T>, Stride<'a, T>) {
         let (l, r) = self.base.split_at(idx);
         (Stride::new_raw(l), Stride::new_raw(r))
     }
+    /// Returns two strided slices split at `idx`, like `split_at`, or
+    /// `Err(IndexOutOfBounds)` if `idx > self.len()`.
+    #[inline]
+    pub fn try_split_at(&self, idx: usize) -> Result<(Stride<'a, T>, Stride<'a, T>), StrideError> {
+        let (l, r) = self.base.try_split_at(idx)?;
+        Ok((Stride::new_raw(l), Stride::new_raw(r)))
+    }
+    /// Returns two strided slices, the first with elements up to
+    /// `idx` (exclusive) and the second with elements from `idx`,
+    /// without checking the bounds.
+    ///
+    /// # Safety
+    ///
+    /// `idx <= self.len()` must hold, otherwise the second slice
+    /// points outside of `self`.
+    #[inline]
+    pub unsafe fn split_at_unchecked(&self, idx: usize) -> (Stride<'a, T>, Stride<'a, T>) {
+        let (l, r) = self.base.split_at_unchecked(idx);
+        (Stride::new_raw(l), Stride::new_raw(r))
+    }
+
+    /// Removes the first element from `self` and returns a reference
+    /// to it, or returns `None` if `self` is empty.
+    #[inline]
+    pub fn take_first(&mut self) -> Option<&'a T> {
+        self.base.take_prefix(1).and_then(|s| s.get(0))
+    }
+    /// Removes the last element from `self` and returns a reference
+    /// to it, or returns `None` if `self` is empty.
+    #[inline]
+    pub fn take_last(&mut self) -> Option<&'a T> {
+        self.base.take_suffix(1).and_then(|s| s.get(0))
+    }
+    /// Removes the first `n` elements from `self` and returns them as
+    /// a strided slice, or returns `None` (leaving `self` untouched)
+    /// if `n > self.len()`.
+    #[inline]
+    pub fn take_prefix(&mut self, n: usize) -> Option<Stride<'a, T>> {
+        self.base.take_prefix(n).map(Stride::new_raw)
+    }
+    /// Removes the last `n` elements from `self` and returns them as
+    /// a strided slice, or returns `None` (leaving `self` untouched)
+    /// if `n > self.len()`.
+    #[inline]
+    pub fn take_suffix(&mut self, n: usize) -> Option<Stride<'a, T>> {
+        self.base.take_suffix(n).map(Stride::new_raw)
+    }
 }
 
 impl<'a, T> Index<usize> for Stride<'a, T> {
     type Output = T;
-    fn index<'b>(&'b self, n: usize) -> &'b T {
-        self.get(n).expect("Stride.index: index out of bounds")
+    #[track_caller]
+    fn index(&self, n: usize) -> &T {
+        match self.get(n) {
+            Some(x) => x,
+            None => self.base.fail("index", StrideError::IndexOutOfBounds { index: n, len: self.len() }),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for Stride<'a, T> {
+    type Item = &'a T;
+    type IntoIter = ::Items<'a, T>;
+    #[inline]
+    fn into_iter(self) -> ::Items<'a, T> {
+        self.iter()
+    }
+}
+impl<'a, T> IntoIterator for &Stride<'a, T> {
+    type Item = &'a T;
+    type IntoIter = ::Items<'a, T>;
+    #[inline]
+    fn into_iter(self) -> ::Items<'a, T> {
+        self.iter()
+    }
+}
+
+/// A formatting adapter that displays the elements of a strided slice
+/// with a separator between them.
+///
+/// This is created by `Stride::display_separated`.
+pub struct DisplaySeparated<'a, 's, T: 'a> {
+    stride: Stride<'a, T>,
+    sep: &'s str,
+}
+
+impl<'a, 's, T: Display> Display for DisplaySeparated<'a, 's, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut is_first = true;
+        for x in self.stride.iter() {
+            if is_first {
+                is_first = false;
+            } else {
+                f.write_str(self.sep)?;
+            }
+            Display::fmt(x, f)?
+        }
+        Ok(())
     }
 }
 
+/// An iterator over the elements of a strided slice along with their
+/// indices in some parent slice.
+///
+/// This is created by `Stride::iter_with_parent_indices`.
+pub struct WithParentIndices<'a, T: 'a> {
+    iter: iter::Enumerate<::Items<'a, T>>,
+    offset: usize,
+    stride: usize,
+}
+
+impl<'a, T> Debug for WithParentIndices<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (len, index) = match self.iter.clone().next() {
+            Some((i, _)) => (self.iter.len(), self.offset + i * self.stride),
+            None => (0, self.offset),
+        };
+        f.debug_struct("WithParentIndices")
+            .field("len", &len)
+            .field("stride", &self.stride)
+            .field("parent_index", &index)
+            .finish()
+    }
+}
+
+impl<'a, T> Iterator for WithParentIndices<'a, T> {
+    type Item = (usize, &'a T);
+    #[inline]
+    fn next(&mut self) -> Option<(usize, &'a T)> {
+        let (offset, stride) = (self.offset, self.stride);
+        self.iter.next().map(|(i, x)| (offset + i * stride, x))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+impl<'a, T> DoubleEndedIterator for WithParentIndices<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(usize, &'a T)> {
+        let (offset, stride) = (self.offset, self.stride);
+        self.iter.next_back().map(|(i, x)| (offset + i * stride, x))
+    }
+}
+impl<'a, T> ExactSizeIterator for WithParentIndices<'a, T> {}
+impl<'a, T> FusedIterator for WithParentIndices<'a, T> {}
+
 /// An iterator over `n` shared substrides of a given stride, each of
 /// which points to every `n`th element starting at successive
 /// offsets.
@@ -185,23 +823,588 @@ pub struct Substrides<'a, T: 'a> {
     base: base::Substrides<'a, T>,
 }
 
+unsafe impl<'a, T: Sync> Sync for Substrides<'a, T> {}
+unsafe impl<'a, T: Sync> Send for Substrides<'a, T> {}
+
+impl<'a, T> Debug for Substrides<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.base.fmt(f)
+    }
+}
+
 impl<'a, T> Iterator for Substrides<'a, T> {
     type Item = Stride<'a, T>;
     fn next(&mut self) -> Option<Stride<'a, T>> {
-        match self.base.next() {
-            Some(s) => Some(Stride::new_raw(s)),
-            None => None
-        }
+        self.base.next().map(Stride::new_raw)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         self.base.size_hint()
     }
+
+    fn nth(&mut self, n: usize) -> Option<Stride<'a, T>> {
+        self.base.nth(n).map(Stride::new_raw)
+    }
+}
+impl<'a, T> FusedIterator for Substrides<'a, T> {}
+
+/// An iterator over equally spaced, possibly overlapping, frames of
+/// a strided slice.
+///
+/// This is created by `Stride::chunks_with_hop`.
+#[derive(Clone)]
+pub struct ChunksWithHop<'a, T: 'a> {
+    // starts at the next frame.
+    rest: Stride<'a, T>,
+    frame_len: usize,
+    hop: usize,
+}
+
+impl<'a, T> Debug for ChunksWithHop<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunksWithHop")
+            .field("len", &self.len())
+            .field("frame_len", &self.frame_len)
+            .field("hop", &self.hop)
+            .finish()
+    }
+}
+
+impl<'a, T> Iterator for ChunksWithHop<'a, T> {
+    type Item = Stride<'a, T>;
+    #[inline]
+    fn next(&mut self) -> Option<Stride<'a, T>> {
+        if self.rest.len() < self.frame_len { return None }
+        let frame = self.rest.slice_to(self.frame_len);
+        self.rest = self.rest.slice_from(self.hop.min(self.rest.len()));
+        Some(frame)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = match self.rest.len().checked_sub(self.frame_len) {
+            Some(extra) => extra / self.hop + 1,
+            None => 0,
+        };
+        (n, Some(n))
+    }
+}
+impl<'a, T> ExactSizeIterator for ChunksWithHop<'a, T> {}
+impl<'a, T> FusedIterator for ChunksWithHop<'a, T> {}
+
+/// Creates a strided slice of one field across a slice of structs.
+///
+/// `project_field!(slice, S, field)` views `field` of each `S` in
+/// `slice` as a `Stride`, and `project_field!(mut slice, S, field)`
+/// as a `MutStride`. Nested fields (`a.b`) are also allowed. Fields of
+/// `#[repr(packed)]` structs that may be misaligned are rejected at
+/// compile time.
+///
+/// # Panic
+///
+/// Panics if `S` is zero-sized, or if its size is not a multiple of
+/// the size of the field.
+///
+/// ```rust
+/// #[macro_use] extern crate strided;
+///
+/// struct Point { x: f32, y: f32, id: u32 }
+///
+/// fn main() {
+///     let mut points = vec![Point { x: 1.0, y: 2.0, id: 7 },
+///                           Point { x: 3.0, y: 4.0, id: 8 }];
+///     assert_eq!(project_field!(&points, Point, id), [7, 8]);
+///     for y in project_field!(mut &mut points, Point, y) { *y *= 10.0 }
+///     assert_eq!(points[1].y, 40.0);
+/// }
+/// ```
+///
+/// Reading a field of a union is `unsafe`, so projecting one is
+/// rejected:
+///
+/// ```compile_fail,E0133
+/// #[macro_use] extern crate strided;
+///
+/// union U { a: u8, b: bool }
+///
+/// fn main() {
+///     let us = [U { a: 3 }];
+///     let _ = project_field!(&us[..], U, b);
+/// }
+/// ```
+#[macro_export]
+macro_rules! project_field {
+    (mut $slice: expr, $S: ty, $($field: tt).+) => {
+        match $slice {
+            slice => {
+                // outside the `unsafe` block, so that fields of unions
+                // are rejected.
+                let field: fn(&mut $S) -> &mut _ = |s| &mut s.$($field).+;
+                unsafe {
+                    $crate::MutStride::__project_field_mut(
+                        slice, ::std::mem::offset_of!($S, $($field).+), field)
+                }
+            }
+        }
+    };
+    ($slice: expr, $S: ty, $($field: tt).+) => {
+        match $slice {
+            slice => {
+                let field: fn(&$S) -> &_ = |s| &s.$($field).+;
+                unsafe {
+                    $crate::Stride::__project_field(
+                        slice, ::std::mem::offset_of!($S, $($field).+), field)
+                }
+            }
+        }
+    };
 }
 
 #[cfg(test)]
 #[allow(unused_mut)]
 mod tests {
     use super::Stride;
-    make_tests!(substrides2, substrides, slice, slice_to, slice_from, split_at, get, iter, );
+    #[test]
+    fn chunks_with_hop() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let s = Stride::new(v);
+        let frames = |n, hop| s.chunks_with_hop(n, hop).map(|f| f.iter().cloned().collect()).collect::<Vec<Vec<_>>>();
+        assert_eq!(frames(3, 1), [[1, 2, 3], [2, 3, 4], [3, 4, 5], [4, 5, 6], [5, 6, 7]]);
+        assert_eq!(frames(3, 3), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(frames(2, 4), [[1, 2], [5, 6]]);
+        assert_eq!(frames(1, 10), [[1]]);
+        assert_eq!(frames(8, 1), Vec::<Vec<u16>>::new());
+        let mut it = s.substrides2().0.chunks_with_hop(2, 1);
+        assert_eq!(it.len(), 3);
+        it.next();
+        eq!(it.next().unwrap(), [3, 5]);
+        assert_eq!(it.size_hint(), (1, Some(1)));
+        assert!(format!("{:?}", it).contains("len: 1, frame_len: 2, hop: 1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.chunks_with_hop: hop must be non-zero")]
+    fn chunks_with_zero_hop() {
+        Stride::new(&[1]).chunks_with_hop(1, 0);
+    }
+
+    #[test]
+    fn try_merge() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let s = Stride::new(v);
+        let (l, r) = s.substrides2();
+        let m = s.try_merge(l, r).unwrap();
+        eq!(m, [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!((m.as_ptr(), m.stride()), (s.as_ptr(), 1));
+        assert!(s.try_merge(r, l).is_none());
+
+        let (ll, lr) = l.split_at(1);
+        eq!(s.try_merge(ll, lr).unwrap(), [1, 3, 5, 7]);
+        assert_eq!(l.try_merge(ll, lr).unwrap().stride(), 2);
+        assert!(s.try_merge(lr, ll).is_none());
+
+        let mut it = s.substrides(3);
+        let (a, b, c) = (it.next().unwrap(), it.next().unwrap(), it.next().unwrap());
+        assert!(s.try_merge(a, b).is_none());
+        assert!(s.try_merge(a, c).is_none());
+
+        let (x, y) = s.slice(2, 4).split_at(1);
+        eq!(s.try_merge(x, y).unwrap(), [3, 4]);
+        eq!(s.try_merge(x, s.slice_from(7)).unwrap(), [3]);
+        eq!(s.try_merge(s.slice_to(0), y).unwrap(), [4]);
+        assert!(s.try_merge(s.slice(0, 1), s.slice(5, 7)).is_none());
+
+        // the result must lie within the parent.
+        assert!(l.try_merge(l, r).is_none());
+        assert!(s.slice(0, 3).try_merge(x, y).is_none());
+        assert!(s.slice(3, 7).try_merge(x, y).is_none());
+        eq!(s.slice(2, 4).try_merge(x, y).unwrap(), [3, 4]);
+    }
+
+    #[test]
+    fn try_merge_unrelated() {
+        // two separate buffers may happen to be adjacent in memory,
+        // but no parent contains both.
+        let v = [[1u8, 2, 3, 4], [5, 6, 7, 8]];
+        let (a, b) = (Stride::new(&v[0]), Stride::new(&v[1]));
+        assert!(a.try_merge(a, b).is_none());
+        assert!(b.try_merge(a, b).is_none());
+        let (x, y) = ([1u8, 2], [3u8, 4]);
+        let (x, y) = (Stride::new(&x), Stride::new(&y));
+        assert!(x.try_merge(x, y).is_none() && y.try_merge(x, y).is_none());
+        let w = [9u8, 10];
+        let (c, d) = (Stride::new(&w[..1]), Stride::new(&w[1..]));
+        assert!(a.try_merge(c, d).is_none());
+        eq!(Stride::new(&w).try_merge(c, d).unwrap(), [9, 10]);
+    }
+
+    #[test]
+    fn items_as_stride() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let (l, _) = Stride::new(v).substrides2();
+        let mut it = l.iter();
+        eq!(it.as_stride(), [1, 3, 5, 7]);
+        it.next();
+        it.next_back();
+        assert!(!it.is_empty());
+        let rest = it.as_stride();
+        eq!(rest, [3, 5]);
+        assert_eq!(rest.stride(), 2);
+        it.nth(1);
+        assert!(it.is_empty());
+        eq!(it.as_stride(), []);
+    }
+
+    #[test]
+    fn into_iter() {
+        let v = &[1u16, 2, 3, 4, 5];
+        let (l, _) = Stride::new(v).substrides2();
+        let mut sum = 0;
+        for x in &l { sum += *x }
+        for x in l { sum += *x }
+        assert_eq!(sum, 2 * (1 + 3 + 5));
+    }
+
+    #[test]
+    fn iter_with_parent_indices() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let (_, r) = Stride::new(v).substrides2();
+        let r = r.slice_from(1);
+        assert_eq!(r.iter_with_parent_indices(v).collect::<Vec<_>>(),
+                   [(3, &4), (5, &6)]);
+        assert_eq!(r.iter_with_parent_indices(v).rev().map(|p| p.0).collect::<Vec<_>>(),
+                   [5, 3]);
+        assert_eq!(r.iter_with_parent_indices(&v[3..6]).map(|p| p.0).collect::<Vec<_>>(),
+                   [0, 2]);
+        assert_eq!(r.slice_to(0).iter_with_parent_indices(&[]).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iter_with_parent_indices_outside() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let (_, r) = Stride::new(v).substrides2();
+        r.iter_with_parent_indices(&v[..5]);
+    }
+
+    #[test]
+    fn debug_iterators() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let (l, _) = Stride::new(v).substrides2();
+        let mut it = l.iter();
+        it.next();
+        let s = format!("{:?}", it);
+        assert!(s.starts_with("Items { len: 3, stride: 2, ptr: "), "{}", s);
+
+        let mut it = l.substrides(3);
+        it.next();
+        let s = format!("{:?}", it);
+        assert!(s.starts_with("Substrides { remaining: 2, index: 1, n: 3, stride: 6, "), "{}", s);
+
+        let mut it = l.iter_with_parent_indices(v);
+        it.next();
+        assert_eq!(format!("{:?}", it),
+                   "WithParentIndices { len: 3, stride: 2, parent_index: 2 }");
+    }
+
+    #[test]
+    fn display_separated() {
+        let v = &[1u16, 2, 3, 4, 5];
+        let (l, r) = Stride::new(v).substrides2();
+        assert_eq!(l.display_separated(", ").to_string(), "1, 3, 5");
+        assert_eq!(format!("{:03}", r.display_separated("\t")), "002\t004");
+        assert_eq!(r.slice_to(1).display_separated(",").to_string(), "2");
+        assert_eq!(r.slice_to(0).display_separated(",").to_string(), "");
+    }
+
+    #[test]
+    fn is_contiguous() {
+        let v = &[1u16, 2, 3, 4, 5];
+        let s = Stride::new(v);
+        assert!(s.is_contiguous());
+        let (l, r) = s.substrides2();
+        assert!(!l.is_contiguous());
+        assert!(l.slice(1, 2).is_contiguous());
+        assert!(r.slice(0, 0).is_contiguous());
+    }
+
+    #[test]
+    fn contiguous_fast_paths() {
+        let v = &[1u16, 2, 3, 4, 5, 6];
+        let s = Stride::new(v);
+        let (l, _) = s.substrides2();
+        let order = |it: ::Items<u16>| it.fold(vec![], |mut acc, &x| { acc.push(x); acc });
+        assert_eq!(order(s.iter()), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(order(l.iter()), [1, 3, 5]);
+        assert_eq!(s.iter().rfold(0, |acc, &x| acc * 10 + x as u32), 654321);
+
+        let mut it = s.iter();
+        it.next();
+        it.next_back();
+        assert_eq!(order(it), [2, 3, 4, 5]);
+
+        let (a, b) = (s.slice(0, 3), Stride::new(&v[..3]));
+        assert_eq!(a, b);
+        assert_eq!(l, Stride::new(&[1u16, 3, 5]));
+        assert!(s.slice(1, 3) > l.slice(0, 2));
+        assert_eq!(a.cmp(&b), ::std::cmp::Ordering::Equal);
+    }
+
+    // These only need to compile.
+    #[allow(dead_code)]
+    fn covariant_lifetime<'a, 'b: 'a>(s: Stride<'b, u8>) -> Stride<'a, u8> { s }
+    #[allow(dead_code)]
+    fn covariant_type<'a, 'b>(s: Stride<'b, &'static str>) -> Stride<'b, &'a str> { s }
+    #[allow(dead_code)]
+    fn covariant_items<'a>(i: ::Items<'static, &'static str>) -> ::Items<'a, &'a str> { i }
+    #[allow(dead_code)]
+    fn covariant_substrides<'a>(s: super::Substrides<'static, &'static str>)
+                                -> super::Substrides<'a, &'a str> { s }
+
+    #[test]
+    fn iter_past_allocation() {
+        // the stride runs well past the end of `v` after the last
+        // element, which iteration must never compute a pointer to.
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let s = Stride::new(v).substride(5, 1);
+        eq!(s, [2, 7]);
+        let mut it = s.iter();
+        assert_eq!(it.nth(1), Some(&7));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.as_stride().len(), 0);
+        assert_eq!(s.iter().rev().collect::<Vec<_>>(), [&7, &2]);
+        assert_eq!(s.split_at(2).1.len(), 0);
+        assert_eq!(s.slice_from(2).iter().next(), None);
+    }
+
+    #[test]
+    fn option_niche() {
+        use std::mem::size_of;
+        assert_eq!(size_of::<Option<Stride<u8>>>(), size_of::<Stride<u8>>());
+        assert_eq!(size_of::<Option<::MutStride<u8>>>(), size_of::<::MutStride<u8>>());
+    }
+
+    #[test]
+    fn from_parts() {
+        use StrideError::*;
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        eq!(Stride::from_parts(v, 0, 3, 3).unwrap(), [1, 4, 7]);
+        eq!(Stride::from_parts(v, 1, 3, 2).unwrap(), [2, 5]);
+        eq!(Stride::from_parts(v, 7, 3, 0).unwrap(), []);
+        eq!(Stride::from_parts(v, 6, 100, 1).unwrap(), [7]);
+        assert_eq!(Stride::from_parts(v, 0, 0, 2), Err(ZeroStride));
+        assert_eq!(Stride::from_parts(v, 8, 1, 0), Err(OutOfBounds));
+        assert_eq!(Stride::from_parts(v, 1, 3, 4), Err(OutOfBounds));
+        assert_eq!(Stride::from_parts(v, 0, usize::MAX, 2), Err(TooLarge));
+
+        let p = v.as_ptr();
+        unsafe {
+            eq!(Stride::from_raw_parts(p, 4, 2).unwrap(), [1, 3, 5, 7]);
+            assert_eq!(Stride::from_raw_parts(p, 3, usize::MAX / 4), Err(TooLarge));
+            assert_eq!(Stride::from_raw_parts(p, 1, usize::MAX), Err(TooLarge));
+        }
+    }
+
+    #[test]
+    fn project_field() {
+        #[derive(Clone, Copy)]
+        struct Inner { a: u8, b: u16 }
+        struct Outer { x: u32, inner: Inner, y: (u16, u16) }
+
+        let mut v = (0..4).map(|i| Outer { x: i, inner: Inner { a: i as u8, b: 10 * i as u16 },
+                                         y: (1, i as u16) })
+                          .collect::<Vec<_>>();
+        eq!(project_field!(&v, Outer, x), [0, 1, 2, 3]);
+        eq!(project_field!(&v, Outer, inner.b), [0, 10, 20, 30]);
+        eq!(project_field!(&v[1..3], Outer, y.1), [1, 2]);
+        eq!(project_field!(&v[..0], Outer, inner.a), []);
+        for a in project_field!(mut &mut v, Outer, inner.a) { *a += 100 }
+        assert_eq!(v[3].inner.a, 103);
+
+        let s = unsafe { Stride::<u16>::project_field(&v, ::std::mem::offset_of!(Outer, y)) };
+        assert_eq!(s.stride(), ::std::mem::size_of::<Outer>() / 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.project_field: a 2-byte field at byte 3 does not fit in a 4-byte struct")]
+    fn project_field_out_of_bounds() {
+        unsafe { Stride::<u16>::project_field(&[0u32; 2], 3) };
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.project_field: strided layout is misaligned for the element type (2-byte field in a 3-byte struct)")]
+    fn project_field_uneven() {
+        unsafe { Stride::<[u8; 2]>::project_field(&[[0u8; 3]; 2], 0) };
+    }
+
+    #[test]
+    fn from_byte_stride() {
+        use StrideError::*;
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let p = v.as_ptr();
+        unsafe {
+            eq!(Stride::from_byte_stride(p, 3, 6).unwrap(), [1, 4, 7]);
+            eq!(Stride::from_byte_stride(p.add(6), 1, -6).unwrap(), [7]);
+            eq!(Stride::from_byte_stride(p, 0, 3).unwrap(), []);
+            assert_eq!(Stride::from_byte_stride(p, 2, -2), Err(NegativeStride));
+            assert_eq!(Stride::from_byte_stride(p, 2, 0), Err(ZeroStride));
+            assert_eq!(Stride::from_byte_stride(p, 2, 3), Err(Misaligned));
+            let q = (p as *const u8).add(1) as *const u16;
+            assert_eq!(Stride::from_byte_stride(q, 1, 2), Err(Misaligned));
+        }
+    }
+
+    #[test]
+    fn try_variants() {
+        use StrideError::*;
+        let v = &[1u16, 2, 3, 4, 5];
+        let s = Stride::new(v);
+        eq!(s.try_slice(1, 3).unwrap(), [2, 3]);
+        eq!(s.try_slice(5, 5).unwrap(), []);
+        assert_eq!(s.try_slice(3, 2), Err(InvalidRange { from: 3, to: 2 }));
+        assert_eq!(s.try_slice(0, 6), Err(IndexOutOfBounds { index: 6, len: 5 }));
+        eq!(s.try_slice_from(3).unwrap(), [4, 5]);
+        assert_eq!(s.try_slice_from(6), Err(IndexOutOfBounds { index: 6, len: 5 }));
+        eq!(s.try_slice_to(2).unwrap(), [1, 2]);
+        assert_eq!(s.try_slice_to(6), Err(IndexOutOfBounds { index: 6, len: 5 }));
+
+        let (l, r) = s.try_split_at(2).unwrap();
+        eq!(l, [1, 2]);
+        eq!(r, [3, 4, 5]);
+        assert_eq!(s.try_split_at(6), Err(IndexOutOfBounds { index: 6, len: 5 }));
+
+        assert_eq!(s.try_substrides(2).unwrap().count(), 2);
+        assert_eq!(s.try_substrides(0).err(), Some(ZeroStride));
+        assert_eq!(s.try_substrides(usize::MAX).err(), Some(TooLarge));
+        eq!(s.try_substride(2, 1).unwrap(), [2, 4]);
+        assert_eq!(s.try_substride(2, 2), Err(IndexOutOfBounds { index: 2, len: 2 }));
+        assert_eq!(s.try_substride(0, 0), Err(ZeroStride));
+
+        assert_eq!(InvalidRange { from: 3, to: 2 }.to_string(), "range starts at 3 but ends at 2");
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.slice: index 6 out of bounds for length 3 (stride 2)")]
+    fn slice_panic_message() {
+        let v = &[1u16, 2, 3, 4, 5, 6];
+        Stride::new(v).substrides2().0.slice(1, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.index: index 3 out of bounds for length 3 (stride 2)")]
+    fn index_panic_message() {
+        let v = &[1u16, 2, 3, 4, 5, 6];
+        let _ = Stride::new(v).substrides2().1[3];
+    }
+
+    #[test]
+    fn overlaps() {
+        fn brute<T, U>(a: Stride<T>, b: Stride<U>) -> bool {
+            let (sa, sb) = (::std::mem::size_of::<T>(), ::std::mem::size_of::<U>());
+            a.iter().any(|x| b.iter().any(|y| {
+                let (x, y) = (x as *const T as usize, y as *const U as usize);
+                x < y + sb && y < x + sa
+            }))
+        }
+        let v = &[0u16; 14];
+        let bytes = unsafe {
+            Stride::from_raw_parts(v.as_ptr() as *const u8, 28, 1).unwrap()
+        };
+        let mut views = vec![];
+        for offset in 0..4 {
+            for stride in 1..6 {
+                for len in 0..4 {
+                    if let Ok(s) = Stride::from_parts(v, offset, stride, len) { views.push(s) }
+                }
+            }
+        }
+        for a in &views {
+            for b in &views {
+                assert_eq!(a.overlaps(b), brute(*a, *b), "{:?} {:?}", a.base, b.base);
+            }
+            for offset in 0..5 {
+                for stride in 1..7 {
+                    let b = bytes.slice_from(offset).substride(stride, 0).slice_clamped(0, 3);
+                    assert_eq!(a.overlaps(&b), brute(*a, b));
+                    assert_eq!(b.overlaps(a), brute(b, *a));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validate_partition() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7, 8];
+        let s = Stride::new(v);
+        let (l, r) = s.substrides2();
+        assert!(s.validate_partition(&[r, l]));
+        assert!(s.validate_partition(&[s.slice_to(3), r.slice_from(1), l.slice_from(2)]));
+        assert!(!s.validate_partition(&[l]));
+        assert!(!l.validate_partition(&[s.slice_to(4)]));
+        assert!(Stride::<u16>::empty().validate_partition(&[]));
+
+        // same length, but elsewhere in memory
+        let w = [1u16, 3, 5, 7];
+        assert!(!l.validate_partition(&[Stride::new(&w)]));
+        // within `l`'s range, but between its elements
+        assert!(!l.validate_partition(&[r]));
+    }
+
+    #[test]
+    fn index_of_ref() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let s = Stride::new(v).substride(3, 1);
+        for (i, x) in s.iter().enumerate() {
+            assert_eq!(s.index_of_ref(x), Some(i));
+            assert!(s.contains_ref(x));
+        }
+        for x in &v[..] {
+            assert_eq!(s.contains_ref(x), x % 3 == 2);
+        }
+        assert_eq!(s.slice_to(1).index_of_ref(&v[4]), None);
+        assert_eq!(Stride::<u16>::empty().index_of_ref(&v[0]), None);
+    }
+
+    #[test]
+    fn ptr_range() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        let s = Stride::new(v).substride(3, 1);
+        let r = s.as_ptr_range();
+        assert_eq!(r.start, &v[1] as *const u16);
+        assert_eq!(r.end, &v[5] as *const u16);
+        assert_eq!(s.byte_offset_of(0), 0);
+        assert_eq!(s.byte_offset_of(1), 6);
+        let e = s.slice_from(2).as_ptr_range();
+        assert_eq!(e.start, e.end);
+        assert_eq!(Stride::new(v).as_ptr_range(), v.as_ptr_range());
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.byte_offset_of: index 2 out of bounds for length 2 (stride 3)")]
+    fn byte_offset_of_panic() {
+        let v = &[1u16, 2, 3, 4, 5, 6, 7];
+        Stride::new(v).substride(3, 1).byte_offset_of(2);
+    }
+
+    #[test]
+    fn send_sync() {
+        fn check<T: Send + Sync>(_: &T) {}
+        let v = &[1u16, 2, 3, 4, 5];
+        let s = Stride::new(v);
+        check(&s);
+        check(&s.iter());
+        check(&s.substrides(2));
+
+        let (l, r) = s.substrides2();
+        let (a, b) = ::std::thread::scope(|scope| {
+            let a = scope.spawn(move || l.iter().sum::<u16>());
+            let b = scope.spawn(move || r.iter().sum::<u16>());
+            (a.join().unwrap(), b.join().unwrap())
+        });
+        assert_eq!((a, b), (9, 6));
+    }
+
+    make_tests!(substrides2, substrides3, substrides4, substrides, substride, slice, slice_to, slice_from, slice_clamped,
+                slice_unchecked, split_at, split_at_unchecked,
+                take_first, take_last, take_prefix, take_suffix, get, iter, );
 }
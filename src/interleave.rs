@@ -0,0 +1,258 @@
+use std::cmp;
+use {Stride, MutStride};
+
+// frames per block in the general case: the block of the interleaved
+// buffer stays in cache while each plane fills its lane of it.
+const BLOCK: usize = 64;
+
+#[track_caller]
+fn check<I>(mut plane_lens: I, n: usize, interleaved: usize, func: &str) -> usize
+    where I: Iterator<Item = usize>
+{
+    let len = plane_lens.next().unwrap_or(0);
+    assert!(plane_lens.all(|l| l == len),
+            "strided::{}: planes have different lengths", func);
+    assert!(n.checked_mul(len) == Some(interleaved),
+            "strided::{}: interleaved length {} is not {} planes of length {}",
+            func, interleaved, n, len);
+    len
+}
+
+/// Interleaves the equal-length `planar` slices into `out`, so that
+/// `out[i * n + c] == planar[c][i]` for `n` planes.
+///
+/// This is equivalent to copying each plane into the corresponding
+/// `substrides_mut(n)` of `out`, but is arranged to be much faster:
+/// common channel counts use loops over whole frames, which the
+/// compiler can vectorise, and the rest work through `out` in
+/// cache-sized blocks.
+///
+/// # Panic
+///
+/// Panics if the planes have different lengths, or if `out` is not
+/// exactly long enough to hold them.
+///
+/// ```rust
+/// let mut out = [0; 6];
+/// strided::interleave(&[&[1, 2, 3], &[10, 20, 30]], &mut out);
+/// assert_eq!(out, [1, 10, 2, 20, 3, 30]);
+/// ```
+#[track_caller]
+pub fn interleave<T: Copy>(planar: &[&[T]], out: &mut [T]) {
+    let len = check(planar.iter().map(|p| p.len()), planar.len(), out.len(), "interleave");
+    match planar.len() {
+        0 => {}
+        1 => out.copy_from_slice(planar[0]),
+        2 => interleave_n::<T, 2>(planar, out),
+        3 => interleave_n::<T, 3>(planar, out),
+        4 => interleave_n::<T, 4>(planar, out),
+        n => {
+            for start in (0..len).step_by(BLOCK) {
+                let end = cmp::min(start + BLOCK, len);
+                let block = &mut out[start * n..end * n];
+                for (c, plane) in planar.iter().enumerate() {
+                    for (y, x) in block[c..].iter_mut().step_by(n).zip(&plane[start..end]) {
+                        *y = *x;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn interleave_n<T: Copy, const N: usize>(planar: &[&[T]], out: &mut [T]) {
+    let frames = out.as_chunks_mut::<N>().0;
+    let planes: [&[T]; N] = std::array::from_fn(|c| &planar[c][..frames.len()]);
+    for (i, frame) in frames.iter_mut().enumerate() {
+        for c in 0..N { frame[c] = planes[c][i] }
+    }
+}
+
+/// Splits the frames of `interleaved` into the equal-length `planar`
+/// slices, so that `planar[c][i] == interleaved[i * n + c]` for `n`
+/// planes. This is the inverse of `interleave`.
+///
+/// # Panic
+///
+/// Panics if the planes have different lengths, or if `interleaved`
+/// is not exactly as long as them all together.
+///
+/// ```rust
+/// let (mut a, mut b) = ([0; 3], [0; 3]);
+/// strided::deinterleave(&[1, 10, 2, 20, 3, 30], &mut [&mut a, &mut b]);
+/// assert_eq!((a, b), ([1, 2, 3], [10, 20, 30]));
+/// ```
+#[track_caller]
+pub fn deinterleave<T: Copy>(interleaved: &[T], planar: &mut [&mut [T]]) {
+    let len = check(planar.iter().map(|p| p.len()), planar.len(), interleaved.len(), "deinterleave");
+    match planar.len() {
+        0 => {}
+        1 => planar[0].copy_from_slice(interleaved),
+        2 => deinterleave_n::<T, 2>(interleaved, planar),
+        3 => deinterleave_n::<T, 3>(interleaved, planar),
+        4 => deinterleave_n::<T, 4>(interleaved, planar),
+        n => {
+            for start in (0..len).step_by(BLOCK) {
+                let end = cmp::min(start + BLOCK, len);
+                let block = &interleaved[start * n..end * n];
+                for (c, plane) in planar.iter_mut().enumerate() {
+                    for (y, x) in plane[start..end].iter_mut().zip(block[c..].iter().step_by(n)) {
+                        *y = *x;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn deinterleave_n<T: Copy, const N: usize>(interleaved: &[T], planar: &mut [&mut [T]]) {
+    let frames = interleaved.as_chunks::<N>().0;
+    let mut planes = planar.iter_mut().map(|p| &mut p[..frames.len()]);
+    let planes: [&mut [T]; N] = std::array::from_fn(|_| planes.next().unwrap());
+    for (i, frame) in frames.iter().enumerate() {
+        for c in 0..N { planes[c][i] = frame[c] }
+    }
+}
+
+/// Interleaves the equal-length strided slices `src` into `dst`, so
+/// that `dst[i * n + c] == src[c][i]` for `n` sources.
+///
+/// When every slice is contiguous this is `interleave`; otherwise
+/// `dst` is filled a cache-sized block of frames at a time.
+///
+/// # Panic
+///
+/// Panics if the sources have different lengths, or if `dst` is not
+/// exactly long enough to hold them.
+#[track_caller]
+pub fn interleave_copy<T: Copy>(src: &[Stride<'_, T>], mut dst: MutStride<'_, T>) {
+    let n = src.len();
+    let len = check(src.iter().map(|s| s.len()), n, dst.len(), "interleave_copy");
+    if let Some(out) = dst.as_mut_slice() {
+        if let Some(planes) = src.iter().map(|s| s.as_slice()).collect::<Option<Vec<_>>>() {
+            return interleave(&planes, out)
+        }
+    }
+    for start in (0..len).step_by(BLOCK) {
+        let end = cmp::min(start + BLOCK, len);
+        let block = dst.reborrow().slice_mut(start * n, end * n);
+        for (lane, s) in block.substrides_mut(n).zip(src) {
+            for (x, y) in ::zip(s.slice(start, end), lane) { *y = *x }
+        }
+    }
+}
+
+/// Splits the frames of the strided slice `src` into the
+/// equal-length strided slices `dst`, so that `dst[c][i] == src[i *
+/// n + c]` for `n` destinations. This is the inverse of
+/// `interleave_copy`.
+///
+/// # Panic
+///
+/// Panics if the destinations have different lengths, or if `src` is
+/// not exactly as long as them all together.
+#[track_caller]
+pub fn deinterleave_copy<T: Copy>(src: Stride<'_, T>, dst: &mut [MutStride<'_, T>]) {
+    let n = dst.len();
+    let len = check(dst.iter().map(|d| d.len()), n, src.len(), "deinterleave_copy");
+    if let Some(interleaved) = src.as_slice() {
+        if let Some(mut planes) = dst.iter_mut().map(|d| d.as_mut_slice()).collect::<Option<Vec<_>>>() {
+            return deinterleave(interleaved, &mut planes)
+        }
+    }
+    for start in (0..len).step_by(BLOCK) {
+        let end = cmp::min(start + BLOCK, len);
+        let block = src.slice(start * n, end * n);
+        for (lane, d) in block.substrides(n).zip(dst.iter_mut()) {
+            for (x, y) in ::zip(lane, d.reborrow().slice_mut(start, end)) { *y = *x }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Stride, MutStride};
+    use super::{interleave, deinterleave, interleave_copy, deinterleave_copy};
+
+    #[test]
+    fn round_trip() {
+        for n in 0..9 {
+            for len in [0, 1, 5, 64, 65, 200] {
+                let planes = (0..n).map(|c| (0..len).map(|i| i * 10 + c).collect::<Vec<_>>())
+                                   .collect::<Vec<_>>();
+                let refs = planes.iter().map(|p| &p[..]).collect::<Vec<_>>();
+                let mut out = vec![0; n * len];
+                interleave(&refs, &mut out);
+
+                let mut expected = vec![0; n * len];
+                if n > 0 {
+                    for (s, p) in MutStride::new(&mut expected).substrides_mut(n).zip(&planes) {
+                        for (x, y) in s.into_iter().zip(p) { *x = *y }
+                    }
+                }
+                assert_eq!(out, expected, "{} planes of {}", n, len);
+
+                let mut back = vec![vec![0; len]; n];
+                deinterleave(&out, &mut back.iter_mut().map(|p| &mut p[..]).collect::<Vec<_>>());
+                assert_eq!(back, planes, "{} planes of {}", n, len);
+            }
+        }
+    }
+
+    #[test]
+    fn strided_round_trip() {
+        for n in 0..6 {
+            for len in [0, 1, 7, 64, 150] {
+                // every other element of each source, and of the
+                // interleaved buffer, is padding.
+                let planes = (0..n).map(|c| (0..2 * len).map(|i| i * 10 + c).collect::<Vec<_>>())
+                                   .collect::<Vec<_>>();
+                let src = planes.iter().map(|p| Stride::new(p).substrides2().0).collect::<Vec<_>>();
+                let mut out = vec![0; 2 * n * len];
+                interleave_copy(&src, MutStride::new(&mut out).substrides2_mut().0);
+                for (i, x) in out.iter().enumerate() {
+                    let expected = if i % 2 == 0 { (i / 2 / n) * 20 + i / 2 % n } else { 0 };
+                    assert_eq!(*x, expected, "{} sources of {}, index {}", n, len, i);
+                }
+
+                let contiguous = src.iter().map(|s| s.iter().cloned().collect::<Vec<_>>())
+                                    .collect::<Vec<_>>();
+                let mut dense = vec![0; n * len];
+                let refs = contiguous.iter().map(|p| Stride::new(p)).collect::<Vec<_>>();
+                interleave_copy(&refs, MutStride::new(&mut dense));
+                assert_eq!(Stride::new(&out).substrides2().0, dense);
+
+                let mut back = vec![vec![0; 2 * len]; n];
+                let mut dst = back.iter_mut().map(|p| MutStride::new(p).substrides2_mut().1)
+                                  .collect::<Vec<_>>();
+                deinterleave_copy(Stride::new(&out).substrides2().0, &mut dst);
+                for (b, p) in back.iter().zip(&contiguous) {
+                    assert_eq!(Stride::new(b).substrides2().1, p[..]);
+                }
+
+                let mut back = vec![vec![0; len]; n];
+                let mut dst = back.iter_mut().map(|p| MutStride::new(p)).collect::<Vec<_>>();
+                deinterleave_copy(Stride::new(&dense), &mut dst);
+                assert_eq!(back, contiguous);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::deinterleave_copy: planes have different lengths")]
+    fn ragged_copy() {
+        deinterleave_copy(Stride::new(&[0; 3]), &mut [MutStride::new(&mut [0; 2]), MutStride::new(&mut [0])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::interleave: planes have different lengths")]
+    fn ragged() {
+        interleave(&[&[1, 2], &[3]], &mut [0; 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::deinterleave: interleaved length 5 is not 2 planes of length 2")]
+    fn wrong_length() {
+        deinterleave(&[0; 5], &mut [&mut [0; 2], &mut [0; 2]]);
+    }
+}
@@ -0,0 +1,119 @@
+//! Linear interpolation of strided signals, for basic rate
+//! conversion.
+//!
+//! ```rust
+//! use strided::{Stride, MutStride, interp};
+//!
+//! // stretch the left channel of a stereo buffer to 5 samples.
+//! let stereo = [0.0, 9.0, 1.0, 9.0, 2.0, 9.0];
+//! let mut out = [0.0; 5];
+//! interp::resample_linear(&Stride::new(&stereo).substrides2().0, MutStride::new(&mut out));
+//! assert_eq!(out, [0.0, 0.5, 1.0, 1.5, 2.0]);
+//! ```
+
+use blas1::Float;
+use {Stride, MutStride};
+
+#[inline]
+fn lerp<T: Float>(a: T, b: T, frac: f64) -> T {
+    a + (b - a) * T::from_f64(frac)
+}
+
+/// Fills `dst` with `src` stretched or squashed to its length, by
+/// linear interpolation. The first and last samples of each line up,
+/// so `dst[i]` is `src` at position
+/// `i * (src.len() - 1) / (dst.len() - 1)`.
+///
+/// # Panic
+///
+/// Panics if `src` is empty and `dst` is not.
+#[track_caller]
+pub fn resample_linear<T: Float>(src: &Stride<'_, T>, mut dst: MutStride<'_, T>) {
+    let (n, m) = (src.len(), dst.len());
+    assert!(n != 0 || m == 0, "strided::interp::resample_linear: empty source");
+    if m == 1 {
+        dst[0] = src[0];
+        return
+    }
+    // the integer part of the position is computed exactly.
+    for (i, y) in dst.iter_mut().enumerate() {
+        let (q, r) = (i * (n - 1) / (m - 1), i * (n - 1) % (m - 1));
+        *y = if r == 0 { src[q] } else { lerp(src[q], src[q + 1], r as f64 / (m - 1) as f64) };
+    }
+}
+
+/// Writes `src` at each of `positions` (in units of its samples) to
+/// successive elements of `dst`, by linear interpolation, until either
+/// runs out. Returns the number written.
+///
+/// Positions outside `0..=src.len() - 1` are clamped to it.
+///
+/// # Panic
+///
+/// Panics if `src` is empty and both `positions` and `dst` are not.
+///
+/// ```rust
+/// use strided::{Stride, MutStride, interp};
+///
+/// // a 1.5x speed-up, by stepping through the source faster.
+/// let src = [0.0, 10.0, 20.0, 30.0];
+/// let mut out = [0.0; 3];
+/// let n = interp::lerp_into(&Stride::new(&src), (0..).map(|i| i as f64 * 1.5), MutStride::new(&mut out));
+/// assert_eq!((n, out), (3, [0.0, 15.0, 30.0]));
+/// ```
+#[track_caller]
+pub fn lerp_into<T: Float, I>(src: &Stride<'_, T>, positions: I, dst: MutStride<'_, T>) -> usize
+    where I: IntoIterator<Item = f64>
+{
+    let last = src.len().wrapping_sub(1);
+    let mut count = 0;
+    for (pos, y) in positions.into_iter().zip(dst) {
+        assert!(!src.is_empty(), "strided::interp::lerp_into: empty source");
+        let pos = pos.max(0.0).min(last as f64);
+        let i = pos as usize;
+        *y = if i >= last { src[last] } else { lerp(src[i], src[i + 1], pos - i as f64) };
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use {Stride, MutStride};
+    use super::*;
+
+    #[test]
+    fn resample() {
+        let src = [0.0f32, 3.0, 6.0, 9.0];
+        let mut out = [0.0; 7];
+        resample_linear(&Stride::new(&src), MutStride::new(&mut out));
+        assert_eq!(out, [0.0, 1.5, 3.0, 4.5, 6.0, 7.5, 9.0]);
+
+        let mut down = [0.0; 6];
+        let (_, odd) = MutStride::new(&mut down).substrides2_mut();
+        resample_linear(&Stride::new(&out), odd);
+        assert_eq!(down, [0.0, 0.0, 0.0, 4.5, 0.0, 9.0]);
+
+        resample_linear(&Stride::new(&src), MutStride::new(&mut out[..1]));
+        assert_eq!(out[0], 0.0);
+        resample_linear(&Stride::new(&[2.0]), MutStride::new(&mut out[..2]));
+        assert_eq!(out[..2], [2.0, 2.0]);
+        resample_linear::<f64>(&Stride::empty(), MutStride::new(&mut []));
+    }
+
+    #[test]
+    fn lerp_positions() {
+        let src = [0.0f64, 10.0, 20.0];
+        let mut out = [1.0; 6];
+        let n = lerp_into(&Stride::new(&src), vec![-1.0, 0.25, 1.0, 1.75, 2.0, 7.0, 0.0], MutStride::new(&mut out));
+        assert_eq!((n, out), (6, [0.0, 2.5, 10.0, 17.5, 20.0, 20.0]));
+        assert_eq!(lerp_into(&Stride::new(&src), vec![0.5], MutStride::new(&mut out)), 1);
+        assert_eq!(lerp_into(&Stride::<f64>::empty(), vec![0.5], MutStride::new(&mut [])), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::interp::resample_linear: empty source")]
+    fn empty_source() {
+        resample_linear(&Stride::empty(), MutStride::new(&mut [0.0f32]));
+    }
+}
@@ -191,7 +191,7 @@
 //!     fft(odds, end.reborrow());
 //!
 //!     // exp(-2πi/N)
-//!     let twiddle = Complex::from_polar(&1.0, &(-2.0 * f64::consts::PI / input.len() as f64));
+//!     let twiddle = Complex::from_polar(1.0, -2.0 * f64::consts::PI / input.len() as f64);
 //!
 //!     let mut factor = Complex::new(1., 0.);
 //!
@@ -226,8 +226,26 @@
 
 //#![feature(core)]
 #![cfg_attr(all(test, feature = "unstable"), feature(test))]
+#![cfg_attr(feature = "nightly", feature(trusted_len))]
+#![cfg_attr(feature = "unstable", feature(iter_advance_by, try_trait_v2))]
 
 #[cfg(all(test, feature = "unstable"))] extern crate test;
+#[cfg(feature = "serde")] extern crate serde;
+#[cfg(all(test, feature = "serde"))] extern crate serde_test;
+#[cfg(feature = "arbitrary")] extern crate arbitrary;
+#[cfg(feature = "proptest")] #[macro_use] extern crate proptest;
+#[cfg(feature = "quickcheck")] extern crate quickcheck;
+#[cfg(feature = "ndarray")] extern crate ndarray;
+#[cfg(feature = "nalgebra")] extern crate nalgebra;
+#[cfg(feature = "faer")] extern crate faer;
+#[cfg(feature = "pyo3")] extern crate numpy;
+#[cfg(feature = "arrow")] extern crate arrow_array;
+#[cfg(feature = "image")] extern crate image;
+#[cfg(feature = "memmap2")] extern crate memmap2;
+#[cfg(feature = "bytemuck")] extern crate bytemuck;
+#[cfg(feature = "derive")] extern crate strided_derive;
+#[cfg(feature = "num-complex")] extern crate num_complex;
+#[cfg(feature = "fft")] extern crate rustfft;
 
 pub use base::{Items, MutItems};
 
@@ -236,17 +254,97 @@ pub use mut_::Substrides as MutSubstrides;
 
 pub use imm::Stride as Stride;
 pub use imm::Substrides as Substrides;
+pub use imm::{DisplaySeparated, WithParentIndices, ChunksWithHop};
 
 
-pub use traits::{Strided, MutStrided};
+pub use traits::{Strided, MutStrided, StridedExt};
+
+pub use buf::StridedBuf;
+
+pub use error::StrideError;
+
+pub use zip::{zip, Zip, multizip, MultiZip, Lockstep};
+
+pub use interleave::{interleave, deinterleave, interleave_copy, deinterleave_copy};
+
+pub use frames::{Frames, MutFrames};
+
+pub use shared::SharedMutStride;
+
+pub use writer::{StrideWriter, Full};
+
+pub use mat::{MatStride, MutMatStride, Lines, MutLines, BandRows, Tiles, MutTiles};
+pub use mat::{Border, Stencil, Stencils, transpose_copy};
+
+pub use const_stride::{ConstStride, MutConstStride, ConstItems, MutConstItems};
+
+#[cfg(feature = "derive")]
+pub use strided_derive::StridedFields;
+
+#[cfg(feature = "serde")]
+pub use serde_impls::DeserializeInto;
 
 #[cfg(test)]
 mod common_tests;
 
+pub mod audio;
+pub mod blas1;
+pub mod blas2;
+pub mod distance;
+pub mod interp;
+pub mod nn;
+pub mod polyphase;
+pub mod quant;
+pub mod rolling;
+pub mod stats;
+mod atomic;
 mod base;
+mod buf;
+mod compare;
+mod const_stride;
+mod error;
+mod frames;
 mod mut_;
+mod ops;
 mod imm;
+mod interleave;
+mod mat;
+mod shared;
 mod traits;
+mod uninit;
+mod writer;
+#[macro_use]
+mod zip;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impls;
+#[cfg(feature = "ndarray")]
+mod ndarray_impls;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls;
+#[cfg(feature = "faer")]
+mod faer_impls;
+#[cfg(feature = "pyo3")]
+mod pyo3_impls;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "image")]
+pub mod channels;
+#[cfg(feature = "memmap2")]
+pub mod records;
+#[cfg(feature = "num-complex")]
+mod complex_impls;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impls;
+#[cfg(feature = "cblas")]
+mod cblas;
+#[cfg(feature = "fft")]
+pub mod stft;
 
 #[cfg(all(test, feature = "unstable"))]
 mod bench {
@@ -285,4 +383,45 @@ mod bench {
             for e in s.iter() { test::black_box(e); }
         })
     }
+
+    #[bench]
+    fn sum_slice(b: &mut B) {
+        let v = (0..N).collect::<Vec<_>>();
+        b.iter(|| {
+            test::black_box(&v);
+            v.iter().fold(0, |a, b| a + *b)
+        })
+    }
+
+    #[bench]
+    fn sum_step_13(b: &mut B) {
+        let v = (0..13 * N).collect::<Vec<_>>();
+        let s = Stride::new(&*v);
+        let s = s.substrides(13).next().unwrap();
+        b.iter(|| {
+            test::black_box(&s);
+            s.iter().fold(0, |a, b| a + *b)
+        })
+    }
+
+    #[bench]
+    fn dot_step_1(b: &mut B) {
+        let v = (0..N).map(|i| i as f32).collect::<Vec<_>>();
+        let s = Stride::new(&*v);
+        b.iter(|| {
+            test::black_box(&s);
+            ::blas1::dot(&s, &s)
+        })
+    }
+
+    #[bench]
+    fn dot_step_13(b: &mut B) {
+        let v = (0..13 * N).map(|i| i as f32).collect::<Vec<_>>();
+        let s = Stride::new(&*v);
+        let s = s.substrides(13).next().unwrap();
+        b.iter(|| {
+            test::black_box(&s);
+            ::blas1::dot(&s, &s)
+        })
+    }
 }
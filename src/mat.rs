@@ -0,0 +1,1524 @@
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::marker;
+use std::mem;
+use std::ops::{Index, IndexMut, Range};
+use std::ptr::NonNull;
+use std::slice;
+use base::Stride as Base;
+use {Stride, MutStride, StrideError};
+
+// The layout shared by `MatStride` and `MutMatStride`: element
+// `(i, j)` is `i * row_stride + j * col_stride` elements after `data`.
+// Every element lies within a single allocation, so all these offsets
+// fit in an `isize`, and the stride along an extent of at most one
+// (or of an empty layout) is normalised to 1, so that every row and
+// column is a valid `Stride`.
+// For a `MutMatStride`, no two elements are at the same address.
+struct Raw<T> {
+    data: NonNull<T>,
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+impl<T> Copy for Raw<T> {}
+impl<T> Clone for Raw<T> {
+    fn clone(&self) -> Raw<T> { *self }
+}
+
+// a layout with element `(0, 0)` at `offset` within the `data_len`
+// elements at `data`, if it fits.
+fn checked_raw<T>(data: *mut T, data_len: usize, offset: usize,
+                  (rows, cols): (usize, usize), (row_stride, col_stride): (usize, usize))
+                  -> Result<Raw<T>, StrideError> {
+    let empty = rows == 0 || cols == 0;
+    let row_stride = if rows <= 1 || empty { 1 } else { row_stride };
+    let col_stride = if cols <= 1 || empty { 1 } else { col_stride };
+    if row_stride == 0 || col_stride == 0 { return Err(StrideError::ZeroStride) }
+    let fits = if empty {
+        offset <= data_len
+    } else {
+        (rows - 1).checked_mul(row_stride)
+            .and_then(|r| (cols - 1).checked_mul(col_stride).and_then(|c| r.checked_add(c)))
+            .and_then(|span| span.checked_add(offset))
+            .is_some_and(|last| last < data_len)
+    };
+    if !fits { return Err(StrideError::OutOfBounds) }
+    let data = unsafe { NonNull::new_unchecked(data.add(offset)) };
+    Ok(Raw { data, rows, cols, row_stride, col_stride })
+}
+
+// the layout of a `rows` by `cols` matrix at `data`, which the caller
+// guarantees lies within a single allocation, if its span fits in an
+// `isize`.
+fn raw_parts<T>(data: *mut T, (rows, cols): (usize, usize), (row_stride, col_stride): (usize, usize))
+                -> Result<Raw<T>, StrideError> {
+    let empty = rows == 0 || cols == 0;
+    let row_stride = if rows <= 1 || empty { 1 } else { row_stride };
+    let col_stride = if cols <= 1 || empty { 1 } else { col_stride };
+    if row_stride == 0 || col_stride == 0 { return Err(StrideError::ZeroStride) }
+    if !empty {
+        let fits = (rows - 1).checked_mul(row_stride)
+            .and_then(|r| (cols - 1).checked_mul(col_stride).and_then(|c| r.checked_add(c)))
+            .and_then(|span| span.checked_mul(mem::size_of::<T>()))
+            .is_some_and(|bytes| bytes <= isize::MAX as usize);
+        if !fits { return Err(StrideError::TooLarge) }
+    }
+    let data = unsafe { NonNull::new_unchecked(data) };
+    Ok(Raw { data, rows, cols, row_stride, col_stride })
+}
+
+impl<T> Raw<T> {
+    // `true` if no two elements are at the same address: either the
+    // rows or the columns are laid out one after another.
+    fn is_disjoint(&self) -> bool {
+        let (r, c) = (self.rows, self.cols);
+        r <= 1 || c <= 1 ||
+            c.checked_mul(self.col_stride).is_some_and(|w| w <= self.row_stride) ||
+            r.checked_mul(self.row_stride).is_some_and(|h| h <= self.col_stride)
+    }
+
+    // the layout or its transpose, whichever has the elements of each
+    // row closer together, so that working along its rows touches
+    // memory in order.
+    fn inner_rows(self) -> Raw<T> {
+        if self.col_stride <= self.row_stride { self } else { self.transpose() }
+    }
+
+    // the number of elements, if they are exactly those of a slice.
+    fn contiguous_len(&self) -> Option<usize> {
+        let inner = self.inner_rows();
+        let len = inner.rows * inner.cols;
+        let dense = inner.col_stride == 1 && (inner.rows <= 1 || inner.row_stride == inner.cols);
+        if dense { Some(len) } else { None }
+    }
+
+    fn transpose(self) -> Raw<T> {
+        Raw {
+            data: self.data,
+            rows: self.cols,
+            cols: self.rows,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+
+    // element `(i, j)`, which must be in bounds.
+    #[inline]
+    unsafe fn ptr(&self, i: usize, j: usize) -> *mut T {
+        self.data.as_ptr().add(i * self.row_stride + j * self.col_stride)
+    }
+
+    #[inline]
+    fn get(&self, i: usize, j: usize) -> Option<*mut T> {
+        if i < self.rows && j < self.cols {
+            unsafe { Some(self.ptr(i, j)) }
+        } else {
+            None
+        }
+    }
+
+    // row `i`, which must be in bounds.
+    #[inline]
+    unsafe fn row<'a>(&self, i: usize) -> Base<'a, T> {
+        if self.cols == 0 { return Base::empty() }
+        Base::new(self.ptr(i, 0), self.cols, self.col_stride)
+    }
+
+    fn sub(&self, rows: Range<usize>, cols: Range<usize>) -> Result<Raw<T>, StrideError> {
+        for (r, len) in [(&rows, self.rows), (&cols, self.cols)] {
+            if r.start > r.end {
+                return Err(StrideError::InvalidRange { from: r.start, to: r.end })
+            }
+            if r.end > len {
+                return Err(StrideError::IndexOutOfBounds { index: r.end, len })
+            }
+        }
+        let (n, m) = (rows.len(), cols.len());
+        // an empty view keeps the old pointer, since its first
+        // element may be past the end of the allocation.
+        let data = if n == 0 || m == 0 {
+            self.data
+        } else {
+            unsafe { NonNull::new_unchecked(self.ptr(rows.start, cols.start)) }
+        };
+        let empty = n == 0 || m == 0;
+        Ok(Raw {
+            data,
+            rows: n,
+            cols: m,
+            row_stride: if n <= 1 || empty { 1 } else { self.row_stride },
+            col_stride: if m <= 1 || empty { 1 } else { self.col_stride },
+        })
+    }
+}
+
+// the layout of the tiles of `rows` by `cols` elements of `raw`, and
+// how many there are.
+#[derive(Clone, Copy)]
+struct TileLayout {
+    rows: usize,
+    cols: usize,
+    across: usize,
+}
+
+impl TileLayout {
+    #[track_caller]
+    fn new<T>(raw: &Raw<T>, rows: usize, cols: usize, method: &str) -> (TileLayout, usize) {
+        assert!(rows != 0 && cols != 0, "{}: tile sizes must be non-zero", method);
+        let (down, across) = (raw.rows.div_ceil(rows), raw.cols.div_ceil(cols));
+        (TileLayout { rows, cols, across }, down * across)
+    }
+
+    // tile `k`, which must be less than the count.
+    fn tile<T>(&self, raw: &Raw<T>, k: usize) -> Raw<T> {
+        let (i, j) = (k / self.across * self.rows, k % self.across * self.cols);
+        match raw.sub(i..raw.rows.min(i + self.rows), j..raw.cols.min(j + self.cols)) {
+            Ok(tile) => tile,
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+/// A shared two-dimensional strided view, such as a matrix, an image
+/// plane with padding at the end of each row, or a sub-block of
+/// either.
+///
+/// Element `(i, j)` is `i * row_stride() + j * col_stride()` elements
+/// after the first, so each row and each column is a `Stride`.
+///
+/// ```rust
+/// use strided::MatStride;
+///
+/// // a 2x3 image with a pitch of 4 elements.
+/// let plane = [1, 2, 3, 0, 4, 5, 6, 0];
+/// let m = MatStride::from_parts(&plane, 0, 2, 3, 4, 1).unwrap();
+/// assert_eq!(m[(1, 2)], 6);
+/// assert_eq!(m.row(0), [1, 2, 3]);
+/// assert_eq!(m.col(1), [2, 5]);
+/// assert_eq!(m.transpose().row(1), [2, 5]);
+/// ```
+pub struct MatStride<'a, T: 'a> {
+    raw: Raw<T>,
+    _marker: marker::PhantomData<&'a T>,
+}
+impl<'a, T> Copy for MatStride<'a, T> {}
+impl<'a, T> Clone for MatStride<'a, T> {
+    fn clone(&self) -> MatStride<'a, T> { *self }
+}
+
+unsafe impl<'a, T: Sync> Sync for MatStride<'a, T> {}
+unsafe impl<'a, T: Sync> Send for MatStride<'a, T> {}
+
+impl<'a, T: Debug> Debug for MatStride<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+impl<'a, 'b, T: PartialEq> PartialEq<MatStride<'b, T>> for MatStride<'a, T> {
+    fn eq(&self, other: &MatStride<'b, T>) -> bool {
+        self.shape() == other.shape() && self.rows().zip(other.rows()).all(|(a, b)| a == b)
+    }
+}
+impl<'a, T: Eq> Eq for MatStride<'a, T> {}
+
+impl<'a, T> MatStride<'a, T> {
+    #[inline(always)]
+    fn from_raw(raw: Raw<T>) -> MatStride<'a, T> {
+        MatStride { raw, _marker: marker::PhantomData }
+    }
+
+    /// Views `data` as a `rows` by `cols` matrix stored row after row.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `data` does not have exactly `rows * cols` elements.
+    #[track_caller]
+    pub fn new(data: &'a [T], rows: usize, cols: usize) -> MatStride<'a, T> {
+        assert!(rows.checked_mul(cols) == Some(data.len()),
+                "strided::MatStride::new: {} elements for a {}x{} matrix", data.len(), rows, cols);
+        MatStride::from_parts(data, 0, rows, cols, cols, 1).unwrap()
+    }
+
+    /// Creates a `rows` by `cols` view of `data` with element `(i, j)`
+    /// at index `offset + i * row_stride + j * col_stride`.
+    ///
+    /// Returns `Err(ZeroStride)` if a stride is zero (other than along
+    /// an extent of at most one, or of an empty layout), and `Err(OutOfBounds)` if the layout
+    /// does not fit within `data`. The rows and columns may overlap.
+    pub fn from_parts(data: &'a [T], offset: usize, rows: usize, cols: usize,
+                      row_stride: usize, col_stride: usize)
+                      -> Result<MatStride<'a, T>, StrideError> {
+        checked_raw(data.as_ptr() as *mut T, data.len(), offset,
+                    (rows, cols), (row_stride, col_stride)).map(MatStride::from_raw)
+    }
+
+    /// Creates a `rows` by `cols` matrix with element `(i, j)` at
+    /// `data.add(i * row_stride + j * col_stride)`.
+    ///
+    /// Returns `Err(ZeroStride)` if a stride is zero (other than along
+    /// an extent of at most one, or of an empty layout), and
+    /// `Err(TooLarge)` if the elements would span more than
+    /// `isize::MAX` bytes. The rows and columns may overlap.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be non-null and aligned, and all the elements must
+    /// lie within a single allocation, be initialised, and not be
+    /// mutated for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(data: *const T, rows: usize, cols: usize,
+                                 row_stride: usize, col_stride: usize)
+                                 -> Result<MatStride<'a, T>, StrideError> {
+        raw_parts(data as *mut T, (rows, cols), (row_stride, col_stride)).map(MatStride::from_raw)
+    }
+
+    #[cold]
+    #[track_caller]
+    fn fail(&self, method: &str, e: StrideError) -> ! {
+        panic!("MatStride.{}: {} ({}x{} matrix)", method, e, self.raw.rows, self.raw.cols)
+    }
+
+    /// Returns the number of rows.
+    #[inline]
+    pub fn nrows(&self) -> usize { self.raw.rows }
+    /// Returns the number of columns.
+    #[inline]
+    pub fn ncols(&self) -> usize { self.raw.cols }
+    /// Returns the number of rows and of columns.
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) { (self.raw.rows, self.raw.cols) }
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.raw.rows == 0 || self.raw.cols == 0 }
+    /// Returns the number of elements between the starts of
+    /// consecutive rows.
+    #[inline]
+    pub fn row_stride(&self) -> usize { self.raw.row_stride }
+    /// Returns the number of elements between consecutive elements of
+    /// a row.
+    #[inline]
+    pub fn col_stride(&self) -> usize { self.raw.col_stride }
+    /// Returns a pointer to element `(0, 0)`.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T { self.raw.data.as_ptr() }
+
+    /// Returns element `(i, j)`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, i: usize, j: usize) -> Option<&'a T> {
+        self.raw.get(i, j).map(|p| unsafe { &*p })
+    }
+
+    /// Returns row `i`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= self.nrows()`.
+    #[inline]
+    #[track_caller]
+    pub fn row(&self, i: usize) -> Stride<'a, T> {
+        if i >= self.raw.rows {
+            self.fail("row", StrideError::IndexOutOfBounds { index: i, len: self.raw.rows })
+        }
+        Stride::new_raw(unsafe { self.raw.row(i) })
+    }
+
+    /// Returns column `j`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `j >= self.ncols()`.
+    #[inline]
+    #[track_caller]
+    pub fn col(&self, j: usize) -> Stride<'a, T> {
+        if j >= self.raw.cols {
+            self.fail("col", StrideError::IndexOutOfBounds { index: j, len: self.raw.cols })
+        }
+        Stride::new_raw(unsafe { self.raw.transpose().row(j) })
+    }
+
+    /// Returns an iterator over the rows, from first to last.
+    #[inline]
+    pub fn rows(&self) -> Lines<'a, T> {
+        Lines { raw: self.raw, front: 0, back: self.raw.rows, _marker: marker::PhantomData }
+    }
+
+    /// Returns an iterator over the columns, from first to last.
+    #[inline]
+    pub fn cols(&self) -> Lines<'a, T> {
+        self.transpose().rows()
+    }
+
+    /// Returns an iterator over the parts of the rows within the band
+    /// of `k_lower` diagonals below the main diagonal and `k_upper`
+    /// above it, so row `i` is restricted to the columns
+    /// `i - k_lower ..= i + k_upper`. Each part comes with the index
+    /// of its first column.
+    ///
+    /// ```rust
+    /// use strided::MatStride;
+    ///
+    /// let v: Vec<i32> = (0..16).collect();
+    /// let m = MatStride::new(&v, 4, 4);
+    /// let tridiagonal: Vec<_> = m.band(1, 1).collect();
+    /// assert_eq!(tridiagonal[0], (0, m.row(0).slice(0, 2)));
+    /// assert_eq!(tridiagonal[2].1, [9, 10, 11]);
+    /// assert_eq!(tridiagonal[3], (2, m.row(3).slice(2, 4)));
+    /// ```
+    #[inline]
+    pub fn band(&self, k_lower: usize, k_upper: usize) -> BandRows<'a, T> {
+        BandRows { lines: self.rows(), front: 0, k_lower, k_upper }
+    }
+
+    /// Returns an iterator over the parts of the rows on or below the
+    /// main diagonal, as used by a Cholesky factorisation: row `i` is
+    /// restricted to columns `0..=i`, so each part starts at column 0.
+    ///
+    /// ```rust
+    /// use strided::MatStride;
+    ///
+    /// let m = MatStride::new(&[1, 2, 3, 4, 5, 6], 3, 2);
+    /// let rows: Vec<_> = m.lower_triangle_rows().map(|(_, r)| r).collect();
+    /// assert_eq!(rows, [&[1][..], &[3, 4], &[5, 6]]);
+    /// ```
+    #[inline]
+    pub fn lower_triangle_rows(&self) -> BandRows<'a, T> {
+        self.band(usize::MAX, 0)
+    }
+
+    /// Returns an iterator over the parts of the rows on or above the
+    /// main diagonal: row `i` is restricted to columns `i..`, so each
+    /// part starts at column `i` (or is empty, below a wide matrix).
+    #[inline]
+    pub fn upper_triangle_rows(&self) -> BandRows<'a, T> {
+        self.band(0, usize::MAX)
+    }
+
+    /// Returns an iterator over the elements in row-major order, each
+    /// with its 3x3 neighbourhood, for image filters and stencil
+    /// computations. `border` chooses which elements are visited, and
+    /// what their neighbours past the edges are.
+    ///
+    /// ```rust
+    /// use strided::{MatStride, Border};
+    ///
+    /// let v = [1, 2, 3,
+    ///          4, 5, 6,
+    ///          7, 8, 9];
+    /// let m = MatStride::new(&v, 3, 3);
+    /// let laplacian: Vec<i32> = m.stencils(Border::Clamp)
+    ///     .map(|s| s.north() + s.south() + s.east() + s.west() - 4 * s.center())
+    ///     .collect();
+    /// assert_eq!(laplacian, [4, 3, 2, 1, 0, -1, -2, -3, -4]);
+    /// assert_eq!(m.stencils(Border::Interior).count(), 1);
+    /// ```
+    #[inline]
+    pub fn stencils(&self, border: Border) -> Stencils<'a, T> {
+        let (rows, cols) = self.shape();
+        let (start, end) = match border {
+            Border::Interior if rows < 3 || cols < 3 => ((0, 0), 0),
+            Border::Interior => ((1, 1), rows - 1),
+            Border::Clamp | Border::Wrap if cols == 0 => ((0, 0), 0),
+            Border::Clamp | Border::Wrap => ((0, 0), rows),
+        };
+        Stencils { mat: *self, border, next: start, end }
+    }
+
+    /// Returns the view with rows and columns exchanged.
+    #[inline]
+    pub fn transpose(self) -> MatStride<'a, T> {
+        MatStride::from_raw(self.raw.transpose())
+    }
+
+    /// Returns an iterator over blocks of `rows` by `cols` elements,
+    /// going across each row of blocks before moving down to the next.
+    /// The blocks at the right and bottom edges are smaller if the
+    /// extents are not multiples of the block size.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `rows` or `cols` is zero.
+    ///
+    /// ```rust
+    /// use strided::MatStride;
+    ///
+    /// let v: Vec<i32> = (0..15).collect();
+    /// let m = MatStride::new(&v, 3, 5);
+    /// let shapes: Vec<_> = m.tiles(2, 2).map(|t| t.shape()).collect();
+    /// assert_eq!(shapes, [(2, 2), (2, 2), (2, 1), (1, 2), (1, 2), (1, 1)]);
+    /// assert_eq!(m.tiles(2, 2).nth(4).unwrap().row(0), [12, 13]);
+    /// ```
+    #[track_caller]
+    pub fn tiles(&self, rows: usize, cols: usize) -> Tiles<'a, T> {
+        let (layout, n) = TileLayout::new(&self.raw, rows, cols, "MatStride.tiles");
+        Tiles { raw: self.raw, layout, front: 0, back: n, _marker: marker::PhantomData }
+    }
+
+    /// Returns the block of elements in `rows` and `cols`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if either range starts after it ends, or ends after the
+    /// corresponding extent.
+    #[track_caller]
+    pub fn submatrix(self, rows: Range<usize>, cols: Range<usize>) -> MatStride<'a, T> {
+        match self.raw.sub(rows, cols) {
+            Ok(raw) => MatStride::from_raw(raw),
+            Err(e) => self.fail("submatrix", e),
+        }
+    }
+}
+
+impl<'a, T> Index<(usize, usize)> for MatStride<'a, T> {
+    type Output = T;
+    #[track_caller]
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        match self.get(i, j) {
+            Some(x) => x,
+            None => panic!("MatStride.index: index ({}, {}) out of bounds for a {}x{} matrix",
+                           i, j, self.raw.rows, self.raw.cols),
+        }
+    }
+}
+
+/// A mutable two-dimensional strided view. This is to `MatStride` as
+/// `MutStride` is to `Stride`: like it, the by-value methods consume
+/// `self`, and `reborrow` gives a shorter-lived view to pass to them.
+///
+/// Unlike a `MatStride`, the rows and columns never overlap.
+///
+/// ```rust
+/// use strided::MutMatStride;
+///
+/// let mut v = [0; 6];
+/// let mut m = MutMatStride::new(&mut v, 2, 3);
+/// m[(0, 1)] = 1;
+/// for x in m.reborrow().col_mut(2) { *x = 2 }
+/// assert_eq!(m.transpose_mut().row_mut(2), [2, 2]);
+/// assert_eq!(v, [0, 1, 2, 0, 0, 2]);
+/// ```
+pub struct MutMatStride<'a, T: 'a> {
+    raw: Raw<T>,
+    _marker: marker::PhantomData<&'a mut T>,
+}
+
+unsafe impl<'a, T: Sync> Sync for MutMatStride<'a, T> {}
+unsafe impl<'a, T: Send> Send for MutMatStride<'a, T> {}
+
+impl<'a, T: Debug> Debug for MutMatStride<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_mat().fmt(f)
+    }
+}
+
+impl<'a, T> MutMatStride<'a, T> {
+    #[inline(always)]
+    fn from_raw(raw: Raw<T>) -> MutMatStride<'a, T> {
+        MutMatStride { raw, _marker: marker::PhantomData }
+    }
+
+    /// Views `data` as a `rows` by `cols` matrix stored row after row.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `data` does not have exactly `rows * cols` elements.
+    #[track_caller]
+    pub fn new(data: &'a mut [T], rows: usize, cols: usize) -> MutMatStride<'a, T> {
+        assert!(rows.checked_mul(cols) == Some(data.len()),
+                "strided::MutMatStride::new: {} elements for a {}x{} matrix", data.len(), rows, cols);
+        MutMatStride::from_parts(data, 0, rows, cols, cols, 1).unwrap()
+    }
+
+    /// Creates a `rows` by `cols` view of `data` with element `(i, j)`
+    /// at index `offset + i * row_stride + j * col_stride`.
+    ///
+    /// Returns `Err(ZeroStride)` if a stride is zero (other than along
+    /// an extent of at most one, or of an empty layout), `Err(OutOfBounds)` if the layout
+    /// does not fit within `data`, and `Err(Overlapping)` unless
+    /// either each row ends before the next starts
+    /// (`cols * col_stride <= row_stride`) or each column does
+    /// (`rows * row_stride <= col_stride`).
+    pub fn from_parts(data: &'a mut [T], offset: usize, rows: usize, cols: usize,
+                      row_stride: usize, col_stride: usize)
+                      -> Result<MutMatStride<'a, T>, StrideError> {
+        let raw = checked_raw(data.as_mut_ptr(), data.len(), offset,
+                              (rows, cols), (row_stride, col_stride))?;
+        if !raw.is_disjoint() { return Err(StrideError::Overlapping) }
+        Ok(MutMatStride::from_raw(raw))
+    }
+
+    /// Creates a `rows` by `cols` matrix with element `(i, j)` at
+    /// `data.add(i * row_stride + j * col_stride)`.
+    ///
+    /// Returns the same errors as `from_parts`, except that a layout
+    /// whose elements would span more than `isize::MAX` bytes gives
+    /// `Err(TooLarge)` rather than `Err(OutOfBounds)`.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be non-null and aligned, and all the elements must
+    /// lie within a single allocation, be initialised, and not be
+    /// accessed through any other pointer for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(data: *mut T, rows: usize, cols: usize,
+                                 row_stride: usize, col_stride: usize)
+                                 -> Result<MutMatStride<'a, T>, StrideError> {
+        let raw = raw_parts(data, (rows, cols), (row_stride, col_stride))?;
+        if !raw.is_disjoint() { return Err(StrideError::Overlapping) }
+        Ok(MutMatStride::from_raw(raw))
+    }
+
+    /// Returns a view with a shorter lifetime, for passing to the
+    /// methods that consume `self`.
+    #[inline(always)]
+    pub fn reborrow(&mut self) -> MutMatStride<'_, T> {
+        MutMatStride::from_raw(self.raw)
+    }
+
+    /// Returns a shared view of the elements.
+    #[inline(always)]
+    pub fn as_mat(&self) -> MatStride<'_, T> {
+        MatStride::from_raw(self.raw)
+    }
+
+    /// Converts this into a shared view with the full lifetime `'a`,
+    /// consuming `self`.
+    #[inline(always)]
+    pub fn into_mat(self) -> MatStride<'a, T> {
+        MatStride::from_raw(self.raw)
+    }
+
+    #[cold]
+    #[track_caller]
+    fn fail(&self, method: &str, e: StrideError) -> ! {
+        panic!("MutMatStride.{}: {} ({}x{} matrix)", method, e, self.raw.rows, self.raw.cols)
+    }
+
+    /// Returns the number of rows.
+    #[inline]
+    pub fn nrows(&self) -> usize { self.raw.rows }
+    /// Returns the number of columns.
+    #[inline]
+    pub fn ncols(&self) -> usize { self.raw.cols }
+    /// Returns the number of rows and of columns.
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) { (self.raw.rows, self.raw.cols) }
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.raw.rows == 0 || self.raw.cols == 0 }
+    /// Returns the number of elements between the starts of
+    /// consecutive rows.
+    #[inline]
+    pub fn row_stride(&self) -> usize { self.raw.row_stride }
+    /// Returns the number of elements between consecutive elements of
+    /// a row.
+    #[inline]
+    pub fn col_stride(&self) -> usize { self.raw.col_stride }
+    /// Returns a pointer to element `(0, 0)`.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T { self.raw.data.as_ptr() }
+
+    /// Returns element `(i, j)`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut T> {
+        self.raw.get(i, j).map(|p| unsafe { &mut *p })
+    }
+
+    /// Sets every element to `value`, with one `slice::fill` per row
+    /// (or column) if they are contiguous, or one for the whole view
+    /// if it is.
+    ///
+    /// ```rust
+    /// use strided::MutMatStride;
+    ///
+    /// // the 2x2 top-left corner of a plane with a pitch of 3.
+    /// let mut plane = [0; 9];
+    /// MutMatStride::from_parts(&mut plane, 0, 2, 2, 3, 1).unwrap().fill(1);
+    /// assert_eq!(plane, [1, 1, 0, 1, 1, 0, 0, 0, 0]);
+    /// ```
+    pub fn fill(&mut self, value: T) where T: Clone {
+        if let Some(len) = self.raw.contiguous_len() {
+            // the elements are exactly these, and exclusively borrowed.
+            return unsafe { slice::from_raw_parts_mut(self.raw.data.as_ptr(), len) }.fill(value)
+        }
+        for mut line in MutMatStride::from_raw(self.raw.inner_rows()).rows_mut() {
+            line.fill(value.clone())
+        }
+    }
+
+    /// Copies the elements of `src` into `self`, along whichever of
+    /// the rows or columns of `self` are closer together in memory.
+    /// Each pair of contiguous lines is copied with one `memcpy`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `self` and `src` have different shapes.
+    ///
+    /// ```rust
+    /// use strided::{MatStride, MutMatStride};
+    ///
+    /// // copy a row-major block into a column-major one.
+    /// let mut dst = [0; 4];
+    /// let src = MatStride::new(&[1, 2, 3, 4], 2, 2);
+    /// MutMatStride::from_parts(&mut dst, 0, 2, 2, 1, 2).unwrap().copy_from(&src);
+    /// assert_eq!(dst, [1, 3, 2, 4]);
+    /// ```
+    #[track_caller]
+    pub fn copy_from(&mut self, src: &MatStride<'_, T>) where T: Copy {
+        if self.shape() != src.shape() {
+            panic!("MutMatStride.copy_from: mismatched shapes {}x{} and {}x{}",
+                   self.raw.rows, self.raw.cols, src.nrows(), src.ncols())
+        }
+        if let (Some(len), Some(_)) = (self.raw.contiguous_len(), src.raw.contiguous_len()) {
+            // both are a plain slice, but the two might still be laid
+            // out differently.
+            if (self.raw.row_stride, self.raw.col_stride) == (src.raw.row_stride, src.raw.col_stride) {
+                let dst = unsafe { slice::from_raw_parts_mut(self.raw.data.as_ptr(), len) };
+                return dst.copy_from_slice(unsafe { slice::from_raw_parts(src.raw.data.as_ptr(), len) })
+            }
+        }
+        let (dst, src) = if self.raw.col_stride <= self.raw.row_stride {
+            (self.raw, src.raw)
+        } else {
+            (self.raw.transpose(), src.raw.transpose())
+        };
+        let src = MatStride::from_raw(src);
+        for (mut d, s) in MutMatStride::from_raw(dst).rows_mut().zip(src.rows()) {
+            d.copy_from_stride(s)
+        }
+    }
+
+    /// Copies the elements of `src`, which holds the matrix row after
+    /// row, into `self`, such as when uploading a packed image into a
+    /// pitched plane.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `src` does not have exactly one element for each of
+    /// `self`.
+    #[track_caller]
+    pub fn copy_from_slice_row_major(&mut self, src: &[T]) where T: Copy {
+        let (rows, cols) = self.shape();
+        if rows * cols != src.len() {
+            panic!("MutMatStride.copy_from_slice_row_major: {} elements for a {}x{} matrix",
+                   src.len(), rows, cols)
+        }
+        self.copy_from(&MatStride::new(src, rows, cols))
+    }
+
+    /// Exchanges rows `a` and `b`, such as for pivoting.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `a` or `b` is out of bounds.
+    ///
+    /// ```rust
+    /// use strided::MutMatStride;
+    ///
+    /// let mut v = [1, 2, 3, 4, 5, 6];
+    /// let mut m = MutMatStride::new(&mut v, 3, 2);
+    /// m.swap_rows(0, 2);
+    /// m.swap_cols(0, 1);
+    /// assert_eq!(v, [6, 5, 4, 3, 2, 1]);
+    /// ```
+    #[track_caller]
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        if let Err(e) = swap_lines(self.raw, a, b) { self.fail("swap_rows", e) }
+    }
+
+    /// Exchanges columns `a` and `b`, such as for pivoting.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `a` or `b` is out of bounds.
+    #[track_caller]
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        if let Err(e) = swap_lines(self.raw.transpose(), a, b) { self.fail("swap_cols", e) }
+    }
+
+    /// Returns row `i`, consuming `self`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= self.nrows()`.
+    #[inline]
+    #[track_caller]
+    pub fn row_mut(self, i: usize) -> MutStride<'a, T> {
+        if i >= self.raw.rows {
+            self.fail("row_mut", StrideError::IndexOutOfBounds { index: i, len: self.raw.rows })
+        }
+        MutStride::new_raw(unsafe { self.raw.row(i) })
+    }
+
+    /// Returns column `j`, consuming `self`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `j >= self.ncols()`.
+    #[inline]
+    #[track_caller]
+    pub fn col_mut(self, j: usize) -> MutStride<'a, T> {
+        if j >= self.raw.cols {
+            self.fail("col_mut", StrideError::IndexOutOfBounds { index: j, len: self.raw.cols })
+        }
+        MutStride::new_raw(unsafe { self.raw.transpose().row(j) })
+    }
+
+    /// Returns an iterator over the rows, from first to last.
+    #[inline]
+    pub fn rows_mut(self) -> MutLines<'a, T> {
+        MutLines { raw: self.raw, front: 0, back: self.raw.rows, _marker: marker::PhantomData }
+    }
+
+    /// Returns an iterator over the columns, from first to last.
+    #[inline]
+    pub fn cols_mut(self) -> MutLines<'a, T> {
+        self.transpose_mut().rows_mut()
+    }
+
+    /// Returns the view with rows and columns exchanged.
+    #[inline]
+    pub fn transpose_mut(self) -> MutMatStride<'a, T> {
+        MutMatStride::from_raw(self.raw.transpose())
+    }
+
+    /// Returns an iterator over disjoint blocks of `rows` by `cols`
+    /// elements, in the order of `MatStride::tiles`, consuming `self`.
+    /// The blocks can be modified independently, such as on different
+    /// threads.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `rows` or `cols` is zero.
+    #[track_caller]
+    pub fn tiles_mut(self, rows: usize, cols: usize) -> MutTiles<'a, T> {
+        let (layout, n) = TileLayout::new(&self.raw, rows, cols, "MutMatStride.tiles_mut");
+        MutTiles { raw: self.raw, layout, front: 0, back: n, _marker: marker::PhantomData }
+    }
+
+    /// Returns the block of elements in `rows` and `cols`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if either range starts after it ends, or ends after the
+    /// corresponding extent.
+    #[track_caller]
+    pub fn submatrix_mut(self, rows: Range<usize>, cols: Range<usize>) -> MutMatStride<'a, T> {
+        match self.raw.sub(rows, cols) {
+            Ok(raw) => MutMatStride::from_raw(raw),
+            Err(e) => self.fail("submatrix_mut", e),
+        }
+    }
+
+    /// Splits the view into rows `0..i` and rows `i..`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i > self.nrows()`.
+    #[track_caller]
+    pub fn split_at_row_mut(self, i: usize) -> (MutMatStride<'a, T>, MutMatStride<'a, T>) {
+        let (rows, cols) = (self.raw.rows, self.raw.cols);
+        match (self.raw.sub(0..i, 0..cols), self.raw.sub(i..rows, 0..cols)) {
+            (Ok(a), Ok(b)) => (MutMatStride::from_raw(a), MutMatStride::from_raw(b)),
+            (Err(e), _) | (_, Err(e)) => self.fail("split_at_row_mut", e),
+        }
+    }
+
+    /// Splits the view into columns `0..j` and columns `j..`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `j > self.ncols()`.
+    #[track_caller]
+    pub fn split_at_col_mut(self, j: usize) -> (MutMatStride<'a, T>, MutMatStride<'a, T>) {
+        let (rows, cols) = (self.raw.rows, self.raw.cols);
+        match (self.raw.sub(0..rows, 0..j), self.raw.sub(0..rows, j..cols)) {
+            (Ok(a), Ok(b)) => (MutMatStride::from_raw(a), MutMatStride::from_raw(b)),
+            (Err(e), _) | (_, Err(e)) => self.fail("split_at_col_mut", e),
+        }
+    }
+}
+
+// swaps rows `a` and `b` of the layout of a `MutMatStride`.
+fn swap_lines<T>(raw: Raw<T>, a: usize, b: usize) -> Result<(), StrideError> {
+    let index = a.max(b);
+    if index >= raw.rows { return Err(StrideError::IndexOutOfBounds { index, len: raw.rows }) }
+    if a != b {
+        // distinct rows of a `MutMatStride` never overlap.
+        let (mut x, mut y) = unsafe { (MutStride::new_raw(raw.row(a)), MutStride::new_raw(raw.row(b))) };
+        x.swap_with_stride(&mut y);
+    }
+    Ok(())
+}
+
+impl<'a, T> Index<(usize, usize)> for MutMatStride<'a, T> {
+    type Output = T;
+    #[track_caller]
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        match self.raw.get(i, j) {
+            Some(x) => unsafe { &*x },
+            None => panic!("MutMatStride.index: index ({}, {}) out of bounds for a {}x{} matrix",
+                           i, j, self.raw.rows, self.raw.cols),
+        }
+    }
+}
+impl<'a, T> IndexMut<(usize, usize)> for MutMatStride<'a, T> {
+    #[track_caller]
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        match self.raw.get(i, j) {
+            Some(x) => unsafe { &mut *x },
+            None => panic!("MutMatStride.index_mut: index ({}, {}) out of bounds for a {}x{} matrix",
+                           i, j, self.raw.rows, self.raw.cols),
+        }
+    }
+}
+
+// the side of the square tiles `transpose_copy` works through, small
+// enough that a tile of the source and of the destination, read along
+// either dimension, stay in the L1 cache.
+const TILE: usize = 32;
+
+/// Copies the transpose of `src` into `dst`, so that
+/// `dst[(j, i)] == src[(i, j)]`.
+///
+/// One of the two is necessarily read or written across its rows,
+/// so the copy works through square tiles of both, to touch each
+/// cache line only once rather than once per element.
+///
+/// # Panic
+///
+/// Panics if `dst` does not have as many rows as `src` has columns,
+/// and vice versa.
+///
+/// ```rust
+/// use strided::{MatStride, MutMatStride};
+///
+/// let mut t = [0; 6];
+/// strided::transpose_copy(&MatStride::new(&[1, 2, 3, 4, 5, 6], 2, 3),
+///                         MutMatStride::new(&mut t, 3, 2));
+/// assert_eq!(t, [1, 4, 2, 5, 3, 6]);
+/// ```
+#[track_caller]
+pub fn transpose_copy<T: Copy>(src: &MatStride<'_, T>, dst: MutMatStride<'_, T>) {
+    let (rows, cols) = src.shape();
+    assert!(dst.shape() == (cols, rows),
+            "strided::transpose_copy: {}x{} source for a {}x{} destination",
+            rows, cols, dst.nrows(), dst.ncols());
+    for i0 in (0..rows).step_by(TILE) {
+        let i1 = rows.min(i0 + TILE);
+        for j0 in (0..cols).step_by(TILE) {
+            let j1 = cols.min(j0 + TILE);
+            for i in i0..i1 {
+                for j in j0..j1 {
+                    // in bounds by the loop ranges, and the shapes.
+                    unsafe { *dst.raw.ptr(j, i) = *src.raw.ptr(i, j) }
+                }
+            }
+        }
+    }
+}
+
+/// An iterator over the rows or the columns of a `MatStride`.
+pub struct Lines<'a, T: 'a> {
+    raw: Raw<T>,
+    front: usize,
+    back: usize,
+    _marker: marker::PhantomData<&'a T>,
+}
+impl<'a, T> Clone for Lines<'a, T> {
+    fn clone(&self) -> Lines<'a, T> {
+        Lines { ..*self }
+    }
+}
+unsafe impl<'a, T: Sync> Sync for Lines<'a, T> {}
+unsafe impl<'a, T: Sync> Send for Lines<'a, T> {}
+
+impl<'a, T> Iterator for Lines<'a, T> {
+    type Item = Stride<'a, T>;
+    #[inline]
+    fn next(&mut self) -> Option<Stride<'a, T>> {
+        if self.front == self.back { return None }
+        self.front += 1;
+        Some(Stride::new_raw(unsafe { self.raw.row(self.front - 1) }))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+impl<'a, T> DoubleEndedIterator for Lines<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Stride<'a, T>> {
+        if self.front == self.back { return None }
+        self.back -= 1;
+        Some(Stride::new_raw(unsafe { self.raw.row(self.back) }))
+    }
+}
+impl<'a, T> ExactSizeIterator for Lines<'a, T> {}
+impl<'a, T> FusedIterator for Lines<'a, T> {}
+
+/// An iterator over the rows or the columns of a `MutMatStride`.
+pub struct MutLines<'a, T: 'a> {
+    raw: Raw<T>,
+    front: usize,
+    back: usize,
+    _marker: marker::PhantomData<&'a mut T>,
+}
+unsafe impl<'a, T: Sync> Sync for MutLines<'a, T> {}
+unsafe impl<'a, T: Send> Send for MutLines<'a, T> {}
+
+// the lines of a `MutMatStride` are disjoint, so each can be handed
+// out with the full lifetime.
+impl<'a, T> Iterator for MutLines<'a, T> {
+    type Item = MutStride<'a, T>;
+    #[inline]
+    fn next(&mut self) -> Option<MutStride<'a, T>> {
+        if self.front == self.back { return None }
+        self.front += 1;
+        Some(MutStride::new_raw(unsafe { self.raw.row(self.front - 1) }))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+impl<'a, T> DoubleEndedIterator for MutLines<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<MutStride<'a, T>> {
+        if self.front == self.back { return None }
+        self.back -= 1;
+        Some(MutStride::new_raw(unsafe { self.raw.row(self.back) }))
+    }
+}
+impl<'a, T> ExactSizeIterator for MutLines<'a, T> {}
+impl<'a, T> FusedIterator for MutLines<'a, T> {}
+
+/// An iterator over the parts of the rows of a `MatStride` within a
+/// band around the diagonal, with the index of the first column of
+/// each, returned by `band` and the triangle methods.
+#[derive(Clone)]
+pub struct BandRows<'a, T: 'a> {
+    lines: Lines<'a, T>,
+    // the index of the next row from the front.
+    front: usize,
+    k_lower: usize,
+    k_upper: usize,
+}
+
+impl<'a, T> BandRows<'a, T> {
+    fn restrict(&self, i: usize, row: Stride<'a, T>) -> (usize, Stride<'a, T>) {
+        let len = row.len();
+        let start = i.saturating_sub(self.k_lower).min(len);
+        let end = i.saturating_add(self.k_upper).saturating_add(1).clamp(start, len);
+        (start, row.slice(start, end))
+    }
+}
+
+impl<'a, T> Iterator for BandRows<'a, T> {
+    type Item = (usize, Stride<'a, T>);
+    #[inline]
+    fn next(&mut self) -> Option<(usize, Stride<'a, T>)> {
+        let row = self.lines.next()?;
+        self.front += 1;
+        Some(self.restrict(self.front - 1, row))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lines.size_hint()
+    }
+}
+impl<'a, T> DoubleEndedIterator for BandRows<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(usize, Stride<'a, T>)> {
+        let row = self.lines.next_back()?;
+        Some(self.restrict(self.front + self.lines.len(), row))
+    }
+}
+impl<'a, T> ExactSizeIterator for BandRows<'a, T> {}
+impl<'a, T> FusedIterator for BandRows<'a, T> {}
+
+/// How `MatStride::stencils` treats the edges of the view.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Border {
+    /// Only visit the elements whose neighbours are all within the
+    /// view, skipping the outermost rows and columns.
+    Interior,
+    /// Visit every element, with neighbours past an edge replaced by
+    /// the nearest element on it.
+    Clamp,
+    /// Visit every element, with neighbours past an edge taken from
+    /// the opposite edge, as on a torus.
+    Wrap,
+}
+
+/// An element of a `MatStride` with its 3x3 neighbourhood, as yielded
+/// by `stencils`. North is towards row 0, and west towards column 0.
+pub struct Stencil<'a, T: 'a> {
+    position: (usize, usize),
+    cells: [[&'a T; 3]; 3],
+}
+impl<'a, T> Copy for Stencil<'a, T> {}
+impl<'a, T> Clone for Stencil<'a, T> {
+    fn clone(&self) -> Stencil<'a, T> { *self }
+}
+impl<'a, T: Debug> Debug for Stencil<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stencil")
+            .field("position", &self.position)
+            .field("cells", &self.cells)
+            .finish()
+    }
+}
+
+impl<'a, T> Stencil<'a, T> {
+    /// Returns the row and column of the centre element.
+    #[inline]
+    pub fn position(&self) -> (usize, usize) { self.position }
+    /// Returns the neighbourhood, with the centre element at `[1][1]`
+    /// and its northern neighbour at `[0][1]`.
+    #[inline]
+    pub fn cells(&self) -> [[&'a T; 3]; 3] { self.cells }
+    /// Returns the centre element.
+    #[inline]
+    pub fn center(&self) -> &'a T { self.cells[1][1] }
+    /// Returns the neighbour in the previous row.
+    #[inline]
+    pub fn north(&self) -> &'a T { self.cells[0][1] }
+    /// Returns the neighbour in the next row.
+    #[inline]
+    pub fn south(&self) -> &'a T { self.cells[2][1] }
+    /// Returns the neighbour in the next column.
+    #[inline]
+    pub fn east(&self) -> &'a T { self.cells[1][2] }
+    /// Returns the neighbour in the previous column.
+    #[inline]
+    pub fn west(&self) -> &'a T { self.cells[1][0] }
+}
+
+/// An iterator over the elements of a `MatStride` with their
+/// neighbourhoods, returned by `stencils`.
+pub struct Stencils<'a, T: 'a> {
+    mat: MatStride<'a, T>,
+    border: Border,
+    // the position of the next element, and the row to stop at.
+    next: (usize, usize),
+    end: usize,
+}
+impl<'a, T> Clone for Stencils<'a, T> {
+    fn clone(&self) -> Stencils<'a, T> {
+        Stencils { ..*self }
+    }
+}
+
+impl<'a, T> Stencils<'a, T> {
+    // the columns visited in each row.
+    fn col_range(&self) -> Range<usize> {
+        match self.border {
+            Border::Interior => 1..self.mat.ncols() - 1,
+            Border::Clamp | Border::Wrap => 0..self.mat.ncols(),
+        }
+    }
+
+    // the index `d` steps from `i`, along an extent of `n`.
+    #[inline]
+    fn offset(&self, i: usize, d: usize, n: usize) -> usize {
+        match (self.border, d) {
+            (_, 1) => i,
+            (Border::Interior, _) => i + d - 1,
+            (Border::Clamp, 0) => i.saturating_sub(1),
+            (Border::Clamp, _) => (i + 1).min(n - 1),
+            (Border::Wrap, 0) => if i == 0 { n - 1 } else { i - 1 },
+            (Border::Wrap, _) => if i + 1 == n { 0 } else { i + 1 },
+        }
+    }
+}
+
+impl<'a, T> Iterator for Stencils<'a, T> {
+    type Item = Stencil<'a, T>;
+    #[inline]
+    fn next(&mut self) -> Option<Stencil<'a, T>> {
+        let (i, j) = self.next;
+        if i >= self.end { return None }
+        let cols = self.col_range();
+        self.next = if j + 1 < cols.end { (i, j + 1) } else { (i + 1, cols.start) };
+        let (rows, n) = self.mat.shape();
+        let raw = &self.mat.raw;
+        // the offsets stay within the view, by the choice of the range
+        // of positions for `Interior`, and by construction otherwise.
+        let cells = std::array::from_fn(|di| {
+            let r = self.offset(i, di, rows);
+            std::array::from_fn(|dj| unsafe { &*raw.ptr(r, self.offset(j, dj, n)) })
+        });
+        Some(Stencil { position: (i, j), cells })
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (i, j) = self.next;
+        let n = if i >= self.end {
+            0
+        } else {
+            let cols = self.col_range();
+            (self.end - i) * cols.len() - (j - cols.start)
+        };
+        (n, Some(n))
+    }
+}
+impl<'a, T> ExactSizeIterator for Stencils<'a, T> {}
+impl<'a, T> FusedIterator for Stencils<'a, T> {}
+
+/// An iterator over blocks of a `MatStride`, returned by `tiles`.
+pub struct Tiles<'a, T: 'a> {
+    raw: Raw<T>,
+    layout: TileLayout,
+    front: usize,
+    back: usize,
+    _marker: marker::PhantomData<&'a T>,
+}
+impl<'a, T> Clone for Tiles<'a, T> {
+    fn clone(&self) -> Tiles<'a, T> {
+        Tiles { ..*self }
+    }
+}
+unsafe impl<'a, T: Sync> Sync for Tiles<'a, T> {}
+unsafe impl<'a, T: Sync> Send for Tiles<'a, T> {}
+
+impl<'a, T> Iterator for Tiles<'a, T> {
+    type Item = MatStride<'a, T>;
+    #[inline]
+    fn next(&mut self) -> Option<MatStride<'a, T>> {
+        if self.front == self.back { return None }
+        self.front += 1;
+        Some(MatStride::from_raw(self.layout.tile(&self.raw, self.front - 1)))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<MatStride<'a, T>> {
+        self.front += n.min(self.back - self.front);
+        self.next()
+    }
+}
+impl<'a, T> DoubleEndedIterator for Tiles<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<MatStride<'a, T>> {
+        if self.front == self.back { return None }
+        self.back -= 1;
+        Some(MatStride::from_raw(self.layout.tile(&self.raw, self.back)))
+    }
+}
+impl<'a, T> ExactSizeIterator for Tiles<'a, T> {}
+impl<'a, T> FusedIterator for Tiles<'a, T> {}
+
+/// An iterator over disjoint blocks of a `MutMatStride`, returned by
+/// `tiles_mut`.
+pub struct MutTiles<'a, T: 'a> {
+    raw: Raw<T>,
+    layout: TileLayout,
+    front: usize,
+    back: usize,
+    _marker: marker::PhantomData<&'a mut T>,
+}
+unsafe impl<'a, T: Sync> Sync for MutTiles<'a, T> {}
+unsafe impl<'a, T: Send> Send for MutTiles<'a, T> {}
+
+// distinct tiles cover distinct elements, and each is handed out only
+// once, so they can have the full lifetime.
+impl<'a, T> Iterator for MutTiles<'a, T> {
+    type Item = MutMatStride<'a, T>;
+    #[inline]
+    fn next(&mut self) -> Option<MutMatStride<'a, T>> {
+        if self.front == self.back { return None }
+        self.front += 1;
+        Some(MutMatStride::from_raw(self.layout.tile(&self.raw, self.front - 1)))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<MutMatStride<'a, T>> {
+        self.front += n.min(self.back - self.front);
+        self.next()
+    }
+}
+impl<'a, T> DoubleEndedIterator for MutTiles<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<MutMatStride<'a, T>> {
+        if self.front == self.back { return None }
+        self.back -= 1;
+        Some(MutMatStride::from_raw(self.layout.tile(&self.raw, self.back)))
+    }
+}
+impl<'a, T> ExactSizeIterator for MutTiles<'a, T> {}
+impl<'a, T> FusedIterator for MutTiles<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use {MatStride, MutMatStride, StrideError, Border};
+    use super::transpose_copy;
+
+    #[test]
+    fn views() {
+        let v: Vec<i32> = (0..12).collect();
+        let m = MatStride::new(&v, 3, 4);
+        assert_eq!(m.shape(), (3, 4));
+        assert_eq!(m.row(1), [4, 5, 6, 7]);
+        assert_eq!(m.col(3), [3, 7, 11]);
+        assert_eq!(m.get(2, 4), None);
+        assert_eq!(m.rows().rev().map(|r| r[0]).collect::<Vec<_>>(), [8, 4, 0]);
+        let sub = m.submatrix(1..3, 1..4).transpose();
+        assert_eq!(sub.shape(), (3, 2));
+        assert_eq!(format!("{:?}", sub), "[[5, 9], [6, 10], [7, 11]]");
+        assert_eq!(m.submatrix(3..3, 0..4).rows().len(), 0);
+        assert!(m.submatrix(1..1, 4..4).is_empty());
+
+        // column-major, and overlapping rows.
+        let cm = MatStride::from_parts(&v, 1, 2, 3, 1, 2).unwrap();
+        assert_eq!(cm.cols().collect::<Vec<_>>(), [[1, 2], [3, 4], [5, 6]]);
+        let hankel = MatStride::from_parts(&v, 0, 3, 3, 1, 1).unwrap();
+        assert_eq!(hankel.row(2), hankel.col(2));
+    }
+
+    #[test]
+    fn layouts() {
+        let v = &mut [0u8; 12];
+        assert_eq!(MatStride::from_parts(v, 0, 3, 4, 4, 1).unwrap().row_stride(), 4);
+        assert_eq!(MatStride::from_parts(v, 1, 3, 4, 4, 1), Err(StrideError::OutOfBounds));
+        assert_eq!(MatStride::from_parts(v, 0, 3, 4, 0, 1), Err(StrideError::ZeroStride));
+        assert_eq!(MatStride::from_parts(v, 0, usize::MAX, 2, 2, 1), Err(StrideError::OutOfBounds));
+        assert_eq!(MatStride::from_parts(v, 11, 1, 1, 0, 0).unwrap()[(0, 0)], 0);
+        assert!(MatStride::from_parts(v, 12, 0, 5, 7, 7).unwrap().is_empty());
+        assert_eq!(MatStride::new(&[0u8; 0], 2, 0).rows().len(), 2);
+        assert_eq!(MutMatStride::from_parts(v, 0, 3, 3, 2, 1).unwrap_err(), StrideError::Overlapping);
+        assert_eq!(MutMatStride::from_parts(v, 0, 3, 3, 1, 1).unwrap_err(), StrideError::Overlapping);
+        assert!(MutMatStride::from_parts(v, 0, 3, 2, 1, 3).is_ok());
+        assert!(MutMatStride::from_parts(v, 0, 2, 3, 6, 2).is_ok());
+
+        let p = v.as_mut_ptr();
+        unsafe {
+            let m = MatStride::from_raw_parts(p, 3, 2, 4, 1).unwrap();
+            assert_eq!((m.shape(), m.as_ptr()), ((3, 2), p as *const u8));
+            assert_eq!(MatStride::from_raw_parts(p, 2, 2, usize::MAX / 2, 1), Err(StrideError::TooLarge));
+            assert_eq!(MatStride::from_raw_parts(p, 2, 2, 0, 1), Err(StrideError::ZeroStride));
+            assert!(MatStride::from_raw_parts(p, 0, 3, usize::MAX, 0).unwrap().is_empty());
+            assert_eq!(MutMatStride::from_raw_parts(p, 3, 3, 2, 1).unwrap_err(), StrideError::Overlapping);
+            assert_eq!(MutMatStride::from_raw_parts(p, 3, 2, 1, 3).unwrap().as_mut_ptr(), p);
+        }
+    }
+
+    #[test]
+    fn mutation() {
+        let mut v = [0; 12];
+        {
+            let mut m = MutMatStride::from_parts(&mut v, 0, 3, 3, 4, 1).unwrap();
+            for (i, row) in m.reborrow().rows_mut().enumerate() {
+                for x in row { *x = i }
+            }
+            for (j, col) in m.reborrow().submatrix_mut(1..3, 0..3).cols_mut().enumerate() {
+                for x in col { *x += 10 * j }
+            }
+            let (top, bottom) = m.reborrow().split_at_row_mut(1);
+            let (mut left, _) = bottom.split_at_col_mut(1);
+            left[(1, 0)] = 99;
+            *top.row_mut(0).get_mut(2).unwrap() = 7;
+            assert_eq!(m.as_mat().col(0), [0, 1, 99]);
+            *m.get_mut(0, 0).unwrap() = 5;
+        }
+        assert_eq!(v, [5, 0, 7, 0, 1, 11, 21, 0, 99, 12, 22, 0]);
+    }
+
+    #[test]
+    fn transpose() {
+        let v: Vec<u32> = (0..70 * 45).collect();
+        let src = MatStride::from_parts(&v, 3, 40, 33, 70, 1).unwrap();
+        let mut t = vec![0; 2 * 33 * 40];
+        {
+            // every other element, row after row.
+            let dst = MutMatStride::from_parts(&mut t, 0, 33, 40, 80, 2).unwrap();
+            transpose_copy(&src, dst);
+        }
+        let dst = MatStride::from_parts(&t, 0, 33, 40, 80, 2).unwrap();
+        assert_eq!(dst, src.transpose());
+        assert!(t.iter().skip(1).step_by(2).all(|&x| x == 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::transpose_copy: 2x3 source for a 2x3 destination")]
+    fn transpose_wrong_shape() {
+        transpose_copy(&MatStride::new(&[0; 6], 2, 3), MutMatStride::new(&mut [0; 6], 2, 3));
+    }
+
+    #[test]
+    fn tiles() {
+        let v: Vec<u16> = (0..7 * 10).collect();
+        let m = MatStride::from_parts(&v, 0, 7, 9, 10, 1).unwrap();
+        let tiles: Vec<_> = m.tiles(3, 4).collect();
+        assert_eq!(tiles.len(), 9);
+        assert_eq!(m.tiles(3, 4).next_back().unwrap(), m.submatrix(6..7, 8..9));
+        assert_eq!(tiles[4], m.submatrix(3..6, 4..8));
+        assert_eq!(m.tiles(3, 4).nth(20), None);
+        assert_eq!(m.submatrix(0..0, 0..9).tiles(1, 1).len(), 0);
+
+        let mut w = vec![0; 7 * 10];
+        {
+            let dst = MutMatStride::from_parts(&mut w, 0, 7, 9, 10, 1).unwrap();
+            let mut tiles: Vec<_> = dst.tiles_mut(2, 5).collect();
+            assert_eq!(tiles.len(), 8);
+            // every element is covered exactly once.
+            for (k, t) in tiles.iter_mut().enumerate() {
+                for row in t.reborrow().rows_mut() {
+                    for x in row { *x += k as u16 + 1 }
+                }
+            }
+        }
+        assert_eq!(w[..10], [1, 1, 1, 1, 1, 2, 2, 2, 2, 0]);
+        assert_eq!(w[60..], [7, 7, 7, 7, 7, 8, 8, 8, 8, 0]);
+    }
+
+    #[test]
+    fn bands() {
+        let v: Vec<i32> = (0..20).collect();
+        let tall = MatStride::new(&v, 5, 4);
+        for (k_lower, k_upper) in [(0, 0), (1, 2), (3, 0), (10, 10), (0, usize::MAX), (usize::MAX, 0)] {
+            let band: Vec<_> = tall.band(k_lower, k_upper).collect();
+            let rev: Vec<_> = tall.band(k_lower, k_upper).rev().collect();
+            assert_eq!(band.len(), 5);
+            for (i, &(start, part)) in band.iter().enumerate() {
+                assert_eq!(rev[4 - i], (start, part));
+                let cols: Vec<usize> = (0..4usize)
+                    .filter(|&j| j.saturating_add(k_lower) >= i && j <= i.saturating_add(k_upper))
+                    .collect();
+                assert_eq!(part.len(), cols.len());
+                if let Some(&j) = cols.first() {
+                    assert_eq!(start, j);
+                    assert_eq!(part, tall.row(i).slice(j, j + cols.len()));
+                }
+            }
+        }
+        let wide = tall.transpose();
+        assert_eq!(wide.upper_triangle_rows().map(|(j, r)| (j, r.len())).collect::<Vec<_>>(),
+                   [(0, 5), (1, 4), (2, 3), (3, 2)]);
+        assert_eq!(tall.upper_triangle_rows().next_back().unwrap(), (4, ::Stride::empty()));
+        assert_eq!(tall.lower_triangle_rows().nth(4).unwrap().1, tall.row(4));
+    }
+
+    #[test]
+    fn fill_and_copy() {
+        let mut v = vec![0u16; 40];
+        {
+            // a pitched plane, its transpose, and a column-major view.
+            let mut m = MutMatStride::from_parts(&mut v, 0, 3, 4, 5, 1).unwrap();
+            m.fill(1);
+            m.reborrow().transpose_mut().submatrix_mut(1..3, 0..2).fill(2);
+            let mut cm = MutMatStride::from_parts(&mut v[20..], 0, 4, 3, 1, 4).unwrap();
+            cm.fill(3);
+            cm.copy_from(&MatStride::new(&[4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15], 4, 3));
+        }
+        assert_eq!(v[..15], [1, 2, 2, 1, 0, 1, 2, 2, 1, 0, 1, 1, 1, 1, 0]);
+        assert_eq!(v[20..32], [4, 7, 10, 13, 5, 8, 11, 14, 6, 9, 12, 15]);
+
+        let mut w = [0u16; 12];
+        MutMatStride::new(&mut w, 4, 3).copy_from(&MatStride::new(&v[20..32], 4, 3));
+        assert_eq!(w, v[20..32]);
+        MutMatStride::new(&mut w, 3, 4).transpose_mut().copy_from(&MatStride::new(&v[20..32], 4, 3));
+        assert_eq!(w, [4, 13, 11, 9, 7, 5, 14, 12, 10, 8, 6, 15]);
+        let mut m = MutMatStride::from_parts(&mut w, 1, 2, 2, 4, 2).unwrap();
+        m.copy_from_slice_row_major(&[1, 2, 3, 4]);
+        assert_eq!(w[1..8], [1, 11, 2, 7, 3, 14, 4]);
+        MutMatStride::from_parts(&mut w, 0, 3, 0, 4, 1).unwrap().copy_from_slice_row_major(&[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "MutMatStride.copy_from: mismatched shapes 2x3 and 3x2")]
+    fn copy_wrong_shape() {
+        MutMatStride::new(&mut [0; 6], 2, 3).copy_from(&MatStride::new(&[0; 6], 3, 2));
+    }
+
+    #[test]
+    fn swaps() {
+        let mut v: Vec<u8> = (0..24).collect();
+        {
+            let mut m = MutMatStride::from_parts(&mut v, 0, 3, 3, 1, 8).unwrap();
+            m.swap_rows(0, 2);
+            m.swap_rows(1, 1);
+            m.swap_cols(2, 0);
+            assert_eq!(m.as_mat().row(0), [18, 10, 2]);
+        }
+        assert_eq!(v[..3], [18, 17, 16]);
+        assert_eq!(v[8..11], [10, 9, 8]);
+        assert_eq!(v[16..19], [2, 1, 0]);
+        // the gaps between the columns are untouched.
+        assert!(v.iter().enumerate().all(|(k, &x)| k % 8 < 3 || x == k as u8));
+    }
+
+    #[test]
+    #[should_panic(expected = "MutMatStride.swap_cols: index 2 out of bounds for length 2 (3x2 matrix)")]
+    fn swap_out_of_bounds() {
+        MutMatStride::new(&mut [0; 6], 3, 2).swap_cols(2, 0);
+    }
+
+    #[test]
+    fn stencils() {
+        let v: Vec<i32> = (0..30).collect();
+        // a 4x5 view with a pitch of 7.
+        let m = MatStride::from_parts(&v, 1, 4, 5, 7, 1).unwrap();
+        let interior: Vec<_> = m.stencils(Border::Interior).collect();
+        assert_eq!(interior.len(), 6);
+        assert_eq!(m.stencils(Border::Interior).size_hint(), (6, Some(6)));
+        let s = interior[4];
+        assert_eq!(s.position(), (2, 2));
+        assert_eq!(s.cells(), [[&9, &10, &11], [&16, &17, &18], [&23, &24, &25]]);
+
+        let clamp: Vec<_> = m.stencils(Border::Clamp).collect();
+        assert_eq!(clamp.len(), 20);
+        assert_eq!((*clamp[0].north(), *clamp[0].west(), *clamp[0].south()), (1, 1, 8));
+        assert_eq!((*clamp[19].south(), *clamp[19].east(), *clamp[19].north()), (26, 26, 19));
+
+        let mut wrap = m.stencils(Border::Wrap);
+        assert_eq!(wrap.len(), 20);
+        let first = wrap.next().unwrap();
+        assert_eq!(wrap.len(), 19);
+        assert_eq!(first.cells(), [[&26, &22, &23], [&5, &1, &2], [&12, &8, &9]]);
+
+        for (a, b) in m.stencils(Border::Wrap).zip(m.stencils(Border::Clamp)) {
+            assert_eq!(a.position(), b.position());
+            assert_eq!(a.center(), b.center());
+        }
+        let thin = MatStride::new(&v[..6], 2, 3);
+        assert_eq!(thin.stencils(Border::Interior).count(), 0);
+        assert_eq!(thin.submatrix(0..2, 0..0).stencils(Border::Clamp).count(), 0);
+        assert_eq!(thin.submatrix(0..2, 0..0).stencils(Border::Interior).len(), 0);
+        let one = MatStride::new(&v[..1], 1, 1).stencils(Border::Wrap).next().unwrap();
+        assert!(one.cells().iter().flatten().all(|&&x| x == 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "MutMatStride.tiles_mut: tile sizes must be non-zero")]
+    fn empty_tiles() {
+        MutMatStride::new(&mut [0; 4], 2, 2).tiles_mut(0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "MatStride.row: index 3 out of bounds for length 3 (3x2 matrix)")]
+    fn row_out_of_bounds() {
+        MatStride::new(&[0; 6], 3, 2).row(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "MutMatStride.split_at_col_mut: index 3 out of bounds for length 2 (3x2 matrix)")]
+    fn split_out_of_bounds() {
+        MutMatStride::new(&mut [0; 6], 3, 2).split_at_col_mut(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::MatStride::new: 5 elements for a 2x3 matrix")]
+    fn new_wrong_len() {
+        MatStride::new(&[0; 5], 2, 3);
+    }
+}
@@ -1,9 +1,12 @@
-use std::fmt::{self, Debug};
+use std::cell::Cell;
+use std::fmt::{self, Debug, Display};
+use std::iter::FusedIterator;
 use std::marker;
 use std::mem;
-use std::ops::{Index, IndexMut, Deref};
+use std::ops::{AddAssign, Index, IndexMut, Deref, Range};
 use base;
 use base::Stride as Base;
+use StrideError;
 
 /// A mutable strided slice. This is equivalent to `&mut [T]`, that
 /// only refers to every `n`th `T`.
@@ -19,27 +22,52 @@ use base::Stride as Base;
 /// by-value slice with a shorter lifetime. This can then be passed
 /// directly into the functions that consume `self` without losing
 /// control of the original slice.
-#[repr(C)]
-#[derive(PartialEq, Eq, PartialOrd, Ord)] // FIXME: marker types
+///
+/// Like `&'a mut [T]`, this is covariant in `'a` but invariant in
+/// `T`, since it allows writing `T`s. For example, a
+/// `MutStride<&'static str>` cannot be used as a `MutStride<&'a str>`,
+/// or else a short-lived string could be written into it:
+///
+/// ```compile_fail
+/// # use strided::MutStride;
+/// fn shorten<'a, 'b>(s: MutStride<'b, &'static str>) -> MutStride<'b, &'a str> {
+///     s
+/// }
+/// ```
+#[repr(transparent)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)] // FIXME: marker types
 pub struct Stride<'a,T: 'a> {
-    base: Base<'a, T>,
+    pub(crate) base: Base<'a, T>,
     _marker: marker::PhantomData<&'a mut T>,
 }
 
+// The same bounds as `&'a mut [T]`: sending a `MutStride` hands over
+// unique access to the elements, while sharing one only allows `&T`.
 unsafe impl<'a, T: Sync> Sync for Stride<'a, T> {}
 unsafe impl<'a, T: Send> Send for Stride<'a, T> {}
 
+impl<'a, T> Default for Stride<'a, T> {
+    fn default() -> Stride<'a, T> { Stride::empty() }
+}
+
 impl<'a, T: Debug> Debug for Stride<'a, T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.base.fmt(f)
+    }
+}
+
+/// Formats like `Stride`: `{:#}` omits the brackets.
+impl<'a, T: Display> Display for Stride<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.base.fmt(f)
     }
 }
 
 impl<'a, T> Stride<'a, T> {
     #[inline(always)]
-    fn new_raw(base: Base<'a, T>) -> Stride<'a, T> {
+    pub(crate) fn new_raw(base: Base<'a, T>) -> Stride<'a, T> {
         Stride {
-            base: base,
+            base,
             _marker: marker::PhantomData
         }
     }
@@ -51,11 +79,107 @@ impl<'a, T> Stride<'a, T> {
         Stride::new_raw(Base::new(x.as_mut_ptr(), x.len(), 1))
     }
 
+    /// Creates a strided slice of the `len` elements of `data` at
+    /// indices `offset`, `offset + stride`, ....
+    ///
+    /// Returns `Err(ZeroStride)` if `stride` is zero, and
+    /// `Err(OutOfBounds)` if the layout does not fit within `data`.
+    pub fn from_parts(data: &'a mut [T], offset: usize, stride: usize, len: usize)
+                      -> Result<Stride<'a, T>, StrideError> {
+        Base::with_layout(data.as_mut_ptr(), data.len(), offset, stride, len).map(Stride::new_raw)
+    }
+
+    /// Creates a strided slice of `len` elements, `stride` elements
+    /// apart, starting at `data`.
+    ///
+    /// Returns `Err(ZeroStride)` if `stride` is zero, and
+    /// `Err(TooLarge)` if the elements would span more than
+    /// `isize::MAX` bytes.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be non-null and aligned, and all `len` elements
+    /// must lie within a single allocation, be initialised, and not be
+    /// accessed through any other pointer for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(data: *mut T, len: usize, stride: usize)
+                                 -> Result<Stride<'a, T>, StrideError> {
+        Base::from_raw_parts(data, len, stride).map(Stride::new_raw)
+    }
+
+    /// Creates a strided slice of `len` elements, `byte_stride` bytes
+    /// apart, starting at `data`, such as a one-dimensional NumPy
+    /// array or a buffer exported through Python's buffer protocol.
+    ///
+    /// Returns `Err(NegativeStride)` or `Err(ZeroStride)` for such
+    /// strides when `len > 1`, and `Err(Misaligned)` if `data` is
+    /// not aligned for `T` or `byte_stride` is not a multiple of its
+    /// size. An empty slice ignores `data` entirely.
+    ///
+    /// # Safety
+    ///
+    /// All `len` elements must lie within a single allocation, be
+    /// initialised, and not be accessed through any other pointer for
+    /// the lifetime `'a`.
+    pub unsafe fn from_byte_stride(data: *mut T, len: usize, byte_stride: isize)
+                                   -> Result<Stride<'a, T>, StrideError> {
+        Base::from_byte_stride(data, len, byte_stride).map(Stride::new_raw)
+    }
+
+    /// Creates a mutable strided slice of the field at `byte_offset`
+    /// within each element of `slice`. The `project_field!` macro
+    /// does this safely, given the name of the field.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the field does not fit within an `S`, if `S` is
+    /// zero-sized, or if the size of `S` is not a multiple of that of
+    /// `T`.
+    ///
+    /// # Safety
+    ///
+    /// `byte_offset` must be the offset of a field of type `T` within
+    /// `S`, such as one given by `std::mem::offset_of!`.
+    #[track_caller]
+    pub unsafe fn project_field_mut<S>(slice: &'a mut [S], byte_offset: usize) -> Stride<'a, T> {
+        Stride::new_raw(Base::project_field(slice.as_mut_ptr(), slice.len(), byte_offset))
+    }
+
+    #[doc(hidden)]
+    #[track_caller]
+    pub unsafe fn __project_field_mut<S>(slice: &'a mut [S], byte_offset: usize,
+                                         _: fn(&mut S) -> &mut T) -> Stride<'a, T> {
+        Stride::project_field_mut(slice, byte_offset)
+    }
+
+    // used by `#[derive(StridedFields)]` to project several fields of
+    // one slice at once.
+    #[doc(hidden)]
+    #[track_caller]
+    pub unsafe fn __project_field_raw<S>(data: *mut S, len: usize, byte_offset: usize,
+                                         _: fn(&mut S) -> &mut T) -> Stride<'a, T> {
+        Stride::new_raw(Base::project_field(data, len, byte_offset))
+    }
+
+    /// Creates a new empty strided slice (with stride 1), not
+    /// pointing to any data.
+    #[inline(always)]
+    pub const fn empty() -> Stride<'a, T> {
+        Stride {
+            base: Base::empty(),
+            _marker: marker::PhantomData
+        }
+    }
+
     /// Returns the number of elements accessible in `self`.
     #[inline(always)]
     pub fn len(&self) -> usize {
         self.base.len()
     }
+    /// Returns `true` if `self` has no elements.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.base.is_empty()
+    }
     /// Returns the offset between successive elements of `self` as a
     /// count of *elements*, not bytes.
     #[inline(always)]
@@ -73,6 +197,24 @@ impl<'a, T> Stride<'a, T> {
     pub fn as_mut_ptr(&mut self) -> *mut T {
         self.base.as_mut_ptr()
     }
+    /// Returns the range of memory spanned by this strided slice, like
+    /// `as_ptr_range`, as mutable pointers.
+    ///
+    /// The same caveat as for `as_mut_ptr` applies: the memory
+    /// between the elements may belong to other slices.
+    #[inline]
+    pub fn as_mut_ptr_range(&mut self) -> Range<*mut T> {
+        self.base.ptr_range()
+    }
+
+    /// Returns a conventional mutable slice of the elements of `self`
+    /// if they are adjacent in memory, that is, if `self.stride() ==
+    /// 1` (or if `self` has at most one element), and `None`
+    /// otherwise.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> Option<&mut [T]> {
+        self.base.as_mut_slice().map(|s| &mut *s)
+    }
 
     /// Creates a temporary copy of this strided slice.
     ///
@@ -85,6 +227,76 @@ impl<'a, T> Stride<'a, T> {
         Stride::new_raw(self.base)
     }
 
+    /// Converts this strided slice into a shared one, consuming
+    /// `self`.
+    ///
+    /// Unlike viewing `self` through `Deref`, the returned slice
+    /// keeps the full lifetime `'a`, and so can be stored or returned
+    /// once mutation is finished.
+    #[inline(always)]
+    pub fn into_stride(self) -> ::imm::Stride<'a, T> {
+        ::imm::Stride::new_raw(self.base)
+    }
+
+    /// Converts this strided slice into a shared strided slice of
+    /// `Cell`s, like `Cell::as_slice_of_cells`, consuming `self`.
+    ///
+    /// The elements can then be read and written through any number
+    /// of copies of the returned slice (and its subslices), within one
+    /// thread.
+    ///
+    /// ```rust
+    /// use strided::MutStride;
+    ///
+    /// let mut v = [1, 2, 3, 4];
+    /// let cells = MutStride::new(&mut v).as_cell_stride();
+    /// let (evens, odds) = cells.substrides2();
+    /// for (e, o) in evens.iter().zip(odds.iter()) { e.set(e.get() + o.get()) }
+    /// cells[3].set(0);
+    /// assert_eq!(v, [3, 2, 7, 0]);
+    /// ```
+    #[inline]
+    pub fn as_cell_stride(self) -> ::imm::Stride<'a, Cell<T>> {
+        // `Cell<T>` has the same layout as `T`, and the exclusive
+        // borrow is handed over to the cells.
+        ::imm::Stride::new_raw(unsafe { self.base.cast() })
+    }
+
+    /// Fuses two strided slices back into one, if they tile a common
+    /// parent.
+    ///
+    /// This is the inverse of both `split_at_mut` and
+    /// `substrides2_mut`; see `Stride::try_merge` for the precise
+    /// conditions. If the slices cannot be merged, they are returned
+    /// unchanged in the `Err` variant.
+    ///
+    /// # Safety
+    ///
+    /// `a` and `b` must have been split from the same mutable strided
+    /// slice (or slice), such as by `split_at_mut` or
+    /// `substrides2_mut`. Adjacent or interleaved addresses do not
+    /// show this: two separate borrows, even of two parts of the
+    /// same buffer, could be fused into one that aliases some other
+    /// reference.
+    ///
+    /// ```rust
+    /// use strided::MutStride;
+    ///
+    /// let mut v = [1u8, 2, 3, 4, 5];
+    /// let (evens, odds) = MutStride::new(&mut v).substrides2_mut();
+    /// // both halves come from the same `MutStride`.
+    /// let mut all = unsafe { MutStride::try_merge(evens, odds) }.ok().unwrap();
+    /// all[1] = 20;
+    /// assert_eq!(v, [1, 20, 3, 4, 5]);
+    /// ```
+    pub unsafe fn try_merge(a: Stride<'a, T>, b: Stride<'a, T>)
+                     -> Result<Stride<'a, T>, (Stride<'a, T>, Stride<'a, T>)> {
+        match Base::try_merge(a.base, b.base) {
+            Some(base) => Ok(Stride::new_raw(base)),
+            None => Err((a, b)),
+        }
+    }
+
     /// Breaks this strided slice into two strided slices pointing to
     /// alternate elements.
     ///
@@ -94,11 +306,39 @@ impl<'a, T> Stride<'a, T> {
     /// succeed even for mismatched lengths, and even if `self` has
     /// only zero or one elements.
     #[inline]
+    #[track_caller]
     pub fn substrides2_mut(self) -> (Stride<'a, T>, Stride<'a, T>) {
         let (l, r) = self.base.substrides2();
         (Stride::new_raw(l), Stride::new_raw(r))
     }
 
+    /// Breaks this strided slice into three strided slices, each
+    /// pointing to every third element, starting at offsets 0, 1 and
+    /// 2 respectively.
+    ///
+    /// This is a tuple-returning shorthand for `substrides_mut(3)`,
+    /// convenient for destructuring, e.g. the channels of RGB data.
+    #[inline]
+    #[track_caller]
+    pub fn substrides3_mut(self) -> (Stride<'a, T>, Stride<'a, T>, Stride<'a, T>) {
+        let (a, b, c) = self.base.substrides3();
+        (Stride::new_raw(a), Stride::new_raw(b), Stride::new_raw(c))
+    }
+
+    /// Breaks this strided slice into four strided slices, each
+    /// pointing to every fourth element, starting at offsets 0, 1, 2
+    /// and 3 respectively.
+    ///
+    /// This is a tuple-returning shorthand for `substrides_mut(4)`,
+    /// convenient for destructuring, e.g. the channels of RGBA data.
+    #[inline]
+    #[track_caller]
+    pub fn substrides4_mut(self)
+                        -> (Stride<'a, T>, Stride<'a, T>, Stride<'a, T>, Stride<'a, T>) {
+        let (a, b, c, d) = self.base.substrides4();
+        (Stride::new_raw(a), Stride::new_raw(b), Stride::new_raw(c), Stride::new_raw(d))
+    }
+
     /// Returns an iterator over `n` strided subslices of `self` each
     /// pointing to every `n`th element, starting at successive
     /// offsets.
@@ -109,15 +349,44 @@ impl<'a, T> Stride<'a, T> {
     /// (return `n` strided slices) even if `self` has fewer than `n`
     /// elements.
     #[inline]
+    #[track_caller]
     pub fn substrides_mut(self, n: usize) -> Substrides<'a, T> {
         Substrides {
             base: self.base.substrides(n),
         }
     }
+    /// Returns an iterator over `n` strided subslices of `self`, like
+    /// `substrides_mut`, or `Err(ZeroStride)` if `n` is zero and
+    /// `Err(TooLarge)` if the new stride overflows.
+    #[inline]
+    pub fn try_substrides_mut(self, n: usize) -> Result<Substrides<'a, T>, StrideError> {
+        self.base.try_substrides(n).map(|base| Substrides { base })
+    }
+    /// Returns the `i`th of the `n` strided subslices that
+    /// `substrides_mut(n)` would yield, that is, the one pointing to
+    /// every `n`th element starting at offset `i`.
+    ///
+    /// This computes the subslice directly, without creating the
+    /// others.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= n`.
+    #[inline]
+    #[track_caller]
+    pub fn substride_mut(self, n: usize, i: usize) -> Stride<'a, T> {
+        Stride::new_raw(self.base.substride(n, i))
+    }
+    /// Returns the `i`th of the `n` strided subslices, like
+    /// `substride_mut`, or an error instead of panicking if `i >= n`.
+    #[inline]
+    pub fn try_substride_mut(self, n: usize, i: usize) -> Result<Stride<'a, T>, StrideError> {
+        self.base.try_substride(n, i).map(Stride::new_raw)
+    }
     /// Returns a reference to the `n`th element of `self`, or `None`
     /// if `n` is out-of-bounds.
     #[inline]
-    pub fn get_mut<'b>(&'b mut self, n: usize) -> Option<&'b mut T> {
+    pub fn get_mut(&mut self, n: usize) -> Option<&mut T> {
         self.base.get_mut(n).map(|r| &mut *r)
     }
 
@@ -131,14 +400,173 @@ impl<'a, T> Stride<'a, T> {
         self.reborrow().into_iter()
     }
 
-    /// Returns an iterator over reference to each successive element
-    /// of `self`, with the maximum possible lifetime.
+    /// Assigns the values of `iter` to successive elements of `self`,
+    /// from the start, returning the number assigned. If `iter` runs
+    /// out first, the remaining elements are left untouched.
     ///
-    /// See also `iter_mut` which avoids consuming `self` at the
-    /// expense of shorter lifetimes.
+    /// Returns `Err(IndexOutOfBounds)` (with `index` and `len` both
+    /// `self.len()`) if `iter` has more values than `self` has
+    /// elements, after assigning all of them. Only one extra value is
+    /// taken from `iter`.
+    ///
+    /// ```rust
+    /// use strided::MutStride;
+    ///
+    /// let mut v = [0; 6];
+    /// let (mut even, mut odd) = MutStride::new(&mut v).substrides2_mut();
+    /// assert_eq!(even.assign_from_iter(1..3), Ok(2));
+    /// assert!(odd.assign_from_iter(1..).is_err());
+    /// assert_eq!(v, [1, 1, 2, 2, 0, 3]);
+    /// ```
+    pub fn assign_from_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<usize, StrideError> {
+        let len = self.len();
+        let mut iter = iter.into_iter();
+        let n = self.iter_mut().zip(&mut iter).map(|(x, v)| *x = v).count();
+        if n == len && iter.next().is_some() {
+            Err(StrideError::IndexOutOfBounds { index: len, len })
+        } else {
+            Ok(n)
+        }
+    }
+
+    /// Calls `f` on each element of `self`, in order.
+    ///
+    /// This is equivalent to `for x in self.iter_mut() { f(x) }`, but
+    /// the loop is counted by index (and runs over a plain slice when
+    /// `self` is contiguous), which is easier for the optimiser to
+    /// vectorise.
     #[inline]
-    pub fn into_iter(mut self) -> ::MutItems<'a, T> {
-        self.base.iter_mut()
+    pub fn map_in_place<F: FnMut(&mut T)>(&mut self, mut f: F) {
+        if let Some(s) = self.as_mut_slice() {
+            return s.iter_mut().for_each(f)
+        }
+        for i in 0..self.len() {
+            unsafe { f(self.base.get_unchecked_mut(i)) }
+        }
+    }
+
+    /// Replaces each element of `self` after the first with a running
+    /// total, by calling `f(prev, x)` on each element `x` in order
+    /// with `prev` the (already updated) element before it.
+    ///
+    /// ```rust
+    /// use strided::MutStride;
+    ///
+    /// let mut v = [3, 0, 1, 0, 4, 0, 1, 0];
+    /// let (mut even, _) = MutStride::new(&mut v).substrides2_mut();
+    /// even.scan_in_place(|&prev, x| *x = (*x).max(prev));
+    /// assert_eq!(v, [3, 0, 3, 0, 4, 0, 4, 0]);
+    /// ```
+    pub fn scan_in_place<F: FnMut(&T, &mut T)>(&mut self, mut f: F) {
+        let mut iter = self.iter_mut();
+        if let Some(mut prev) = iter.next() {
+            for x in iter {
+                f(prev, x);
+                prev = x;
+            }
+        }
+    }
+
+    /// Replaces each element of `self` with the sum of it and all the
+    /// elements before it, a prefix sum.
+    ///
+    /// ```rust
+    /// use strided::MutStride;
+    ///
+    /// let mut v = [1, 10, 2, 20, 3, 30];
+    /// let (_, mut odd) = MutStride::new(&mut v).substrides2_mut();
+    /// odd.cumsum_in_place();
+    /// assert_eq!(v, [1, 10, 2, 30, 3, 60]);
+    /// ```
+    pub fn cumsum_in_place(&mut self) where T: Copy + AddAssign {
+        self.scan_in_place(|&prev, x| *x += prev)
+    }
+
+    /// Calls `f` on each element of `self` and the corresponding
+    /// element of `other`, in order, like `map_in_place`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `self` and `other` have different lengths.
+    ///
+    /// ```rust
+    /// use strided::{Stride, MutStride};
+    ///
+    /// let mut v = [1, 2, 3, 4];
+    /// let (_, mut odd) = MutStride::new(&mut v).substrides2_mut();
+    /// odd.zip_apply(Stride::new(&[10, 20]), |x, y| *x += y);
+    /// assert_eq!(v, [1, 12, 3, 24]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn zip_apply<U, F: FnMut(&mut T, &U)>(&mut self, other: ::imm::Stride<'_, U>, mut f: F) {
+        assert!(self.len() == other.len(), "Stride.zip_apply: mismatched lengths {} and {}",
+                self.len(), other.len());
+        if let (Some(a), Some(b)) = (self.as_mut_slice(), other.as_slice()) {
+            return a.iter_mut().zip(b).for_each(|(x, y)| f(x, y))
+        }
+        for i in 0..self.len() {
+            unsafe { f(self.base.get_unchecked_mut(i), other.base.get_unchecked(i)) }
+        }
+    }
+
+    /// Sets every element to `value`, as one `slice::fill` if the
+    /// elements are contiguous.
+    ///
+    /// ```rust
+    /// use strided::MutStride;
+    ///
+    /// let mut v = [0; 5];
+    /// MutStride::new(&mut v).substrides2_mut().1.fill(7);
+    /// assert_eq!(v, [0, 7, 0, 7, 0]);
+    /// ```
+    #[inline]
+    pub fn fill(&mut self, value: T) where T: Clone {
+        if let Some(s) = self.as_mut_slice() {
+            return s.fill(value)
+        }
+        for x in self.iter_mut() { x.clone_from(&value) }
+    }
+
+    /// Copies the elements of `src` into `self`, like
+    /// `slice::copy_from_slice`, which is used if both are
+    /// contiguous.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `self` and `src` have different lengths.
+    #[inline]
+    #[track_caller]
+    pub fn copy_from_stride(&mut self, src: ::imm::Stride<'_, T>) where T: Copy {
+        assert!(self.len() == src.len(), "Stride.copy_from_stride: mismatched lengths {} and {}",
+                self.len(), src.len());
+        self.zip_apply(src, |x, &y| *x = y)
+    }
+
+    /// Swaps each element of `self` with the corresponding element of
+    /// `other`, like `slice::swap_with_slice`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `self` and `other` have different lengths.
+    ///
+    /// ```rust
+    /// use strided::MutStride;
+    ///
+    /// let mut v = [1, 2, 3, 4, 5, 6];
+    /// let (mut even, mut odd) = MutStride::new(&mut v).substrides2_mut();
+    /// even.swap_with_stride(&mut odd);
+    /// assert_eq!(v, [2, 1, 4, 3, 6, 5]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn swap_with_stride(&mut self, other: &mut Stride<'_, T>) {
+        assert!(self.len() == other.len(), "Stride.swap_with_stride: mismatched lengths {} and {}",
+                self.len(), other.len());
+        if let (Some(a), Some(b)) = (self.as_mut_slice(), other.as_mut_slice()) {
+            return a.swap_with_slice(b)
+        }
+        for (x, y) in self.iter_mut().zip(other.iter_mut()) { mem::swap(x, y) }
     }
 
     /// Returns a strided slice containing only the elements from
@@ -148,16 +576,38 @@ impl<'a, T> Stride<'a, T> {
     ///
     /// Panics if `from > to` or if `to > self.len()`.
     #[inline]
+    #[track_caller]
     pub fn slice_mut(self, from: usize, to: usize) -> Stride<'a, T> {
         Stride::new_raw(self.base.slice(from, to))
     }
     /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive), or
+    /// `Err(InvalidRange)` if `from > to` and `Err(IndexOutOfBounds)`
+    /// if `to > self.len()`.
+    #[inline]
+    pub fn try_slice_mut(self, from: usize, to: usize) -> Result<Stride<'a, T>, StrideError> {
+        self.base.try_slice(from, to).map(Stride::new_raw)
+    }
+    /// Returns a strided slice containing only the elements from
+    /// index `from` (inclusive), or an error if `from > self.len()`.
+    #[inline]
+    pub fn try_slice_from_mut(self, from: usize) -> Result<Stride<'a, T>, StrideError> {
+        self.base.try_slice_from(from).map(Stride::new_raw)
+    }
+    /// Returns a strided slice containing only the elements to index
+    /// `to` (exclusive), or an error if `to > self.len()`.
+    #[inline]
+    pub fn try_slice_to_mut(self, to: usize) -> Result<Stride<'a, T>, StrideError> {
+        self.base.try_slice(0, to).map(Stride::new_raw)
+    }
+    /// Returns a strided slice containing only the elements from
     /// index `from` (inclusive).
     ///
     /// # Panic
     ///
     /// Panics if `from > self.len()`.
     #[inline]
+    #[track_caller]
     pub fn slice_from_mut(self, from: usize) -> Stride<'a, T> {
         Stride::new_raw(self.base.slice_from(from))
     }
@@ -168,9 +618,32 @@ impl<'a, T> Stride<'a, T> {
     ///
     /// Panics if `to > self.len()`.
     #[inline]
+    #[track_caller]
     pub fn slice_to_mut(self, to: usize) -> Stride<'a, T> {
         Stride::new_raw(self.base.slice_to(to))
     }
+    /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive), after clamping
+    /// both to `self.len()`.
+    ///
+    /// This never panics: out-of-bounds ends are truncated, and if
+    /// `from >= to` (after clamping) the result is empty.
+    #[inline]
+    pub fn slice_clamped_mut(self, from: usize, to: usize) -> Stride<'a, T> {
+        Stride::new_raw(self.base.slice_clamped(from, to))
+    }
+    /// Returns a strided slice containing only the elements from
+    /// indices `from` (inclusive) to `to` (exclusive), without
+    /// checking the bounds.
+    ///
+    /// # Safety
+    ///
+    /// `from <= to` and `to <= self.len()` must hold, otherwise the
+    /// result points outside of `self`.
+    #[inline]
+    pub unsafe fn slice_unchecked_mut(self, from: usize, to: usize) -> Stride<'a, T> {
+        Stride::new_raw(self.base.slice_unchecked(from, to))
+    }
     /// Returns two strided slices, the first with elements up to
     /// `idx` (exclusive) and the second with elements from `idx`.
     ///
@@ -181,28 +654,112 @@ impl<'a, T> Stride<'a, T> {
     ///
     /// Panics if `idx > self.len()`.
     #[inline]
+    #[track_caller]
     pub fn split_at_mut(self, idx: usize) -> (Stride<'a, T>, Stride<'a, T>) {
         let (l, r) = self.base.split_at(idx);
         (Stride::new_raw(l), Stride::new_raw(r))
     }
+    /// Returns two strided slices split at `idx`, like
+    /// `split_at_mut`, or `Err(IndexOutOfBounds)` if
+    /// `idx > self.len()`.
+    #[inline]
+    pub fn try_split_at_mut(self, idx: usize)
+                            -> Result<(Stride<'a, T>, Stride<'a, T>), StrideError> {
+        let (l, r) = self.base.try_split_at(idx)?;
+        Ok((Stride::new_raw(l), Stride::new_raw(r)))
+    }
+    /// Returns two strided slices, the first with elements up to
+    /// `idx` (exclusive) and the second with elements from `idx`,
+    /// without checking the bounds.
+    ///
+    /// # Safety
+    ///
+    /// `idx <= self.len()` must hold, otherwise the second slice
+    /// points outside of `self`.
+    #[inline]
+    pub unsafe fn split_at_unchecked_mut(self, idx: usize) -> (Stride<'a, T>, Stride<'a, T>) {
+        let (l, r) = self.base.split_at_unchecked(idx);
+        (Stride::new_raw(l), Stride::new_raw(r))
+    }
+
+    /// Removes the first element from `self` and returns a reference
+    /// to it, or returns `None` if `self` is empty.
+    ///
+    /// The returned reference has the full lifetime `'a`, since
+    /// `self` no longer covers that element.
+    #[inline]
+    pub fn take_first_mut(&mut self) -> Option<&'a mut T> {
+        self.base.take_prefix(1).and_then(|mut s| s.get_mut(0))
+    }
+    /// Removes the last element from `self` and returns a reference
+    /// to it, or returns `None` if `self` is empty.
+    ///
+    /// The returned reference has the full lifetime `'a`, since
+    /// `self` no longer covers that element.
+    #[inline]
+    pub fn take_last_mut(&mut self) -> Option<&'a mut T> {
+        self.base.take_suffix(1).and_then(|mut s| s.get_mut(0))
+    }
+    /// Removes the first `n` elements from `self` and returns them as
+    /// a strided slice, or returns `None` (leaving `self` untouched)
+    /// if `n > self.len()`.
+    #[inline]
+    pub fn take_prefix_mut(&mut self, n: usize) -> Option<Stride<'a, T>> {
+        self.base.take_prefix(n).map(Stride::new_raw)
+    }
+    /// Removes the last `n` elements from `self` and returns them as
+    /// a strided slice, or returns `None` (leaving `self` untouched)
+    /// if `n > self.len()`.
+    #[inline]
+    pub fn take_suffix_mut(&mut self, n: usize) -> Option<Stride<'a, T>> {
+        self.base.take_suffix(n).map(Stride::new_raw)
+    }
 }
 
 impl<'a, T> Index<usize> for Stride<'a, T> {
     type Output = T;
-    fn index<'b>(&'b self, n: usize) -> &'b T {
-&        (**self)[n]
+    #[track_caller]
+    fn index(&self, n: usize) -> &T {
+        &(**self)[n]
     }
 }
 impl<'a, T> IndexMut<usize> for Stride<'a, T> {
-    fn index_mut<'b>(&'b mut self, n: usize) -> &'b mut T {
-        self.get_mut(n).expect("Stride.index_mut: index out of bounds")
+    #[track_caller]
+    fn index_mut(&mut self, n: usize) -> &mut T {
+        let len = self.len();
+        match self.base.get_mut(n) {
+            Some(x) => x,
+            None => self.base.fail("index_mut", StrideError::IndexOutOfBounds { index: n, len }),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for Stride<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = ::MutItems<'a, T>;
+    /// Returns an iterator over reference to each successive element
+    /// of `self`, with the maximum possible lifetime.
+    ///
+    /// See also `iter_mut` which avoids consuming `self` at the
+    /// expense of shorter lifetimes.
+    #[inline]
+    fn into_iter(mut self) -> ::MutItems<'a, T> {
+        self.base.iter_mut()
+    }
+}
+impl<'a, 'b, T> IntoIterator for &'b mut Stride<'a, T> {
+    type Item = &'b mut T;
+    type IntoIter = ::MutItems<'b, T>;
+    #[inline]
+    fn into_iter(self) -> ::MutItems<'b, T> {
+        self.iter_mut()
     }
 }
 
 impl<'a, T> Deref for Stride<'a, T> {
     type Target = ::imm::Stride<'a, T>;
-    fn deref<'b>(&'b self) -> &'b ::imm::Stride<'a, T> {
-        unsafe { mem::transmute(self) }
+    fn deref(&self) -> &::imm::Stride<'a, T> {
+        ::imm::Stride::from_base_ref(&self.base)
     }
 }
 
@@ -213,25 +770,143 @@ pub struct Substrides<'a, T: 'a> {
     base: base::Substrides<'a, T>,
 }
 
+unsafe impl<'a, T: Sync> Sync for Substrides<'a, T> {}
+unsafe impl<'a, T: Send> Send for Substrides<'a, T> {}
+
+impl<'a, T> Debug for Substrides<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.base.fmt(f)
+    }
+}
+
 impl<'a, T> Iterator for Substrides<'a, T> {
     type Item = Stride<'a, T>;
     fn next(&mut self) -> Option<Stride<'a, T>> {
-        match self.base.next() {
-            Some(s) => Some(Stride::new_raw(s)),
-            None => None
-        }
+        self.base.next().map(Stride::new_raw)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         self.base.size_hint()
     }
+
+    fn nth(&mut self, n: usize) -> Option<Stride<'a, T>> {
+        self.base.nth(n).map(Stride::new_raw)
+    }
 }
+impl<'a, T> FusedIterator for Substrides<'a, T> {}
 
 #[cfg(test)]
 mod tests {
     use super::Stride;
-    make_tests!(substrides2_mut, substrides_mut,
-                slice_mut, slice_to_mut, slice_from_mut, split_at_mut, get_mut, iter_mut, mut);
+    make_tests!(substrides2_mut, substrides3_mut, substrides4_mut,
+                substrides_mut, substride_mut,
+                slice_mut, slice_to_mut, slice_from_mut, slice_clamped_mut,
+                slice_unchecked_mut, split_at_mut, split_at_unchecked_mut,
+                take_first_mut, take_last_mut, take_prefix_mut, take_suffix_mut,
+                get_mut, iter_mut, mut);
+
+    #[test]
+    fn as_cell_stride() {
+        let v = &mut [1u16, 2, 3, 4, 5, 6];
+        let cells = Stride::new(v).substrides2_mut().1.as_cell_stride();
+        assert_eq!(cells.stride(), 2);
+        let (a, b) = (cells.slice_to(2), cells.slice_from(1));
+        a[1].set(10);
+        b[0].set(b[0].get() + 1);
+        assert_eq!(b[1].get(), 6);
+        assert_eq!(*v, [1, 2, 3, 11, 5, 6]);
+        assert_eq!(Stride::<u8>::new(&mut []).as_cell_stride().len(), 0);
+    }
+
+    #[test]
+    fn assign_from_iter() {
+        use StrideError::IndexOutOfBounds;
+        let v = &mut [0u16; 7];
+        let (mut l, mut r) = Stride::new(v).substrides2_mut();
+        assert_eq!(l.assign_from_iter(vec![1, 2, 3, 4]), Ok(4));
+        assert_eq!(r.assign_from_iter(10..12), Ok(2));
+        let mut it = 20..;
+        assert_eq!(r.assign_from_iter(&mut it), Err(IndexOutOfBounds { index: 3, len: 3 }));
+        assert_eq!(it.next(), Some(24));
+        assert_eq!(Stride::new(&mut [0u8; 0]).assign_from_iter(None), Ok(0));
+        assert_eq!(*v, [1, 20, 2, 21, 3, 22, 4]);
+    }
+
+    #[test]
+    fn map_in_place() {
+        let v = &mut [1u16, 2, 3, 4, 5];
+        let mut s = Stride::new(v);
+        s.map_in_place(|x| *x *= 2);
+        s.reborrow().substrides2_mut().1.map_in_place(|x| *x += 1);
+        assert_eq!(*v, [2, 5, 6, 9, 10]);
+    }
+
+    #[test]
+    fn cumsum_in_place() {
+        // a 3x3 integral image, by rows and then by columns.
+        let v = &mut [1u32, 2, 3, 4, 5, 6, 7, 8, 9];
+        for i in 0..3 { Stride::new(v).slice_mut(3 * i, 3 * i + 3).cumsum_in_place() }
+        for mut col in Stride::new(v).substrides_mut(3) { col.cumsum_in_place() }
+        assert_eq!(*v, [1, 3, 6, 5, 12, 21, 12, 27, 45]);
+        Stride::<u8>::new(&mut []).cumsum_in_place();
+    }
+
+    #[test]
+    fn zip_apply() {
+        let v = &mut [1u16, 2, 3, 4, 5];
+        let w = [10u8, 20, 30, 40, 50];
+        let mut s = Stride::new(v);
+        s.zip_apply(::Stride::new(&w), |x, &y| *x += y as u16);
+        let (l, r) = s.substrides2_mut();
+        l.slice_to_mut(2).zip_apply(*r, |x, &y| *x += y);
+        assert_eq!(*v, [33, 22, 77, 44, 55]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.zip_apply: mismatched lengths 2 and 3")]
+    fn zip_apply_mismatched() {
+        Stride::new(&mut [1, 2]).zip_apply(::Stride::new(&[1, 2, 3]), |_, _| ());
+    }
+
+    #[test]
+    fn fill_and_copy() {
+        let v = &mut [0u32; 7];
+        {
+            let (mut even, mut odd) = Stride::new(v).substrides2_mut();
+            even.fill(1);
+            odd.copy_from_stride(::Stride::new(&[4, 5, 6]));
+            even.reborrow().slice_to_mut(2).copy_from_stride(*odd.slice_from_mut(1));
+        }
+        assert_eq!(*v, [5, 4, 6, 5, 1, 6, 1]);
+        Stride::new(&mut v[..3]).fill(9);
+        Stride::new(&mut v[3..]).copy_from_stride(::Stride::new(&[8; 4]));
+        assert_eq!(*v, [9, 9, 9, 8, 8, 8, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.copy_from_stride: mismatched lengths 1 and 2")]
+    fn copy_from_stride_mismatched() {
+        Stride::new(&mut [0]).copy_from_stride(::Stride::new(&[1, 2]));
+    }
+
+    #[test]
+    fn swap_with_stride() {
+        let v = &mut [1, 2, 3, 4, 5, 6];
+        {
+            let (front, back) = Stride::new(v).split_at_mut(3);
+            let (mut front, mut back) = (front, back.substrides2_mut().0);
+            front.reborrow().slice_to_mut(2).swap_with_stride(&mut back);
+            let (mut l, mut r) = front.split_at_mut(2);
+            r.swap_with_stride(&mut l.reborrow().slice_from_mut(1));
+        }
+        assert_eq!(*v, [4, 3, 6, 1, 5, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.swap_with_stride: mismatched lengths 2 and 1")]
+    fn swap_with_stride_mismatched() {
+        Stride::new(&mut [1, 2]).swap_with_stride(&mut Stride::new(&mut [3]));
+    }
 
     #[test]
     fn reborrow() {
@@ -240,4 +915,135 @@ mod tests {
         eq!(s.reborrow(), [1,2,3,4,5]);
         eq!(s.reborrow(), [1,2,3,4,5]);
     }
+
+    #[test]
+    fn as_mut_slice() {
+        let v = &mut [1u8, 2, 3, 4, 5];
+        let mut s = Stride::new(v);
+        s.as_mut_slice().unwrap()[0] = 10;
+        {
+            let (mut l, mut r) = s.reborrow().substrides2_mut();
+            assert!(l.as_mut_slice().is_none());
+            r.reborrow().slice_to_mut(1).as_mut_slice().unwrap()[0] = 20;
+        }
+        assert_eq!(s.as_mut_slice().unwrap(), &mut [10, 20, 3, 4, 5]);
+    }
+
+    #[test]
+    fn try_merge() {
+        let v = &mut [1u8, 2, 3, 4, 5];
+        let s = Stride::new(v);
+        let (l, r) = s.substrides2_mut();
+        // all these views come from `s`.
+        let (r, l) = unsafe { Stride::try_merge(r, l) }.unwrap_err();
+        let mut s = unsafe { Stride::try_merge(l, r) }.ok().unwrap();
+        eq!(s.reborrow(), [1, 2, 3, 4, 5]);
+        assert_eq!(s.stride(), 1);
+
+        let (a, b) = s.split_at_mut(2);
+        eq!(unsafe { Stride::try_merge(a, b) }.ok().unwrap(), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn items_into_stride() {
+        let v = &mut [1u8, 2, 3, 4, 5, 6, 7];
+        let (l, _) = Stride::new(v).substrides2_mut();
+        let mut it = l.into_iter();
+        *it.next().unwrap() = 10;
+        *it.next_back().unwrap() = 70;
+        assert!(!it.is_empty());
+        let mut rest = it.into_stride();
+        eq!(rest.reborrow(), [3, 5]);
+        assert_eq!(rest.stride(), 2);
+        for x in rest.iter_mut() { *x *= 10 }
+        assert_eq!(*v, [10, 2, 30, 4, 50, 6, 70]);
+    }
+
+    #[test]
+    fn into_iter() {
+        let v = &mut [1u8, 2, 3, 4, 5];
+        {
+            let (mut l, r) = Stride::new(v).substrides2_mut();
+            for x in &mut l { *x *= 10 }
+            for x in r { *x += 1 }
+            for x in l { *x += 1 }
+        }
+        assert_eq!(*v, [11, 3, 31, 5, 51]);
+    }
+
+    #[test]
+    fn into_stride() {
+        let v = &mut [1u8, 2, 3, 4, 5];
+        let shared = {
+            let (mut l, _) = Stride::new(v).substrides2_mut();
+            l[1] = 10;
+            l.into_stride()
+        };
+        eq!(shared, [1, 10, 5]);
+        assert_eq!(shared.stride(), 2);
+    }
+
+    // Only the lifetime is covariant; see the compile_fail doctest on
+    // `Stride` for the element type.
+    #[allow(dead_code)]
+    fn covariant_lifetime<'a, 'b: 'a>(s: Stride<'b, u8>) -> Stride<'a, u8> { s }
+    #[allow(dead_code)]
+    fn covariant_items<'a, 'b: 'a>(i: ::MutItems<'b, u8>) -> ::MutItems<'a, u8> { i }
+
+    #[test]
+    fn from_parts() {
+        let v = &mut [1u16, 2, 3, 4, 5, 6, 7];
+        {
+            let mut s = Stride::from_parts(v, 1, 3, 2).unwrap();
+            s[1] = 50;
+        }
+        assert_eq!(Stride::from_parts(v, 0, 0, 2).unwrap_err(), ::StrideError::ZeroStride);
+        assert_eq!(Stride::from_parts(v, 2, 3, 3).unwrap_err(), ::StrideError::OutOfBounds);
+        assert_eq!(*v, [1, 2, 3, 4, 50, 6, 7]);
+    }
+
+    #[test]
+    fn try_variants() {
+        use StrideError::*;
+        let v = &mut [1u16, 2, 3, 4, 5];
+        let mut s = Stride::new(v);
+        eq!(s.reborrow().try_slice_mut(1, 3).unwrap(), [2, 3]);
+        assert_eq!(s.reborrow().try_slice_mut(3, 2).err(), Some(InvalidRange { from: 3, to: 2 }));
+        assert_eq!(s.reborrow().try_slice_from_mut(6).err(), Some(IndexOutOfBounds { index: 6, len: 5 }));
+        eq!(s.reborrow().try_slice_to_mut(1).unwrap(), [1]);
+        assert!(s.reborrow().try_split_at_mut(6).is_err());
+        assert_eq!(s.reborrow().try_substrides_mut(0).err().unwrap(), ZeroStride);
+        for x in s.reborrow().try_substride_mut(2, 1).unwrap() { *x = 0 }
+        let (l, r) = s.try_split_at_mut(1).unwrap();
+        eq!(l, [1]);
+        eq!(r, [0, 3, 0, 5]);
+    }
+
+    #[test]
+    fn overlaps() {
+        let v = &mut [1u16, 2, 3, 4, 5];
+        let (l, r) = Stride::new(v).substrides2_mut();
+        assert!(!l.overlaps(&r));
+        assert!(l.overlaps(&*l));
+        assert!(!r.overlaps(&r.slice_to(0)));
+    }
+
+    #[test]
+    fn send_sync() {
+        fn check<T: Send + Sync>(_: &T) {}
+        let v = &mut [1u16, 2, 3, 4, 5];
+        {
+            let mut s = Stride::new(v);
+            check(&s);
+            check(&s.reborrow().iter_mut());
+            check(&s.reborrow().substrides_mut(2));
+
+            let (l, r) = s.substrides2_mut();
+            ::std::thread::scope(|scope| {
+                scope.spawn(move || for x in l { *x *= 10 });
+                scope.spawn(move || for x in r { *x += 1 });
+            });
+        }
+        assert_eq!(*v, [10, 3, 30, 5, 50]);
+    }
 }
@@ -0,0 +1,211 @@
+use std::convert::TryFrom;
+use nalgebra::{DMatrixView, DMatrixViewMut, DVectorView, DVectorViewMut, Dim, Dyn, Matrix, ViewStorage,
+               ViewStorageMut, U1};
+use {Stride, MutStride, MatStride, MutMatStride, StrideError};
+
+// Strides convert into nalgebra's `DVectorView<T, Dyn, Dyn>`, a column
+// vector view with dynamic length and strides, and back from any column
+// vector view or dynamically-sized row vector view. Strided matrices
+// likewise convert into `DMatrixView<T, Dyn, Dyn>`, and back from any
+// matrix view.
+
+
+// nalgebra ignores the column stride of a single column, but it
+// should still be a sensible value.
+fn strides(s: &Stride<'_, impl Sized>) -> (Dyn, Dyn) {
+    (Dyn(s.stride()), Dyn(s.stride().saturating_mul(s.len())))
+}
+
+impl<'a, T> From<Stride<'a, T>> for DVectorView<'a, T, Dyn, Dyn> {
+    fn from(s: Stride<'a, T>) -> DVectorView<'a, T, Dyn, Dyn> {
+        let storage = unsafe { ViewStorage::from_raw_parts(s.as_ptr(), (Dyn(s.len()), U1), strides(&s)) };
+        Matrix::from_data(storage)
+    }
+}
+impl<'a, T> From<MutStride<'a, T>> for DVectorViewMut<'a, T, Dyn, Dyn> {
+    fn from(mut s: MutStride<'a, T>) -> DVectorViewMut<'a, T, Dyn, Dyn> {
+        let (shape, strides) = ((Dyn(s.len()), U1), strides(&s));
+        let storage = unsafe { ViewStorageMut::from_raw_parts(s.as_mut_ptr(), shape, strides) };
+        Matrix::from_data(storage)
+    }
+}
+
+// the element stride of a column with the given stride and length.
+fn elem_stride(stride: usize, len: usize) -> Result<usize, StrideError> {
+    match (len, stride) {
+        (0..=1, _) => Ok(1),
+        (_, 0) => Err(StrideError::ZeroStride),
+        (_, s) => Ok(s),
+    }
+}
+
+/// Converts any column vector view, such as `DMatrix::column`, into a
+/// strided slice. Fails if the view has a zero stride and more than
+/// one element.
+impl<'a, T, R: Dim, RS: Dim, CS: Dim> TryFrom<Matrix<T, R, U1, ViewStorage<'a, T, R, U1, RS, CS>>>
+    for Stride<'a, T>
+{
+    type Error = StrideError;
+    fn try_from(m: Matrix<T, R, U1, ViewStorage<'a, T, R, U1, RS, CS>>)
+                -> Result<Stride<'a, T>, StrideError> {
+        let len = m.nrows();
+        unsafe { Stride::from_raw_parts(m.as_ptr(), len, elem_stride(m.strides().0, len)?) }
+    }
+}
+/// Converts any mutable column vector view, such as
+/// `DMatrix::column_mut`, into a strided slice. Fails if the view has
+/// a zero stride and more than one element.
+impl<'a, T, R: Dim, RS: Dim, CS: Dim> TryFrom<Matrix<T, R, U1, ViewStorageMut<'a, T, R, U1, RS, CS>>>
+    for MutStride<'a, T>
+{
+    type Error = StrideError;
+    fn try_from(mut m: Matrix<T, R, U1, ViewStorageMut<'a, T, R, U1, RS, CS>>)
+                -> Result<MutStride<'a, T>, StrideError> {
+        let len = m.nrows();
+        let stride = elem_stride(m.strides().0, len)?;
+        unsafe { MutStride::from_raw_parts(m.as_mut_ptr(), len, stride) }
+    }
+}
+
+/// Converts a dynamically-sized row vector view, such as
+/// `DMatrix::row`, into a strided slice. Fails if the view has a zero
+/// stride and more than one element.
+impl<'a, T, RS: Dim, CS: Dim> TryFrom<Matrix<T, U1, Dyn, ViewStorage<'a, T, U1, Dyn, RS, CS>>>
+    for Stride<'a, T>
+{
+    type Error = StrideError;
+    fn try_from(m: Matrix<T, U1, Dyn, ViewStorage<'a, T, U1, Dyn, RS, CS>>)
+                -> Result<Stride<'a, T>, StrideError> {
+        let len = m.ncols();
+        unsafe { Stride::from_raw_parts(m.as_ptr(), len, elem_stride(m.strides().1, len)?) }
+    }
+}
+/// Converts a mutable dynamically-sized row vector view, such as
+/// `DMatrix::row_mut`, into a strided slice. Fails if the view has a
+/// zero stride and more than one element.
+impl<'a, T, RS: Dim, CS: Dim> TryFrom<Matrix<T, U1, Dyn, ViewStorageMut<'a, T, U1, Dyn, RS, CS>>>
+    for MutStride<'a, T>
+{
+    type Error = StrideError;
+    fn try_from(mut m: Matrix<T, U1, Dyn, ViewStorageMut<'a, T, U1, Dyn, RS, CS>>)
+                -> Result<MutStride<'a, T>, StrideError> {
+        let len = m.ncols();
+        let stride = elem_stride(m.strides().1, len)?;
+        unsafe { MutStride::from_raw_parts(m.as_mut_ptr(), len, stride) }
+    }
+}
+
+impl<'a, T> From<MatStride<'a, T>> for DMatrixView<'a, T, Dyn, Dyn> {
+    fn from(m: MatStride<'a, T>) -> DMatrixView<'a, T, Dyn, Dyn> {
+        let (shape, strides) = ((Dyn(m.nrows()), Dyn(m.ncols())), (Dyn(m.row_stride()), Dyn(m.col_stride())));
+        let storage = unsafe { ViewStorage::from_raw_parts(m.as_ptr(), shape, strides) };
+        Matrix::from_data(storage)
+    }
+}
+impl<'a, T> From<MutMatStride<'a, T>> for DMatrixViewMut<'a, T, Dyn, Dyn> {
+    fn from(mut m: MutMatStride<'a, T>) -> DMatrixViewMut<'a, T, Dyn, Dyn> {
+        let (shape, strides) = ((Dyn(m.nrows()), Dyn(m.ncols())), (Dyn(m.row_stride()), Dyn(m.col_stride())));
+        let storage = unsafe { ViewStorageMut::from_raw_parts(m.as_mut_ptr(), shape, strides) };
+        Matrix::from_data(storage)
+    }
+}
+
+/// Converts any matrix view, such as `DMatrix::view`, into a strided
+/// matrix. Fails if the view has a zero stride along an axis of more
+/// than one element.
+impl<'a, T, R: Dim, C: Dim, RS: Dim, CS: Dim> TryFrom<Matrix<T, R, C, ViewStorage<'a, T, R, C, RS, CS>>>
+    for MatStride<'a, T>
+{
+    type Error = StrideError;
+    fn try_from(m: Matrix<T, R, C, ViewStorage<'a, T, R, C, RS, CS>>)
+                -> Result<MatStride<'a, T>, StrideError> {
+        let ((rows, cols), (rs, cs)) = (m.shape(), m.strides());
+        let (rs, cs) = (elem_stride(rs, rows)?, elem_stride(cs, cols)?);
+        unsafe { MatStride::from_raw_parts(m.as_ptr(), rows, cols, rs, cs) }
+    }
+}
+/// Converts any mutable matrix view, such as `DMatrix::view_mut`, into
+/// a strided matrix. Fails if the view has a zero stride along an axis
+/// of more than one element.
+impl<'a, T, R: Dim, C: Dim, RS: Dim, CS: Dim> TryFrom<Matrix<T, R, C, ViewStorageMut<'a, T, R, C, RS, CS>>>
+    for MutMatStride<'a, T>
+{
+    type Error = StrideError;
+    fn try_from(mut m: Matrix<T, R, C, ViewStorageMut<'a, T, R, C, RS, CS>>)
+                -> Result<MutMatStride<'a, T>, StrideError> {
+        let ((rows, cols), (rs, cs)) = (m.shape(), m.strides());
+        let (rs, cs) = (elem_stride(rs, rows)?, elem_stride(cs, cols)?);
+        unsafe { MutMatStride::from_raw_parts(m.as_mut_ptr(), rows, cols, rs, cs) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use nalgebra::{DMatrix, DMatrixView, DMatrixViewMut, DVectorView, DVectorViewMut, Dyn, Matrix2x3};
+    use {Stride, MutStride, MatStride, MutMatStride};
+
+    #[test]
+    fn conversions() {
+        // column-major: the rows are strided
+        let m = DMatrix::from_row_slice(2, 3, &[1, 2, 3,
+                                                4, 5, 6]);
+        let row = Stride::try_from(m.row(1)).unwrap();
+        eq!(row, [4, 5, 6]);
+        assert_eq!(row.stride(), 2);
+        let col = Stride::try_from(m.column(2)).unwrap();
+        eq!(col, [3, 6]);
+        let s = Stride::new(m.as_slice()).substride(2, 1);
+        let v = DVectorView::<_, Dyn, Dyn>::from(s);
+        assert_eq!(v.iter().copied().collect::<Vec<_>>(), [4, 5, 6]);
+        assert_eq!(v.dot(&v), 16 + 25 + 36);
+        assert_eq!(Stride::try_from(v).unwrap(), s);
+
+        let mut n = Matrix2x3::new(1, 2, 3,
+                                   4, 5, 6);
+        {
+            let mut c = MutStride::try_from(n.column_mut(1)).unwrap();
+            c[0] = 20;
+        }
+        let mut d = DMatrix::from_row_slice(2, 2, &[1, 2,
+                                                    3, 4]);
+        for x in MutStride::try_from(d.row_mut(0)).unwrap() { *x = 0 }
+        assert_eq!(d.as_slice(), [0, 3, 0, 4]);
+        {
+            let s = MutStride::new(n.as_mut_slice()).substride_mut(2, 0);
+            let mut v = DVectorViewMut::<_, Dyn, Dyn>::from(s);
+            v *= 10;
+        }
+        assert_eq!(n, Matrix2x3::new(10, 200, 30,
+                                     4, 5, 6));
+    }
+
+    #[test]
+    fn matrices() {
+        let m = DMatrix::from_row_slice(2, 3, &[1, 2, 3,
+                                                4, 5, 6]);
+        let s = MatStride::try_from(m.view((0, 0), (2, 3))).unwrap();
+        assert_eq!((s.shape(), s.row_stride(), s.col_stride()), ((2, 3), 1, 2));
+        eq!(s.row(1), [4, 5, 6]);
+        eq!(MatStride::try_from(m.view((0, 1), (2, 2))).unwrap().col(1), [3, 6]);
+        let n = Matrix2x3::new(1, 2, 3,
+                               4, 5, 6);
+        eq!(MatStride::try_from(n.fixed_view::<1, 3>(1, 0)).unwrap().row(0), [4, 5, 6]);
+
+        let v: Vec<i32> = (0..12).collect();
+        let s = MatStride::from_parts(&v, 1, 2, 3, 6, 2).unwrap();
+        let a = DMatrixView::<_, Dyn, Dyn>::from(s);
+        assert_eq!((a.shape(), a.strides()), ((2, 3), (6, 2)));
+        assert_eq!(a[(1, 2)], 11);
+        assert_eq!(MatStride::try_from(a), Ok(s));
+
+        let mut w = [0; 6];
+        {
+            let mut a = DMatrixViewMut::<_, Dyn, Dyn>::from(MutMatStride::new(&mut w, 2, 3));
+            a[(0, 1)] = 1;
+            let mut s = MutMatStride::try_from(a).unwrap();
+            s[(1, 2)] = 2;
+        }
+        assert_eq!(w, [0, 1, 0, 0, 0, 2]);
+    }
+}
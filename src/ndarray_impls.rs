@@ -0,0 +1,198 @@
+use std::convert::TryFrom;
+use ndarray::{ArrayBase, ArrayView1, ArrayView2, ArrayViewMut1, ArrayViewMut2, Data, DataMut, Ix1,
+              ShapeBuilder};
+use base::positive_stride as elem_stride;
+use {Stride, MutStride, MatStride, MutMatStride, Strided, MutStrided, StrideError};
+
+// `ptr` must point to an array of `len` elements with stride
+// `stride`, valid for `'a`.
+unsafe fn view<'a, T>(ptr: *const T, len: usize, stride: isize)
+                      -> Result<Stride<'a, T>, StrideError> {
+    Stride::from_raw_parts(ptr, len, elem_stride(stride, len)?)
+}
+// as for `view`, with unique access.
+unsafe fn view_mut<'a, T>(ptr: *mut T, len: usize, stride: isize)
+                          -> Result<MutStride<'a, T>, StrideError> {
+    MutStride::from_raw_parts(ptr, len, elem_stride(stride, len)?)
+}
+
+/// Views a 1-D array as a strided slice.
+///
+/// # Panic
+///
+/// `as_stride` panics if the array has more than one element and a
+/// negative or zero stride (such as from `slice(s![..;-1])` or
+/// `broadcast`), which strided slices can't represent. Use
+/// `Stride::try_from` on a view to handle this case.
+impl<T, S: Data<Elem = T>> Strided for ArrayBase<S, Ix1> {
+    type Elem = T;
+    #[track_caller]
+    fn as_stride(&self) -> Stride<'_, T> {
+        unsafe { view(self.as_ptr(), self.len(), self.strides()[0]) }
+            .unwrap_or_else(|e| panic!("strided: can't view ndarray as a Stride: {}", e))
+    }
+}
+impl<T, S: DataMut<Elem = T>> MutStrided for ArrayBase<S, Ix1> {
+    #[track_caller]
+    fn as_stride_mut(&mut self) -> MutStride<'_, T> {
+        let ptr = self.as_mut_ptr();
+        unsafe { view_mut(ptr, self.len(), self.strides()[0]) }
+            .unwrap_or_else(|e| panic!("strided: can't view ndarray as a Stride: {}", e))
+    }
+}
+
+impl<'a, T> From<Stride<'a, T>> for ArrayView1<'a, T> {
+    fn from(s: Stride<'a, T>) -> ArrayView1<'a, T> {
+        unsafe { ArrayView1::from_shape_ptr(s.len().strides(s.stride()), s.as_ptr()) }
+    }
+}
+impl<'a, T> From<MutStride<'a, T>> for ArrayViewMut1<'a, T> {
+    fn from(mut s: MutStride<'a, T>) -> ArrayViewMut1<'a, T> {
+        unsafe { ArrayViewMut1::from_shape_ptr(s.len().strides(s.stride()), s.as_mut_ptr()) }
+    }
+}
+
+/// Fails if the view has more than one element and a negative or
+/// zero stride.
+impl<'a, T> TryFrom<ArrayView1<'a, T>> for Stride<'a, T> {
+    type Error = StrideError;
+    fn try_from(a: ArrayView1<'a, T>) -> Result<Stride<'a, T>, StrideError> {
+        unsafe { view(a.as_ptr(), a.len(), a.strides()[0]) }
+    }
+}
+/// Fails if the view has more than one element and a negative or
+/// zero stride.
+impl<'a, T> TryFrom<ArrayViewMut1<'a, T>> for MutStride<'a, T> {
+    type Error = StrideError;
+    fn try_from(mut a: ArrayViewMut1<'a, T>) -> Result<MutStride<'a, T>, StrideError> {
+        let ptr = a.as_mut_ptr();
+        unsafe { view_mut(ptr, a.len(), a.strides()[0]) }
+    }
+}
+
+impl<'a, T> From<MatStride<'a, T>> for ArrayView2<'a, T> {
+    fn from(m: MatStride<'a, T>) -> ArrayView2<'a, T> {
+        let shape = m.shape().strides((m.row_stride(), m.col_stride()));
+        unsafe { ArrayView2::from_shape_ptr(shape, m.as_ptr()) }
+    }
+}
+impl<'a, T> From<MutMatStride<'a, T>> for ArrayViewMut2<'a, T> {
+    fn from(mut m: MutMatStride<'a, T>) -> ArrayViewMut2<'a, T> {
+        let shape = m.shape().strides((m.row_stride(), m.col_stride()));
+        unsafe { ArrayViewMut2::from_shape_ptr(shape, m.as_mut_ptr()) }
+    }
+}
+
+/// Fails if the view has a negative or zero stride along an axis of
+/// more than one element.
+impl<'a, T> TryFrom<ArrayView2<'a, T>> for MatStride<'a, T> {
+    type Error = StrideError;
+    fn try_from(a: ArrayView2<'a, T>) -> Result<MatStride<'a, T>, StrideError> {
+        let (rows, cols) = a.dim();
+        let (rs, cs) = (elem_stride(a.strides()[0], rows)?, elem_stride(a.strides()[1], cols)?);
+        unsafe { MatStride::from_raw_parts(a.as_ptr(), rows, cols, rs, cs) }
+    }
+}
+/// Fails if the view has a negative or zero stride along an axis of
+/// more than one element.
+impl<'a, T> TryFrom<ArrayViewMut2<'a, T>> for MutMatStride<'a, T> {
+    type Error = StrideError;
+    fn try_from(mut a: ArrayViewMut2<'a, T>) -> Result<MutMatStride<'a, T>, StrideError> {
+        let (rows, cols) = a.dim();
+        let (rs, cs) = (elem_stride(a.strides()[0], rows)?, elem_stride(a.strides()[1], cols)?);
+        let ptr = a.as_mut_ptr();
+        unsafe { MutMatStride::from_raw_parts(ptr, rows, cols, rs, cs) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use ndarray::{arr1, arr2, s, Array1, ArrayView1, ArrayView2, ArrayViewMut1, ArrayViewMut2};
+    use {Stride, MutStride, MatStride, MutMatStride, Strided, MutStrided, StrideError};
+
+    #[test]
+    fn views() {
+        let a = arr2(&[[1, 2, 3], [4, 5, 6]]);
+        let col = a.column(1);
+        eq!(col.as_stride(), [2, 5]);
+        assert_eq!(col.as_stride().stride(), 3);
+        let row = a.slice(s![1, ..;2]);
+        eq!(row.as_stride(), [4, 6]);
+        let empty = ArrayView1::<i32>::from(&[][..]);
+        eq!(empty.as_stride(), []);
+        // a single element, with a negative stride
+        let single = a.slice(s![..1, 0]);
+        let single = single.slice(s![..;-1]);
+        eq!(single.as_stride(), [1]);
+
+        let mut b = arr2(&[[1, 2, 3], [4, 5, 6]]);
+        for x in b.column_mut(2).as_stride_mut() { *x *= 10 }
+        let mut owned: Array1<i32> = arr1(&[1, 2, 3]);
+        owned.as_stride_mut()[1] = 20;
+        assert_eq!(b, arr2(&[[1, 2, 30], [4, 5, 60]]));
+        assert_eq!(owned, arr1(&[1, 20, 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't view ndarray as a Stride: stride must not be negative")]
+    fn negative() {
+        let a = arr1(&[1, 2, 3]);
+        a.slice(s![..;-1]).as_stride();
+    }
+
+    #[test]
+    fn conversions() {
+        let v = [1, 2, 3, 4, 5, 6, 7];
+        let s = Stride::new(&v).substride(3, 1);
+        let a = ArrayView1::from(s);
+        assert_eq!(a, arr1(&[2, 5]));
+        assert_eq!(a.strides(), [3]);
+        assert_eq!(Stride::try_from(a), Ok(s));
+        assert_eq!(Stride::try_from(a.slice(s![..;-1])), Err(StrideError::NegativeStride));
+        let b = arr1(&[1]);
+        let b = b.broadcast(3).unwrap();
+        assert_eq!(Stride::try_from(b), Err(StrideError::ZeroStride));
+
+        let mut w = [1, 2, 3, 4, 5, 6, 7];
+        {
+            let mut a = ArrayViewMut1::from(MutStride::new(&mut w).substride_mut(3, 2));
+            a[1] = 60;
+            let mut s = MutStride::try_from(a).unwrap();
+            s[0] = 30;
+        }
+        assert_eq!(w, [1, 2, 30, 4, 5, 60, 7]);
+    }
+
+    #[test]
+    fn matrices() {
+        let a = arr2(&[[1, 2, 3], [4, 5, 6]]);
+        let m = MatStride::try_from(a.view()).unwrap();
+        assert_eq!((m.shape(), m.row_stride(), m.col_stride()), ((2, 3), 3, 1));
+        eq!(m.row(1), [4, 5, 6]);
+        let t = MatStride::try_from(a.t()).unwrap();
+        assert_eq!((t.row_stride(), t.col_stride()), (1, 3));
+        eq!(t.row(2), [3, 6]);
+        eq!(MatStride::try_from(a.slice(s![.., ..;2])).unwrap().col(1), [3, 6]);
+        assert_eq!(MatStride::try_from(a.slice(s![..;-1, ..])), Err(StrideError::NegativeStride));
+        eq!(MatStride::try_from(a.slice(s![..1;-1, ..])).unwrap().row(0), [1, 2, 3]);
+        let b = arr1(&[1, 2]);
+        assert_eq!(MatStride::try_from(b.broadcast((3, 2)).unwrap()), Err(StrideError::ZeroStride));
+
+        let v: Vec<i32> = (0..12).collect();
+        let m = MatStride::from_parts(&v, 1, 2, 3, 6, 2).unwrap();
+        let a = ArrayView2::from(m);
+        assert_eq!(a, arr2(&[[1, 3, 5], [7, 9, 11]]));
+        assert_eq!(a.strides(), [6, 2]);
+        assert_eq!(MatStride::try_from(a), Ok(m));
+
+        let mut w = [0; 6];
+        {
+            let mut a = ArrayViewMut2::from(MutMatStride::new(&mut w, 2, 3));
+            a[(0, 1)] = 1;
+            let mut m = MutMatStride::try_from(a.reversed_axes()).unwrap();
+            m[(2, 1)] = 2;
+        }
+        assert_eq!(w, [0, 1, 0, 0, 0, 2]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Normalisation kernels for neural network code, applied along a
+//! strided axis such as a column of a row-major matrix.
+//!
+//! Both use the online normaliser of Milakov and Gimelshein, which
+//! finds the maximum and the sum of exponentials relative to it in
+//! one pass, so they neither overflow for large inputs nor need a
+//! separate pass for the maximum.
+//!
+//! ```rust
+//! use strided::{MutStride, nn};
+//!
+//! // attention scores, softmaxed down each column.
+//! let mut scores = [1.0f32, 0.0, 1.0, 0.0];
+//! let (col0, col1) = MutStride::new(&mut scores).substrides2_mut();
+//! assert_eq!(nn::logsumexp(&col1), 2f32.ln());
+//! nn::softmax_in_place(col0);
+//! assert_eq!(scores, [0.5, 0.0, 0.5, 0.0]);
+//! ```
+
+use blas1::Float;
+use {Stride, MutStride};
+
+// the maximum of `x`, and the sum of `exp(a - max)` over its elements
+// `a`.
+fn max_and_sum<T: Float>(x: &Stride<'_, T>) -> (T, T) {
+    let (mut max, mut sum) = (T::NEG_INFINITY, T::ZERO);
+    for &a in x {
+        if a > max {
+            sum = sum * (max - a).exp() + T::ONE;
+            max = a;
+        } else if a == max {
+            // avoids `exp(inf - inf)`.
+            sum = sum + T::ONE;
+        } else {
+            sum = sum + (a - max).exp();
+        }
+    }
+    (max, sum)
+}
+
+/// Returns `ln(Σ exp(x[i]))`, without overflowing for large elements.
+/// This is negative infinity if `x` is empty.
+pub fn logsumexp<T: Float>(x: &Stride<'_, T>) -> T {
+    let (max, sum) = max_and_sum(x);
+    if x.is_empty() { return T::NEG_INFINITY }
+    max + sum.ln()
+}
+
+/// Replaces each element `a` of `x` with `exp(a) / Σ exp(x[i])`, so
+/// that they are positive and sum to one, in two passes.
+pub fn softmax_in_place<T: Float>(mut x: MutStride<'_, T>) {
+    let (max, sum) = max_and_sum(&x);
+    let inv = T::ONE / sum;
+    x.map_in_place(|a| *a = (*a - max).exp() * inv)
+}
+
+#[cfg(test)]
+mod tests {
+    use {Stride, MutStride};
+    use super::*;
+
+    #[test]
+    fn logsumexp() {
+        let v = [1000.0f64, 0.0, 1000.0, 0.0, f64::NEG_INFINITY];
+        let (a, b) = Stride::new(&v).substrides2();
+        assert_eq!(super::logsumexp(&a), 1000.0 + 2f64.ln());
+        assert_eq!(super::logsumexp(&b), 2f64.ln());
+        assert_eq!(super::logsumexp(&Stride::new(&[0.0, f64::INFINITY, f64::INFINITY])), f64::INFINITY);
+        assert_eq!(super::logsumexp::<f32>(&Stride::empty()), f32::NEG_INFINITY);
+        let w = [-1.0f64, 3.0, 2.0];
+        let naive = w.iter().map(|a| a.exp()).sum::<f64>().ln();
+        assert!((super::logsumexp(&Stride::new(&w)) - naive).abs() < 1e-12);
+    }
+
+    #[test]
+    fn softmax() {
+        let mut v = [1000.0f32, 9.0, 1000.0, 9.0, f32::NEG_INFINITY, 9.0];
+        let (a, _) = MutStride::new(&mut v).substrides2_mut();
+        softmax_in_place(a);
+        assert_eq!(v, [0.5, 9.0, 0.5, 9.0, 0.0, 9.0]);
+
+        let mut w = [3.0f64, 1.0, 2.0];
+        softmax_in_place(MutStride::new(&mut w));
+        let z = 3f64.exp() + 1f64.exp() + 2f64.exp();
+        for (x, e) in w.iter().zip([3f64.exp() / z, 1f64.exp() / z, 2f64.exp() / z]) {
+            assert!((x - e).abs() < 1e-12);
+        }
+        softmax_in_place(MutStride::<f32>::new(&mut []));
+    }
+}
@@ -0,0 +1,76 @@
+use std::ops::{AddAssign, SubAssign, MulAssign, DivAssign};
+use {Stride, MutStride};
+
+macro_rules! assign_ops {
+    ($($Trait: ident, $method: ident, $op: tt;)*) => {$(
+        /// Updates each element of `self` with the corresponding
+        /// element of `rhs`.
+        ///
+        /// # Panic
+        ///
+        /// Panics if `self` and `rhs` have different lengths.
+        ///
+        /// ```rust
+        /// use strided::{Stride, MutStride};
+        ///
+        /// let mut m = [1.0, 2.0, 3.0, 4.0];
+        /// let (mut col0, col1) = MutStride::new(&mut m).substrides2_mut();
+        /// col0 += *col1;
+        /// col0 *= 0.5;
+        /// assert_eq!(m, [1.5, 2.0, 3.5, 4.0]);
+        /// ```
+        impl<'a, 'b, T: $Trait<U>, U: Copy> $Trait<Stride<'b, U>> for MutStride<'a, T> {
+            #[inline]
+            #[track_caller]
+            fn $method(&mut self, rhs: Stride<'b, U>) {
+                assert!(self.len() == rhs.len(),
+                        concat!("Stride.", stringify!($method), ": mismatched lengths {} and {}"),
+                        self.len(), rhs.len());
+                self.zip_apply(rhs, |x, &y| *x $op y)
+            }
+        }
+
+        /// Updates every element of `self` with `rhs`.
+        impl<'a, T: $Trait + Copy> $Trait<T> for MutStride<'a, T> {
+            #[inline]
+            fn $method(&mut self, rhs: T) {
+                self.map_in_place(|x| *x $op rhs)
+            }
+        }
+    )*}
+}
+assign_ops! {
+    AddAssign, add_assign, +=;
+    SubAssign, sub_assign, -=;
+    MulAssign, mul_assign, *=;
+    DivAssign, div_assign, /=;
+}
+
+#[cfg(test)]
+mod tests {
+    use {Stride, MutStride};
+
+    #[test]
+    fn assign() {
+        let v = &mut [1i32, 2, 3, 4, 5, 6];
+        let (mut a, b) = MutStride::new(v).substrides2_mut();
+        a += *b;
+        a -= Stride::new(&[1, 1, 1]);
+        a *= 10;
+        a /= Stride::new(&[2, 5, 10]);
+        let w = &mut [0.5f64, 1.0];
+        let mut c = MutStride::new(w);
+        c -= 1.0;
+        c *= Stride::new(&[4.0, 2.0]);
+        eq!(c, [-2.0, 0.0]);
+        assert_eq!(*v, [10, 2, 12, 4, 10, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.add_assign: mismatched lengths 2 and 1")]
+    fn mismatched() {
+        let v = &mut [1, 2];
+        let mut s = MutStride::new(v);
+        s += Stride::new(&[1]);
+    }
+}
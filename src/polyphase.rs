@@ -0,0 +1,125 @@
+//! Polyphase decimation and interpolation.
+//!
+//! Phase `p` of a signal split into `n` phases is every `n`th sample
+//! starting at `p`, exactly `substrides(n)`. Filtering then
+//! downsampling (or upsampling then filtering) by `n` is equivalent
+//! to filtering each phase with the matching phase of the FIR
+//! coefficients, which skips every product with a discarded output
+//! or an inserted zero.
+//!
+//! ```rust
+//! use strided::{Stride, polyphase};
+//!
+//! let signal = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+//! // average adjacent pairs, keeping every second output.
+//! let half = polyphase::decimate(&Stride::new(&signal), 2, &[0.5, 0.5]);
+//! assert_eq!(half, [0.5, 2.5, 4.5]);
+//! // insert a sample between each pair, by linear interpolation.
+//! let double = polyphase::interpolate(&Stride::new(&half), 2, &[0.5, 1.0, 0.5]);
+//! assert_eq!(double, [0.25, 0.5, 1.5, 2.5, 3.5, 4.5]);
+//! ```
+
+use blas1::{axpy, Float};
+use {Stride, MutStride};
+
+// adds the convolution of `x` and `h`, delayed by `delay` samples, to
+// `y`: `y[m] += Σ h[j] x[m - j - delay]`, over the valid indices.
+fn convolve_add<T: Float>(x: Stride<'_, T>, h: Stride<'_, T>, delay: usize, mut y: MutStride<'_, T>) {
+    for (j, &c) in h.iter().enumerate() {
+        let start = j + delay;
+        if start >= y.len() { break }
+        let n = (y.len() - start).min(x.len());
+        axpy(c, &x.slice_to(n), y.reborrow().slice_mut(start, start + n));
+    }
+}
+
+/// Filters `signal` with the FIR filter with coefficients `fir`, and
+/// keeps every `factor`th output, starting with the first.
+///
+/// The result has `ceil(signal.len() / factor)` samples, with `y[m] =
+/// Σ fir[k] signal[m * factor - k]` (treating samples before the
+/// start as zero).
+///
+/// # Panic
+///
+/// Panics if `factor` is zero.
+#[track_caller]
+pub fn decimate<T: Float>(signal: &Stride<'_, T>, factor: usize, fir: &[T]) -> Vec<T> {
+    assert!(factor != 0, "strided::polyphase::decimate: factor must be non-zero");
+    let mut y = vec![T::ZERO; signal.len().div_ceil(factor)];
+    let phases = signal.substrides(factor).collect::<Vec<_>>();
+    for (p, h) in Stride::new(fir).substrides(factor).enumerate() {
+        // `signal[m * factor - (j * factor + p)]` is element
+        // `m - j` of phase 0 when `p` is zero, and otherwise element
+        // `m - j - 1` of phase `factor - p`.
+        let (x, delay) = if p == 0 { (phases[0], 0) } else { (phases[factor - p], 1) };
+        convolve_add(x, h, delay, MutStride::new(&mut y));
+    }
+    y
+}
+
+/// Inserts `factor - 1` zeros after each sample of `signal`, and
+/// filters the result with the FIR filter with coefficients `fir`.
+///
+/// The result has `signal.len() * factor` samples. The inserted zeros
+/// scale the signal down by `factor`, so a typical interpolation
+/// filter has a gain of `factor`.
+///
+/// # Panic
+///
+/// Panics if `factor` is zero.
+#[track_caller]
+pub fn interpolate<T: Float>(signal: &Stride<'_, T>, factor: usize, fir: &[T]) -> Vec<T> {
+    assert!(factor != 0, "strided::polyphase::interpolate: factor must be non-zero");
+    let mut y = vec![T::ZERO; signal.len() * factor];
+    // phase `r` of the output, `y[m * factor + r]`, is `Σ fir[j *
+    // factor + r] signal[m - j]`.
+    let out = MutStride::new(&mut y).substrides_mut(factor);
+    for (h, y) in Stride::new(fir).substrides(factor).zip(out) {
+        convolve_add(*signal, h, 0, y);
+    }
+    y
+}
+
+#[cfg(test)]
+mod tests {
+    use Stride;
+    use super::*;
+
+    // the direct forms, for comparison.
+    fn filter(x: &[f64], h: &[f64]) -> Vec<f64> {
+        (0..x.len()).map(|n| (0..h.len()).filter(|&k| k <= n).map(|k| h[k] * x[n - k]).sum()).collect()
+    }
+
+    #[test]
+    fn decimate_matches_direct() {
+        let x = (0..23).map(|i| (i * i % 11) as f64).collect::<Vec<_>>();
+        let h = [1.0, -2.0, 3.0, 5.0, 7.0, 0.5, 2.0];
+        for factor in 1..10 {
+            let direct = filter(&x, &h).into_iter().step_by(factor).collect::<Vec<_>>();
+            assert_eq!(decimate(&Stride::new(&x), factor, &h), direct, "factor {}", factor);
+        }
+        let (even, _) = Stride::new(&x).substrides2();
+        let even_vec = even.iter().cloned().collect::<Vec<_>>();
+        assert_eq!(decimate(&even, 3, &h), filter(&even_vec, &h).into_iter().step_by(3).collect::<Vec<_>>());
+        assert_eq!(decimate(&Stride::new(&x), 2, &[]), [0.0; 12]);
+        assert_eq!(decimate::<f64>(&Stride::empty(), 2, &h), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn interpolate_matches_direct() {
+        let x = (0..13).map(|i| (i * 7 % 5) as f64).collect::<Vec<_>>();
+        let h = [1.0, -2.0, 3.0, 5.0, 7.0, 0.5, 2.0];
+        for factor in 1..10 {
+            let mut up = vec![0.0; x.len() * factor];
+            for (i, &a) in x.iter().enumerate() { up[i * factor] = a }
+            assert_eq!(interpolate(&Stride::new(&x), factor, &h), filter(&up, &h), "factor {}", factor);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::polyphase::decimate: factor must be non-zero")]
+    fn zero_factor() {
+        decimate(&Stride::new(&[1.0]), 0, &[1.0]);
+    }
+}
@@ -0,0 +1,97 @@
+use numpy::{Element, PyArrayMethods, PyReadonlyArray1, PyReadwriteArray1, PyUntypedArrayMethods};
+use {Stride, MutStride, StrideError};
+
+// NumPy strides are in bytes and may be negative, zero (from
+// `broadcast_to`), or not a multiple of the element size (from
+// fields of structured arrays), all of which `from_byte_stride`
+// rejects. The borrow held by the `PyReadonlyArray1` or
+// `PyReadwriteArray1` keeps the data alive and unaliased for `'a`.
+//
+// These are inherent constructors rather than `TryFrom` impls, which
+// would overlap with the blanket `From<&U> where U: AsRef<[T]>`.
+
+impl<'a, T: Element> Stride<'a, T> {
+    /// Creates a strided slice viewing the elements of a NumPy array,
+    /// without copying.
+    ///
+    /// Fails if the array has more than one element and a negative or
+    /// zero stride, or if its data or stride is misaligned for `T`.
+    pub fn from_numpy(a: &'a PyReadonlyArray1<'_, T>) -> Result<Stride<'a, T>, StrideError> {
+        unsafe { Stride::from_byte_stride(a.data(), a.len(), a.strides()[0]) }
+    }
+}
+
+impl<'a, T: Element> MutStride<'a, T> {
+    /// Creates a mutable strided slice viewing the elements of a
+    /// NumPy array, without copying.
+    ///
+    /// Fails if the array has more than one element and a negative or
+    /// zero stride, or if its data or stride is misaligned for `T`.
+    pub fn from_numpy_mut(a: &'a mut PyReadwriteArray1<'_, T>) -> Result<MutStride<'a, T>, StrideError> {
+        unsafe { MutStride::from_byte_stride(a.data(), a.len(), a.strides()[0]) }
+    }
+}
+
+// These need NumPy to be installed for the Python that the tests link
+// against.
+#[cfg(test)]
+mod tests {
+    use numpy::pyo3::prelude::*;
+    use numpy::pyo3::types::PySlice;
+    use numpy::{PyArray1, PyArrayMethods};
+    use {Stride, MutStride, StrideError};
+
+    // `a[start:stop:step]`, a view of `a` without copying.
+    fn view<'py>(a: &Bound<'py, PyArray1<f64>>, (start, stop, step): (isize, isize, isize))
+                 -> Bound<'py, PyArray1<f64>> {
+        a.get_item(PySlice::new(a.py(), start, stop, step)).unwrap().cast_into().unwrap()
+    }
+
+    // runs `f` with the interpreter attached, skipping the test if
+    // NumPy is not installed.
+    fn with_numpy(f: impl FnOnce(Python<'_>)) {
+        Python::initialize();
+        Python::attach(|py| {
+            if py.import("numpy").is_err() {
+                eprintln!("skipping: NumPy is not installed");
+                return
+            }
+            f(py)
+        })
+    }
+
+    #[test]
+    fn from_numpy() {
+        with_numpy(|py| {
+            let a = PyArray1::arange(py, 0.0, 10.0, 1.0);
+            let odd = view(&a, (1, 10, 2));
+            let r = odd.readonly();
+            let s = Stride::from_numpy(&r).unwrap();
+            assert_eq!(s, [1.0, 3.0, 5.0, 7.0, 9.0]);
+            assert_eq!(s.stride(), 2);
+
+            let rev = view(&a, (9, -11, -1));
+            assert_eq!(Stride::from_numpy(&rev.readonly()), Err(StrideError::NegativeStride));
+            // a single element may have any stride.
+            let one = view(&a, (4, 3, -1));
+            assert_eq!(Stride::from_numpy(&one.readonly()).unwrap(), [4.0]);
+        });
+    }
+
+    #[test]
+    fn from_numpy_mut() {
+        with_numpy(|py| {
+            let a = PyArray1::arange(py, 0.0, 6.0, 1.0);
+            {
+                let mut w = view(&a, (1, 6, 2)).readwrite();
+                let mut s = MutStride::from_numpy_mut(&mut w).unwrap();
+                assert_eq!(s.stride(), 2);
+                for x in s.iter_mut() { *x *= 10.0 }
+            }
+            assert_eq!(a.readonly().as_slice().unwrap(), [0.0, 10.0, 2.0, 30.0, 4.0, 50.0]);
+
+            let mut w = view(&a, (5, -7, -2)).readwrite();
+            assert_eq!(MutStride::from_numpy_mut(&mut w).err(), Some(StrideError::NegativeStride));
+        });
+    }
+}
@@ -0,0 +1,85 @@
+//! Conversions between `f32` values and affine-quantised `i8` ones,
+//! where `q` represents `(q - zero_point) * scale`, as stored by
+//! quantised ML models.
+//!
+//! ```rust
+//! use strided::{Stride, MutStride, quant};
+//!
+//! // activations interleaved with another channel.
+//! let act = [0.5f32, 9.0, -1.0, 9.0, 100.0, 9.0];
+//! let mut q = [0i8; 3];
+//! let (act, _) = Stride::new(&act).substrides2();
+//! quant::quantize_into(&act, MutStride::new(&mut q), 0.25, 10);
+//! assert_eq!(q, [12, 6, 127]);
+//!
+//! let mut back = [0.0f32; 3];
+//! quant::dequantize_into(&Stride::new(&q), MutStride::new(&mut back), 0.25, 10);
+//! assert_eq!(back, [0.5, -1.0, 29.25]);
+//! ```
+
+use {Stride, MutStride};
+
+#[track_caller]
+fn check_lengths(a: usize, b: usize, func: &str) {
+    assert!(a == b, "strided::quant::{}: mismatched lengths {} and {}", func, a, b);
+}
+
+/// Quantises each element `x` of `src` into the corresponding element
+/// of `dst`, as `x / scale` rounded to the nearest integer (ties to
+/// even, as ONNX's `QuantizeLinear`) plus `zero_point`, saturated to
+/// the range of `i8`. NaNs become `zero_point`.
+///
+/// # Panic
+///
+/// Panics if `src` and `dst` have different lengths.
+#[track_caller]
+pub fn quantize_into(src: &Stride<'_, f32>, dst: MutStride<'_, i8>, scale: f32, zero_point: i8) {
+    check_lengths(src.len(), dst.len(), "quantize_into");
+    for (&x, q) in ::zip(*src, dst) {
+        let v = (x / scale).round_ties_even() + zero_point as f32;
+        // `as` saturates, but would take NaN to 0.
+        *q = if v.is_nan() { zero_point } else { v as i8 };
+    }
+}
+
+/// Dequantises each element `q` of `src` into the corresponding
+/// element of `dst`, as `(q - zero_point) * scale`.
+///
+/// # Panic
+///
+/// Panics if `src` and `dst` have different lengths.
+#[track_caller]
+pub fn dequantize_into(src: &Stride<'_, i8>, dst: MutStride<'_, f32>, scale: f32, zero_point: i8) {
+    check_lengths(src.len(), dst.len(), "dequantize_into");
+    for (&q, x) in ::zip(*src, dst) {
+        *x = (q as i32 - zero_point as i32) as f32 * scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Stride, MutStride};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let x = [0.0f32, 0.25, 0.5, 0.75, -0.25, -1e9, 1e9, f32::NAN, f32::INFINITY];
+        let mut q = [0i8; 18];
+        {
+            let (_, odd) = MutStride::new(&mut q).substrides2_mut();
+            quantize_into(&Stride::new(&x), odd, 0.5, -3);
+        }
+        assert_eq!(Stride::new(&q).substrides2().1, [-3, -3, -2, -1, -3, -128, 127, -3, 127]);
+        assert!(Stride::new(&q).substrides2().0.iter().all(|&q| q == 0));
+
+        let mut y = [0.0f32; 9];
+        dequantize_into(&Stride::new(&q).substrides2().1, MutStride::new(&mut y), 0.5, -3);
+        assert_eq!(y, [0.0, 0.0, 0.5, 1.0, 0.0, -62.5, 65.0, 0.0, 65.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::quant::quantize_into: mismatched lengths 1 and 2")]
+    fn mismatched() {
+        quantize_into(&Stride::new(&[1.0]), MutStride::new(&mut [0; 2]), 1.0, 0);
+    }
+}
@@ -0,0 +1,81 @@
+use quickcheck::{Arbitrary, Gen};
+use StridedBuf;
+
+// Strides are kept small, so that most of the generated elements are
+// actually part of the view.
+const MAX_STRIDE: usize = 8;
+
+fn below(g: &mut Gen, n: usize) -> usize {
+    usize::arbitrary(g) % n
+}
+
+/// Generates a buffer with a random offset, stride (between 1 and 8)
+/// and length, with random padding after the last element. The length
+/// is bounded by the generator's size.
+///
+/// Shrinking truncates the view, and then tries laying its elements
+/// out contiguously.
+impl<T: Arbitrary> Arbitrary for StridedBuf<T> {
+    fn arbitrary(g: &mut Gen) -> StridedBuf<T> {
+        let size = g.size() + 1;
+        let stride = 1 + below(g, MAX_STRIDE);
+        let offset = below(g, stride + 1);
+        let padding = below(g, stride);
+        let len = below(g, size);
+        let total = match len {
+            0 => offset + padding,
+            _ => offset + (len - 1) * stride + 1 + padding,
+        };
+        let data = (0..total).map(|_| T::arbitrary(g)).collect();
+        StridedBuf::new(data, offset, stride, len).unwrap()
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = StridedBuf<T>>> {
+        let (offset, stride, len) = (self.offset(), self.stride(), self.len());
+        let mut out = vec![];
+        let mut lens = vec![0, len / 2, len.saturating_sub(1)];
+        lens.dedup();
+        for new_len in lens.into_iter().filter(|&l| l < len) {
+            let extent = match new_len {
+                0 => offset,
+                _ => offset + (new_len - 1) * stride + 1,
+            };
+            let data = self.data()[..extent].to_vec();
+            out.push(StridedBuf::new(data, offset, stride, new_len).unwrap());
+        }
+        if self.data().len() != len {
+            let data = self.as_stride().iter().cloned().collect();
+            out.push(StridedBuf::new(data, 0, 1, len).unwrap());
+        }
+        Box::new(out.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::{Arbitrary, Gen, QuickCheck};
+    use StridedBuf;
+
+    fn valid(b: StridedBuf<u16>) -> bool {
+        let s = b.as_stride();
+        s.len() == b.len() && b.stride() >= 1 &&
+            s.iter().enumerate().all(|(i, x)| *x == b.data()[b.offset() + i * b.stride()])
+    }
+
+    #[test]
+    fn arbitrary() {
+        QuickCheck::new().quickcheck(valid as fn(StridedBuf<u16>) -> bool);
+    }
+
+    #[test]
+    fn shrink() {
+        let mut g = Gen::new(20);
+        for _ in 0..50 {
+            let b = StridedBuf::<u16>::arbitrary(&mut g);
+            for s in b.shrink() {
+                assert!(s.len() < b.len() || s.data().len() < b.data().len());
+                assert!(valid(s));
+            }
+        }
+    }
+}
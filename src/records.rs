@@ -0,0 +1,94 @@
+//! Views of one field of memory-mapped files of fixed-width records.
+//!
+//! A file of `record_size`-byte records, each holding a `T` at byte
+//! `field_offset`, has that field laid out as a strided slice, so a
+//! column can be scanned straight out of the page cache.
+//!
+//! ```rust,no_run
+//! # extern crate memmap2;
+//! # extern crate strided;
+//! # fn main() -> std::io::Result<()> {
+//! use std::fs::File;
+//! use memmap2::Mmap;
+//!
+//! // records of a `u32` id followed by an `f32` reading.
+//! let map = unsafe { Mmap::map(&File::open("readings.bin")?)? };
+//! let readings = strided::records::field::<f32>(&map, 8, 4).unwrap();
+//! let total: f32 = readings.iter().sum();
+//! # Ok(()) }
+//! ```
+
+use std::mem;
+use bytemuck::{AnyBitPattern, Pod};
+use memmap2::{Mmap, MmapMut};
+use {Stride, MutStride, StrideError};
+
+// returns the number of whole records, and checks that the field lies
+// within one.
+fn records<T>(data_len: usize, record_size: usize, field_offset: usize)
+              -> Result<usize, StrideError> {
+    if record_size == 0 { return Err(StrideError::ZeroStride) }
+    match field_offset.checked_add(mem::size_of::<T>()) {
+        Some(end) if end <= record_size => Ok(data_len / record_size),
+        _ => Err(StrideError::OutOfBounds),
+    }
+}
+
+/// Returns a view of the `T` at byte `field_offset` of each
+/// `record_size`-byte record of `map`. Trailing bytes that do not
+/// make up a whole record are ignored.
+///
+/// Returns `Err(ZeroStride)` if `record_size` is zero,
+/// `Err(OutOfBounds)` if the field does not fit in a record, and
+/// `Err(Misaligned)` if the fields are not aligned for `T` or
+/// `record_size` is not a multiple of its size.
+pub fn field<T: AnyBitPattern>(map: &Mmap, record_size: usize, field_offset: usize)
+                               -> Result<Stride<'_, T>, StrideError> {
+    let len = records::<T>(map.len(), record_size, field_offset)?;
+    if len == 0 { return Ok(Stride::empty()) }
+    // `len > 0` puts the first field within `map`, and `record_size`
+    // within `isize`.
+    let data = unsafe { map.as_ptr().add(field_offset) } as *const T;
+    unsafe { Stride::from_byte_stride(data, len, record_size as isize) }
+}
+
+/// Returns a mutable view of the `T` at byte `field_offset` of each
+/// `record_size`-byte record of `map`, like `field`.
+pub fn field_mut<T: Pod>(map: &mut MmapMut, record_size: usize, field_offset: usize)
+                         -> Result<MutStride<'_, T>, StrideError> {
+    let len = records::<T>(map.len(), record_size, field_offset)?;
+    if len == 0 { return Ok(MutStride::empty()) }
+    let data = unsafe { map.as_mut_ptr().add(field_offset) } as *mut T;
+    unsafe { MutStride::from_byte_stride(data, len, record_size as isize) }
+}
+
+#[cfg(test)]
+mod tests {
+    use memmap2::MmapMut;
+    use StrideError::*;
+    use super::{field, field_mut};
+
+    #[test]
+    fn fields() {
+        let mut map = MmapMut::map_anon(26).unwrap();
+        for (i, rec) in map.chunks_exact_mut(8).enumerate() {
+            rec[..4].copy_from_slice(&(i as u32).to_ne_bytes());
+            rec[4..].copy_from_slice(&(i as u16 * 10).to_ne_bytes().repeat(2));
+        }
+        for x in field_mut::<u16>(&mut map, 8, 6).unwrap() { *x += 1 }
+
+        let map = map.make_read_only().unwrap();
+        assert_eq!(field::<u32>(&map, 8, 0).unwrap(), [0, 1, 2]);
+        assert_eq!(field::<u16>(&map, 8, 4).unwrap(), [0, 10, 20]);
+        assert_eq!(field::<u16>(&map, 8, 6).unwrap(), [1, 11, 21]);
+        assert_eq!(field::<u64>(&map, 8, 0).unwrap().len(), 3);
+        assert_eq!(field::<u8>(&map, 26, 25).unwrap(), [0]);
+        assert_eq!(field::<u8>(&map, 27, 0).unwrap().len(), 0);
+
+        assert_eq!(field::<u32>(&map, 0, 0), Err(ZeroStride));
+        assert_eq!(field::<u32>(&map, 8, 6), Err(OutOfBounds));
+        assert_eq!(field::<u32>(&map, 8, usize::MAX), Err(OutOfBounds));
+        assert_eq!(field::<u32>(&map, 8, 2), Err(Misaligned));
+        assert_eq!(field::<u32>(&map, 6, 0), Err(Misaligned));
+    }
+}
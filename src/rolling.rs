@@ -0,0 +1,207 @@
+//! Reductions over every window of consecutive elements of a strided
+//! vector, such as moving averages of one channel of interleaved
+//! data.
+//!
+//! Each takes linear time, however wide the windows are: sums are
+//! updated by adding the element entering the window and subtracting
+//! the one leaving it, and extrema are tracked with a monotonic
+//! queue of candidates. There are `src.len() - width + 1` windows
+//! (none if `src` is shorter than `width`), the same as
+//! `chunks_with_hop(width, 1)`, and `dst` must have exactly that
+//! many elements.
+//!
+//! ```rust
+//! use strided::{Stride, MutStride, rolling};
+//!
+//! let xy = [1.0, 0.0, 3.0, 0.0, 2.0, 0.0, 6.0, 0.0];
+//! let (x, _) = Stride::new(&xy).substrides2();
+//! let mut out = [0.0; 3];
+//! rolling::mean(&x, 2, MutStride::new(&mut out));
+//! assert_eq!(out, [2.0, 2.5, 4.0]);
+//! rolling::max(&x, 2, MutStride::new(&mut out));
+//! assert_eq!(out, [3.0, 3.0, 6.0]);
+//! ```
+
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+use blas1::Float;
+use {Stride, MutStride};
+
+#[track_caller]
+fn check(len: usize, width: usize, out: usize, func: &str) {
+    assert!(width != 0, "strided::rolling::{}: width must be non-zero", func);
+    let windows = len.checked_sub(width).map_or(0, |extra| extra + 1);
+    assert!(out == windows, "strided::rolling::{}: output of {} elements for {} windows",
+            func, out, windows);
+}
+
+fn sum_into<T, U, F>(src: &Stride<'_, T>, width: usize, dst: MutStride<'_, U>, mut f: F)
+    where T: Copy + Default + Add<Output = T> + Sub<Output = T>, F: FnMut(T) -> U
+{
+    let mut ys = dst.into_iter();
+    let first = match ys.next() {
+        Some(y) => y,
+        None => return,
+    };
+    let mut sum = src.slice_to(width).iter().fold(T::default(), |s, &x| s + x);
+    *first = f(sum);
+    for ((y, &entering), &leaving) in ys.zip(src.slice_from(width)).zip(src) {
+        // leaving first: what remains of the window is no larger
+        // than the window sum, so does not overflow where it does not.
+        sum = sum - leaving + entering;
+        *y = f(sum);
+    }
+}
+
+// `dominates(a, b)` is `true` if `a` is at least as extreme as `b`,
+// so that an earlier `b` can never be the answer while `a` is in the
+// window.
+fn extremum_into<T, F>(src: &Stride<'_, T>, width: usize, dst: MutStride<'_, T>, dominates: F)
+    where T: Copy, F: Fn(&T, &T) -> bool
+{
+    // the indices of the candidates, oldest first, each strictly
+    // less extreme than those before it.
+    let mut queue = VecDeque::with_capacity(width.min(src.len()));
+    let mut ys = dst.into_iter();
+    for (i, x) in src.iter().enumerate() {
+        while queue.back().is_some_and(|&j| dominates(x, &src[j])) {
+            queue.pop_back();
+        }
+        queue.push_back(i);
+        if queue[0] + width <= i {
+            queue.pop_front();
+        }
+        if i + 1 >= width {
+            match ys.next() {
+                Some(y) => *y = src[queue[0]],
+                None => return,
+            }
+        }
+    }
+}
+
+/// Writes the sum of each window of `width` consecutive elements of
+/// `src` into `dst`.
+///
+/// The sums of floating-point elements are updated incrementally, so
+/// they can accumulate rounding error along a long `src`.
+///
+/// # Panic
+///
+/// Panics if `width` is zero, or if `dst` does not have one element
+/// per window.
+#[track_caller]
+pub fn sum<T>(src: &Stride<'_, T>, width: usize, dst: MutStride<'_, T>)
+    where T: Copy + Default + Add<Output = T> + Sub<Output = T>
+{
+    check(src.len(), width, dst.len(), "sum");
+    sum_into(src, width, dst, |s| s)
+}
+
+/// Writes the mean of each window of `width` consecutive elements of
+/// `src` into `dst`.
+///
+/// # Panic
+///
+/// Panics if `width` is zero, or if `dst` does not have one element
+/// per window.
+#[track_caller]
+pub fn mean<T: Float + Default>(src: &Stride<'_, T>, width: usize, dst: MutStride<'_, T>) {
+    check(src.len(), width, dst.len(), "mean");
+    let n = T::from_f64(width as f64);
+    sum_into(src, width, dst, |s| s / n)
+}
+
+/// Writes the smallest element of each window of `width` consecutive
+/// elements of `src` into `dst`, the first if several are equal.
+///
+/// Which element is chosen for a window containing elements not
+/// comparable to themselves, such as NaNs, is unspecified.
+///
+/// # Panic
+///
+/// Panics if `width` is zero, or if `dst` does not have one element
+/// per window.
+#[track_caller]
+pub fn min<T: PartialOrd + Copy>(src: &Stride<'_, T>, width: usize, dst: MutStride<'_, T>) {
+    check(src.len(), width, dst.len(), "min");
+    extremum_into(src, width, dst, |a, b| a < b)
+}
+
+/// Writes the largest element of each window of `width` consecutive
+/// elements of `src` into `dst`, the last if several are equal.
+///
+/// Which element is chosen for a window containing elements not
+/// comparable to themselves, such as NaNs, is unspecified.
+///
+/// # Panic
+///
+/// Panics if `width` is zero, or if `dst` does not have one element
+/// per window.
+#[track_caller]
+pub fn max<T: PartialOrd + Copy>(src: &Stride<'_, T>, width: usize, dst: MutStride<'_, T>) {
+    check(src.len(), width, dst.len(), "max");
+    extremum_into(src, width, dst, |a, b| a >= b)
+}
+
+#[cfg(test)]
+mod tests {
+    use {Stride, MutStride};
+
+    // the reductions computed window by window.
+    fn naive<F: Fn(&[i64]) -> i64>(v: &[i64], width: usize, f: F) -> Vec<i64> {
+        v.windows(width).map(f).collect()
+    }
+
+    #[test]
+    fn against_naive() {
+        let v: Vec<i64> = (0..40).map(|i| (i * 17) % 13 - 6).collect();
+        let (evens, _) = Stride::new(&v).substrides2();
+        let evens_vec: Vec<i64> = evens.iter().cloned().collect();
+        for width in 1..22 {
+            let n = (evens.len() + 1).saturating_sub(width);
+            let mut out = vec![0; n];
+            super::sum(&evens, width, MutStride::new(&mut out));
+            assert_eq!(out, naive(&evens_vec, width, |w| w.iter().sum()));
+            super::min(&evens, width, MutStride::new(&mut out));
+            assert_eq!(out, naive(&evens_vec, width, |w| *w.iter().min().unwrap()));
+            super::max(&evens, width, MutStride::new(&mut out));
+            assert_eq!(out, naive(&evens_vec, width, |w| *w.iter().max().unwrap()));
+        }
+    }
+
+    #[test]
+    fn sum_near_max() {
+        let mut out = [0u8; 2];
+        super::sum(&Stride::new(&[200u8, 50, 200]), 2, MutStride::new(&mut out));
+        assert_eq!(out, [250, 250]);
+        let mut out = [0i8; 2];
+        super::sum(&Stride::new(&[100i8, 27, 100]), 2, MutStride::new(&mut out));
+        assert_eq!(out, [127, 127]);
+        let m = i32::MAX;
+        let mut out = [0; 4];
+        super::sum(&Stride::new(&[m, 0, m, 0, m]), 2, MutStride::new(&mut out));
+        assert_eq!(out, [m; 4]);
+    }
+
+    #[test]
+    fn mean() {
+        let v = [1.0f32, 2.0, 3.0, 6.0];
+        let mut out = [0.0; 2];
+        super::mean(&Stride::new(&v), 3, MutStride::new(&mut out));
+        assert_eq!(out, [2.0, 11.0 / 3.0]);
+        super::mean::<f64>(&Stride::empty(), 3, MutStride::new(&mut []));
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::rolling::max: output of 3 elements for 2 windows")]
+    fn wrong_output() {
+        super::max(&Stride::new(&[1, 2, 3]), 2, MutStride::new(&mut [0; 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::rolling::sum: width must be non-zero")]
+    fn zero_width() {
+        super::sum(&Stride::new(&[1, 2, 3]), 0, MutStride::new(&mut [0; 4]));
+    }
+}
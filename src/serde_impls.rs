@@ -0,0 +1,132 @@
+use std::fmt;
+use serde::de::{self, Deserialize, Deserializer, DeserializeSeed, IgnoredAny, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer, SerializeSeq};
+use {Stride, MutStride};
+
+impl<'a, T: Serialize> Serialize for Stride<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for x in self.iter() {
+            seq.serialize_element(x)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'a, T: Serialize> Serialize for MutStride<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Deserializes a sequence directly into the elements of an existing
+/// `MutStride`, without any temporary allocation.
+///
+/// The sequence must have exactly as many elements as the strided
+/// slice; otherwise an `invalid_length` error is returned. Elements
+/// are written as they are read, so on error a prefix of the slice
+/// may already have been overwritten.
+///
+/// ```rust
+/// # extern crate serde;
+/// # extern crate strided;
+/// use serde::de::DeserializeSeed;
+/// use serde::de::value::{Error, SeqDeserializer};
+/// use strided::{MutStride, DeserializeInto};
+///
+/// # fn main() {
+/// let mut v = [0u8; 6];
+/// {
+///     let (mut left, _) = MutStride::new(&mut v).substrides2_mut();
+///     let input = SeqDeserializer::<_, Error>::new(vec![1u8, 2, 3].into_iter());
+///     DeserializeInto(&mut left).deserialize(input).unwrap();
+/// }
+/// assert_eq!(v, [1, 0, 2, 0, 3, 0]);
+/// # }
+/// ```
+pub struct DeserializeInto<'b, 'a: 'b, T: 'a>(pub &'b mut MutStride<'a, T>);
+
+impl<'de, 'b, 'a, T: Deserialize<'de>> DeserializeSeed<'de> for DeserializeInto<'b, 'a, T> {
+    type Value = ();
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'b, 'a, T: Deserialize<'de>> Visitor<'de> for DeserializeInto<'b, 'a, T> {
+    type Value = ();
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of length {}", self.0.len())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let len = self.0.len();
+        for (i, slot) in self.0.iter_mut().enumerate() {
+            match seq.next_element()? {
+                Some(x) => *slot = x,
+                None => return Err(de::Error::invalid_length(i, &Elements(len))),
+            }
+        }
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            let mut extra = 1;
+            while seq.next_element::<IgnoredAny>()?.is_some() {
+                extra += 1;
+            }
+            return Err(de::Error::invalid_length(len + extra, &Elements(len)))
+        }
+        Ok(())
+    }
+}
+
+struct Elements(usize);
+impl de::Expected for Elements {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} elements", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::de::DeserializeSeed;
+    use serde::de::value::{Error, SeqDeserializer};
+    use serde_test::{assert_ser_tokens, Token};
+    use {Stride, MutStride};
+    use super::DeserializeInto;
+
+    #[test]
+    fn serialize() {
+        let v = &mut [1u16, 2, 3, 4, 5];
+        let (l, _) = Stride::new(v).substrides2();
+        assert_ser_tokens(&l, &[Token::Seq { len: Some(3) },
+                                Token::U16(1), Token::U16(3), Token::U16(5),
+                                Token::SeqEnd]);
+
+        let (_, r) = MutStride::new(v).substrides2_mut();
+        assert_ser_tokens(&r, &[Token::Seq { len: Some(2) },
+                                Token::U16(2), Token::U16(4),
+                                Token::SeqEnd]);
+        assert_ser_tokens(&Stride::<u16>::empty(), &[Token::Seq { len: Some(0) },
+                                                     Token::SeqEnd]);
+    }
+
+    #[test]
+    fn deserialize_into() {
+        fn input(v: Vec<u16>) -> SeqDeserializer<::std::vec::IntoIter<u16>, Error> {
+            SeqDeserializer::new(v.into_iter())
+        }
+
+        let v = &mut [0u16; 5];
+        {
+            let (mut l, mut r) = MutStride::new(v).substrides2_mut();
+            DeserializeInto(&mut l).deserialize(input(vec![1, 3, 5])).unwrap();
+            DeserializeInto(&mut r).deserialize(input(vec![2, 4])).unwrap();
+        }
+        assert_eq!(*v, [1, 2, 3, 4, 5]);
+
+        let mut s = MutStride::new(v);
+        let e = DeserializeInto(&mut s).deserialize(input(vec![10, 20])).unwrap_err();
+        assert_eq!(e.to_string(), "invalid length 2, expected 5 elements");
+        let e = DeserializeInto(&mut s).deserialize(input(vec![0; 7])).unwrap_err();
+        assert_eq!(e.to_string(), "invalid length 7, expected 5 elements");
+    }
+}
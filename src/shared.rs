@@ -0,0 +1,235 @@
+use std::cell::UnsafeCell;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::mem;
+use base::Stride as Base;
+use {Stride, MutStride};
+
+/// A strided slice that can be shared between threads and still be
+/// used for mutation, with the aliasing rules enforced by the user
+/// instead of the borrow checker.
+///
+/// This is a strided slice of `UnsafeCell<T>`, for building schemes
+/// like tiled parallel writers, where each thread is handed a
+/// disjoint part of one `MutStride`, but the parts cannot be
+/// expressed by splitting it. Created by `MutStride::into_shared`,
+/// it can be copied, sliced and subdivided freely; only accessing the
+/// elements is `unsafe`.
+///
+/// # Aliasing
+///
+/// Over the lifetime `'a`, each element may either be read through
+/// any number of shared references (from `get` or `as_stride`), or
+/// be accessed through one mutable reference (from `get_mut` or
+/// `as_mut_stride`), but not both at once, exactly as if the
+/// references had been borrowed from the original `MutStride`. The
+/// raw pointers from `get_ptr` may be used with any other protocol,
+/// such as atomic or volatile accesses.
+///
+/// ```rust
+/// use std::thread;
+/// use strided::MutStride;
+///
+/// let mut v = [0; 12];
+/// let shared = MutStride::new(&mut v).into_shared();
+/// thread::scope(|s| {
+///     for t in 0..3 {
+///         s.spawn(move || {
+///             // each thread writes every third element, which no
+///             // other thread touches.
+///             let mine = unsafe { shared.substride(3, t).as_mut_stride() };
+///             for x in mine { *x = t }
+///         });
+///     }
+/// });
+/// assert_eq!(v[..6], [0, 1, 2, 0, 1, 2]);
+/// ```
+pub struct SharedMutStride<'a, T: 'a> {
+    base: Base<'a, T>,
+    _marker: PhantomData<&'a UnsafeCell<T>>,
+}
+
+impl<'a, T> Copy for SharedMutStride<'a, T> {}
+impl<'a, T> Clone for SharedMutStride<'a, T> {
+    fn clone(&self) -> SharedMutStride<'a, T> { *self }
+}
+
+// the elements can be handed out mutably to, and shared between, any
+// thread, and it is up to the user to do so consistently.
+unsafe impl<'a, T: Send + Sync> Send for SharedMutStride<'a, T> {}
+unsafe impl<'a, T: Send + Sync> Sync for SharedMutStride<'a, T> {}
+
+impl<'a, T> Debug for SharedMutStride<'a, T> {
+    // the elements may be being written elsewhere, so only the layout
+    // is shown.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedMutStride")
+            .field("ptr", &self.as_ptr())
+            .field("len", &self.len())
+            .field("stride", &self.stride())
+            .finish()
+    }
+}
+
+impl<'a, T> From<MutStride<'a, T>> for SharedMutStride<'a, T> {
+    fn from(s: MutStride<'a, T>) -> SharedMutStride<'a, T> {
+        SharedMutStride::new_raw(s.base)
+    }
+}
+
+impl<'a, T> MutStride<'a, T> {
+    /// Converts this strided slice into one that can be copied and
+    /// shared between threads, leaving the aliasing rules to the
+    /// user. See `SharedMutStride`.
+    #[inline]
+    pub fn into_shared(self) -> SharedMutStride<'a, T> {
+        SharedMutStride::new_raw(self.base)
+    }
+}
+
+impl<'a, T> SharedMutStride<'a, T> {
+    fn new_raw(base: Base<'a, T>) -> SharedMutStride<'a, T> {
+        SharedMutStride { base, _marker: PhantomData }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize { self.base.len() }
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.base.is_empty() }
+    /// Returns the number of elements from the start of one element
+    /// to the start of the next.
+    #[inline]
+    pub fn stride(&self) -> usize { self.base.stride() / mem::size_of::<T>() }
+    /// Returns a pointer to the first element.
+    #[inline]
+    pub fn as_ptr(&self) -> *mut T { self.base.as_mut_ptr() }
+    /// Returns a pointer to element `n`, or `None` if it is out of
+    /// bounds. This does not access the element.
+    #[inline]
+    pub fn get_ptr(&self, n: usize) -> Option<*mut T> { self.base.get_ptr(n) }
+
+    /// Returns a shared reference to element `n`, or `None` if it is
+    /// out of bounds.
+    ///
+    /// # Safety
+    ///
+    /// The element must not be mutated for `'a`; see the aliasing
+    /// rules on `SharedMutStride`.
+    #[inline]
+    pub unsafe fn get(&self, n: usize) -> Option<&'a T> { self.base.get(n) }
+    /// Returns a mutable reference to element `n`, or `None` if it is
+    /// out of bounds.
+    ///
+    /// # Safety
+    ///
+    /// The element must not be otherwise accessed for `'a`; see the
+    /// aliasing rules on `SharedMutStride`.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut(&self, n: usize) -> Option<&'a mut T> {
+        let mut base = self.base;
+        base.get_mut(n)
+    }
+
+    /// Returns the elements from indices `from` (inclusive) to `to`
+    /// (exclusive).
+    ///
+    /// # Panic
+    ///
+    /// Panics if `from > to` or if `to > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn slice(&self, from: usize, to: usize) -> SharedMutStride<'a, T> {
+        SharedMutStride::new_raw(self.base.slice(from, to))
+    }
+    /// Returns the elements before and from index `idx`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `idx > self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn split_at(&self, idx: usize) -> (SharedMutStride<'a, T>, SharedMutStride<'a, T>) {
+        let (l, r) = self.base.split_at(idx);
+        (SharedMutStride::new_raw(l), SharedMutStride::new_raw(r))
+    }
+    /// Returns every `n`th element starting at index `i`, like
+    /// `Stride::substride`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= n`.
+    #[inline]
+    #[track_caller]
+    pub fn substride(&self, n: usize, i: usize) -> SharedMutStride<'a, T> {
+        SharedMutStride::new_raw(self.base.substride(n, i))
+    }
+
+    /// Returns a shared strided slice of the elements.
+    ///
+    /// # Safety
+    ///
+    /// The elements must not be mutated for `'a`; see the aliasing
+    /// rules on `SharedMutStride`.
+    #[inline]
+    pub unsafe fn as_stride(self) -> Stride<'a, T> {
+        Stride::new_raw(self.base)
+    }
+    /// Returns a mutable strided slice of the elements.
+    ///
+    /// # Safety
+    ///
+    /// The elements must not be otherwise accessed for `'a`; see the
+    /// aliasing rules on `SharedMutStride`.
+    #[inline]
+    pub unsafe fn as_mut_stride(self) -> MutStride<'a, T> {
+        MutStride::new_raw(self.base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use MutStride;
+
+    #[test]
+    fn tiles() {
+        // four threads each fill a 2x2 tile of a 4x4 grid, stored in
+        // the even elements of `v`.
+        let mut v = [0u16; 32];
+        let grid = MutStride::new(&mut v).substrides2_mut().0.into_shared();
+        assert_eq!((grid.len(), grid.stride()), (16, 2));
+        thread::scope(|s| {
+            for t in 0..4 {
+                s.spawn(move || {
+                    let (r, c) = (t / 2 * 2, t % 2 * 2);
+                    for row in r..r + 2 {
+                        let tile = grid.slice(row * 4 + c, row * 4 + c + 2);
+                        for x in unsafe { tile.as_mut_stride() } { *x = t as u16 + 1 }
+                    }
+                });
+            }
+        });
+        assert_eq!(MutStride::new(&mut v).substrides2_mut().0,
+                   [1, 1, 2, 2, 1, 1, 2, 2, 3, 3, 4, 4, 3, 3, 4, 4]);
+        assert!(v.iter().skip(1).step_by(2).all(|&x| x == 0));
+    }
+
+    #[test]
+    fn access() {
+        let mut v = [1, 2, 3, 4, 5];
+        let s = MutStride::new(&mut v).into_shared();
+        let (l, r) = s.substride(2, 0).split_at(1);
+        unsafe {
+            *r.get_mut(0).unwrap() += 10;
+            assert_eq!(l.get(0), Some(&1));
+            assert_eq!(r.get(2), None);
+            assert_eq!(r.get_ptr(1), Some(s.as_ptr().add(4)));
+            assert_eq!(s.get_ptr(5), None);
+            assert_eq!(s.slice(2, 5).as_stride(), [13, 4, 5]);
+        }
+        assert!(format!("{:?}", l.slice(1, 1)).contains("len: 0"));
+    }
+}
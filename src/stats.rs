@@ -0,0 +1,314 @@
+//! Single-pass statistics of strided vectors, such as each channel of
+//! interleaved sensor data.
+//!
+//! The moments are computed with Welford's algorithm, which does not
+//! suffer the cancellation of the textbook `E[x²] - E[x]²` formula
+//! when the mean is large compared to the spread.
+//!
+//! ```rust
+//! use strided::{Stride, stats};
+//!
+//! // x, y readings from a two-axis sensor.
+//! let readings = [1.0, 10.0, 2.0, 10.5, 3.0, 9.5];
+//! let (x, y) = Stride::new(&readings).substrides2();
+//! assert_eq!(stats::mean(&x), Some(2.0));
+//! assert_eq!(stats::variance(&y), Some(1.0 / 6.0));
+//! assert_eq!(stats::min_max(&y), Some((9.5, 10.5)));
+//! ```
+
+use std::cmp::Ordering;
+use blas1::Float;
+use Stride;
+
+// the number of elements, their mean and the sum of squared
+// differences from it.
+fn welford<T: Float>(x: &Stride<'_, T>) -> (T, T, T) {
+    let (mut n, mut mean, mut m2) = (T::ZERO, T::ZERO, T::ZERO);
+    for &a in x {
+        n = n + T::ONE;
+        let delta = a - mean;
+        mean = mean + delta / n;
+        m2 = m2 + delta * (a - mean);
+    }
+    (n, mean, m2)
+}
+
+/// Returns the mean of the elements of `x`, or `None` if it is empty.
+pub fn mean<T: Float>(x: &Stride<'_, T>) -> Option<T> {
+    if x.is_empty() { return None }
+    Some(welford(x).1)
+}
+
+/// Returns the (population) variance of the elements of `x`, the
+/// mean of the squared differences from their mean, or `None` if it
+/// is empty.
+pub fn variance<T: Float>(x: &Stride<'_, T>) -> Option<T> {
+    if x.is_empty() { return None }
+    let (n, _, m2) = welford(x);
+    Some(m2 / n)
+}
+
+/// Returns the sample variance of the elements of `x`, with Bessel's
+/// correction (dividing by `x.len() - 1`), or `None` if it has fewer
+/// than two elements.
+pub fn sample_variance<T: Float>(x: &Stride<'_, T>) -> Option<T> {
+    if x.len() < 2 { return None }
+    let (n, _, m2) = welford(x);
+    Some(m2 / (n - T::ONE))
+}
+
+/// Returns the (population) standard deviation of the elements of
+/// `x`, the square root of `variance`, or `None` if it is empty.
+pub fn stddev<T: Float>(x: &Stride<'_, T>) -> Option<T> {
+    variance(x).map(T::sqrt)
+}
+
+// the number of pairs of elements, the sum of the products of their
+// differences from the means, and the sums of squared differences
+// from the means.
+#[track_caller]
+fn co_welford<T: Float>(x: &Stride<'_, T>, y: &Stride<'_, T>, func: &str) -> (T, T, T, T) {
+    assert!(x.len() == y.len(), "strided::stats::{}: mismatched lengths {} and {}",
+            func, x.len(), y.len());
+    let (mut n, mut mean_x, mut mean_y) = (T::ZERO, T::ZERO, T::ZERO);
+    let (mut c, mut m2_x, mut m2_y) = (T::ZERO, T::ZERO, T::ZERO);
+    for (&a, &b) in ::multizip((*x, *y)) {
+        n = n + T::ONE;
+        let (dx, dy) = (a - mean_x, b - mean_y);
+        mean_x = mean_x + dx / n;
+        mean_y = mean_y + dy / n;
+        c = c + dx * (b - mean_y);
+        m2_x = m2_x + dx * (a - mean_x);
+        m2_y = m2_y + dy * (b - mean_y);
+    }
+    (n, c, m2_x, m2_y)
+}
+
+/// Returns the (population) covariance of the pairs of elements of
+/// `x` and `y`, or `None` if they are empty.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+#[track_caller]
+pub fn covariance<T: Float>(x: &Stride<'_, T>, y: &Stride<'_, T>) -> Option<T> {
+    let (n, c, _, _) = co_welford(x, y, "covariance");
+    if x.is_empty() { return None }
+    Some(c / n)
+}
+
+/// Returns Pearson's correlation coefficient of the pairs of elements
+/// of `x` and `y`, or `None` if they are empty. This is NaN if either
+/// has zero variance.
+///
+/// # Panic
+///
+/// Panics if `x` and `y` have different lengths.
+///
+/// ```rust
+/// use strided::{Stride, stats};
+///
+/// // records of (time, temperature, pressure).
+/// let records = [0.0, 20.0, 1000.0, 1.0, 21.0, 998.0, 2.0, 22.0, 996.0];
+/// let mut cols = Stride::new(&records).substrides(3);
+/// let (t, temp, pressure) = (cols.next().unwrap(), cols.next().unwrap(), cols.next().unwrap());
+/// assert_eq!(stats::pearson(&temp, &pressure), Some(-1.0));
+/// assert_eq!(stats::covariance(&t, &temp), Some(2.0 / 3.0));
+/// ```
+#[track_caller]
+pub fn pearson<T: Float>(x: &Stride<'_, T>, y: &Stride<'_, T>) -> Option<T> {
+    let (_, c, m2_x, m2_y) = co_welford(x, y, "pearson");
+    if x.is_empty() { return None }
+    Some(c / (m2_x * m2_y).sqrt())
+}
+
+/// Returns the smallest and largest elements of `x`, or `None` if it
+/// is empty.
+///
+/// Elements that are not comparable to themselves, such as NaNs, are
+/// skipped, and `None` is returned if there are no others. Of equal
+/// elements, the first is taken as the smallest and the last as the
+/// largest, like `Iterator::min_by` and `max_by`.
+pub fn min_max<T: PartialOrd + Copy>(x: &Stride<'_, T>) -> Option<(T, T)> {
+    let mut iter = x.iter().filter(|a| a.partial_cmp(a).is_some());
+    let &first = iter.next()?;
+    Some(iter.fold((first, first), |(min, max), &a| {
+        (if a.partial_cmp(&min) == Some(Ordering::Less) { a } else { min },
+         if a.partial_cmp(&max) == Some(Ordering::Less) { max } else { a })
+    }))
+}
+
+/// Counts the elements of `x` in each of `bins.len()` equal-width
+/// bins spanning `range`, adding to the existing counts so that
+/// several vectors can be binned together.
+///
+/// Bin `i` holds the elements `lo + i * width <= a < lo + (i + 1) * width`,
+/// where `(lo, hi) = range` and `width = (hi - lo) / bins.len()`,
+/// except that the last also holds `hi` itself. Elements outside
+/// `range`, and NaNs, are not counted; the number of them is
+/// returned.
+///
+/// # Panic
+///
+/// Panics if `bins` is empty, or if `range` is not a pair of finite
+/// numbers with `lo < hi`.
+///
+/// ```rust
+/// use strided::{Stride, stats};
+///
+/// // (id, latency) records.
+/// let records = [1.0, 0.5, 2.0, 2.5, 3.0, 1.0, 4.0, 9.0];
+/// let (_, latency) = Stride::new(&records).substrides2();
+/// let mut bins = [0; 4];
+/// assert_eq!(stats::histogram(&latency, &mut bins, (0.0, 4.0)), 1);
+/// assert_eq!(bins, [1, 1, 1, 0]);
+/// ```
+#[track_caller]
+pub fn histogram<T: Float>(x: &Stride<'_, T>, bins: &mut [u64], range: (T, T)) -> usize {
+    let (lo, hi) = (range.0.to_f64(), range.1.to_f64());
+    assert!(!bins.is_empty(), "strided::stats::histogram: no bins");
+    assert!(lo < hi && (hi - lo).is_finite(),
+            "strided::stats::histogram: invalid range {} to {}", lo, hi);
+    let n = bins.len();
+    let scale = n as f64 / (hi - lo);
+    let mut outside = 0;
+    for &a in x {
+        let a = a.to_f64();
+        // written so NaNs fail the test.
+        if lo <= a && a <= hi {
+            // the rounding of the scaling can only push `hi` (and
+            // values just below it) to `n`.
+            bins[(((a - lo) * scale) as usize).min(n - 1)] += 1;
+        } else {
+            outside += 1;
+        }
+    }
+    outside
+}
+
+/// Returns the `q`-quantile of the elements of `x`, such as the
+/// median for `q = 0.5`, or `None` if it is empty. NaNs are skipped,
+/// and `None` is returned if there is nothing else.
+///
+/// The quantile interpolates linearly between the two elements
+/// closest to position `q * (n - 1)` in sorted order, like the
+/// default of NumPy's `quantile`. The elements are copied so that
+/// they can be partially sorted in place, which takes linear time.
+///
+/// # Panic
+///
+/// Panics if `q` is not between 0 and 1 inclusive.
+///
+/// ```rust
+/// use strided::{Stride, stats};
+///
+/// let xy = [3.0, 0.0, 1.0, 0.0, 4.0, 0.0, 1.0, 0.0, 5.0, 0.0];
+/// let (x, _) = Stride::new(&xy).substrides2();
+/// assert_eq!(stats::quantile(&x, 0.5), Some(3.0));
+/// assert_eq!(stats::quantile(&x, 0.875), Some(4.5));
+/// ```
+#[track_caller]
+pub fn quantile<T: Float>(x: &Stride<'_, T>, q: f64) -> Option<T> {
+    assert!((0.0..=1.0).contains(&q), "strided::stats::quantile: q = {} is not in [0, 1]", q);
+    let mut v: Vec<T> = x.iter().cloned().filter(|a| !a.is_nan()).collect();
+    if v.is_empty() { return None }
+    let cmp = |a: &T, b: &T| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+    let pos = q * (v.len() - 1) as f64;
+    let i = pos as usize;
+    let (_, &mut below, above) = v.select_nth_unstable_by(i, cmp);
+    let frac = pos - i as f64;
+    if frac == 0.0 { return Some(below) }
+    // the next element in sorted order is the smallest of the rest.
+    let next = above.iter().cloned().fold(None, |m: Option<T>, a| match m {
+        Some(m) if m <= a => Some(m),
+        _ => Some(a),
+    }).unwrap_or(below);
+    Some(below + (next - below) * T::from_f64(frac))
+}
+
+#[cfg(test)]
+mod tests {
+    use Stride;
+    use super::*;
+
+    #[test]
+    fn moments() {
+        // a large offset, which the textbook formula loses the
+        // variance to.
+        let v = [1e9 + 4.0, 0.0, 1e9 + 7.0, 0.0, 1e9 + 13.0, 0.0, 1e9 + 16.0];
+        let (x, zeros) = Stride::new(&v).substrides2();
+        assert_eq!(mean(&x), Some(1e9 + 10.0));
+        assert_eq!(variance(&x), Some(22.5));
+        assert_eq!(sample_variance(&x), Some(30.0));
+        assert_eq!(stddev(&zeros), Some(0.0));
+        assert_eq!(mean::<f32>(&Stride::empty()), None);
+        assert_eq!(variance::<f32>(&Stride::empty()), None);
+        assert_eq!(sample_variance(&x.slice_to(1)), None);
+        assert_eq!(stddev(&Stride::new(&[2.0f32, 4.0])), Some(1.0));
+    }
+
+    #[test]
+    fn co_moments() {
+        let v = [1.0, 2.0, 2.0, 4.0, 3.0, 6.0, 4.0, 8.0];
+        let (x, y) = Stride::new(&v).substrides2();
+        assert_eq!(covariance(&x, &y), Some(2.5));
+        assert_eq!(covariance(&x, &x), variance(&x));
+        assert_eq!(pearson(&x, &y), Some(1.0));
+        let flipped = [4.0, 3.0, 2.0, 1.0];
+        assert_eq!(pearson(&x, &Stride::new(&flipped)), Some(-1.0));
+        assert!(pearson(&x, &Stride::new(&[1.0; 4])).unwrap().is_nan());
+        assert_eq!(pearson::<f32>(&Stride::empty(), &Stride::empty()), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::stats::pearson: mismatched lengths 1 and 2")]
+    fn mismatched() {
+        pearson(&Stride::new(&[1.0]), &Stride::new(&[1.0, 2.0]));
+    }
+
+    #[test]
+    fn min_max() {
+        assert_eq!(super::min_max(&Stride::new(&[3, 1, 4, 1, 5]).substrides2().0), Some((3, 5)));
+        assert_eq!(super::min_max(&Stride::new(&[f64::NAN, 2.0, -1.0, f64::NAN])), Some((-1.0, 2.0)));
+        assert_eq!(super::min_max(&Stride::new(&[f64::NAN])), None);
+        assert_eq!(super::min_max::<u8>(&Stride::empty()), None);
+    }
+
+    #[test]
+    fn histogram() {
+        let v = [0.0, -1.0, 0.25, 0.5, 0.99, 1.0, f64::NAN, 1.5];
+        let mut bins = [0; 2];
+        assert_eq!(super::histogram(&Stride::new(&v), &mut bins, (0.0, 1.0)), 3);
+        assert_eq!(bins, [2, 3]);
+        let (odd, _) = Stride::new(&v[1..]).substrides2();
+        assert_eq!(super::histogram(&odd, &mut bins, (-1.0, 1.0)), 1);
+        assert_eq!(bins, [3, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::stats::histogram: invalid range 1 to 1")]
+    fn histogram_empty_range() {
+        super::histogram(&Stride::new(&[1.0f32]), &mut [0; 3], (1.0, 1.0));
+    }
+
+    #[test]
+    fn quantile() {
+        let v: Vec<f64> = (0..101).map(|i| ((i * 37) % 101) as f64).collect();
+        let (x, _) = Stride::new(&v).substrides2();
+        assert_eq!(super::quantile(&Stride::new(&v), 0.5), Some(50.0));
+        assert_eq!(super::quantile(&Stride::new(&v), 0.255), Some(25.5));
+        assert_eq!(super::quantile(&Stride::new(&v), 1.0), Some(100.0));
+        let mut sorted: Vec<f64> = x.iter().cloned().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(super::quantile(&x, 0.0), Some(sorted[0]));
+        assert_eq!(super::quantile(&x, 0.3), Some(sorted[15]));
+        assert_eq!(super::quantile(&Stride::new(&[f64::NAN, 2.0, 1.0]), 0.5), Some(1.5));
+        assert_eq!(super::quantile(&Stride::new(&[f32::NAN]), 0.5), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::stats::quantile: q = 2 is not in [0, 1]")]
+    fn quantile_out_of_range() {
+        super::quantile(&Stride::new(&[1.0]), 2.0);
+    }
+}
@@ -0,0 +1,109 @@
+//! Short-time Fourier transforms of strided signals, such as one
+//! channel of interleaved audio.
+//!
+//! ```rust
+//! # extern crate num_complex;
+//! # extern crate strided;
+//! # fn main() {
+//! use num_complex::Complex;
+//! use strided::{Stride, MutStride, stft};
+//!
+//! // a constant left channel, and a silent right one.
+//! let stereo = [1.0f32, 0.0].repeat(8);
+//! let (left, _) = Stride::new(&stereo).substrides2();
+//! let n = stft::frame_count(left.len(), 4, 2);
+//! let mut spectrogram = vec![Complex::new(0.0, 0.0); n * 4];
+//! stft::stft(&left, 4, 2, &[1.0; 4], MutStride::new(&mut spectrogram));
+//! // all the energy of each frame is in the DC bin.
+//! let (dc, _, _, _) = Stride::new(&spectrogram).substrides4();
+//! assert_eq!(dc, [Complex::new(4.0, 0.0); 3]);
+//! # }
+//! ```
+
+use num_complex::Complex;
+use rustfft::FftPlanner;
+use {Stride, MutStride};
+
+/// Returns the number of frames `stft` computes for a signal of
+/// `len` samples, the number of complete frames of `frame_len`
+/// samples starting every `hop` samples.
+///
+/// # Panic
+///
+/// Panics if `frame_len` or `hop` is zero.
+#[track_caller]
+pub fn frame_count(len: usize, frame_len: usize, hop: usize) -> usize {
+    assert!(frame_len != 0 && hop != 0, "strided::stft::frame_count: frame_len and hop must be non-zero");
+    len.checked_sub(frame_len).map_or(0, |extra| extra / hop + 1)
+}
+
+/// Computes the spectrum of each frame of `frame_len` samples of
+/// `signal`, starting every `hop` samples, after multiplying it by
+/// `window`, and returns the number of frames.
+///
+/// The spectra are written one after another into `out`, each
+/// `frame_len` bins long, so `out.substrides_mut(frame_len)` gives
+/// the time series of each bin. The frames are those of
+/// `signal.chunks_with_hop(frame_len, hop)`, so `out` must hold
+/// `frame_count(signal.len(), frame_len, hop)` of them.
+///
+/// # Panic
+///
+/// Panics if `frame_len` or `hop` is zero, if `window` does not have
+/// `frame_len` elements, or if `out` does not have room for exactly
+/// every frame.
+#[track_caller]
+pub fn stft(signal: &Stride<'_, f32>, frame_len: usize, hop: usize, window: &[f32],
+            mut out: MutStride<'_, Complex<f32>>) -> usize {
+    let n = frame_count(signal.len(), frame_len, hop);
+    assert!(window.len() == frame_len,
+            "strided::stft::stft: window of {} samples for frames of {}", window.len(), frame_len);
+    assert!(out.len() == n * frame_len,
+            "strided::stft::stft: output of {} bins for {} frames of {}", out.len(), n, frame_len);
+
+    let fft = FftPlanner::new().plan_fft_forward(frame_len);
+    let mut buf = vec![Complex::new(0.0, 0.0); frame_len];
+    let mut scratch = vec![Complex::new(0.0, 0.0); fft.get_inplace_scratch_len()];
+    for (k, frame) in signal.chunks_with_hop(frame_len, hop).enumerate() {
+        for ((b, &x), &w) in buf.iter_mut().zip(frame).zip(window) { *b = Complex::new(x * w, 0.0) }
+        fft.process_with_scratch(&mut buf, &mut scratch);
+        out.reborrow().slice_mut(k * frame_len, (k + 1) * frame_len)
+            .zip_apply(Stride::new(&buf), |y, &b| *y = b);
+    }
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+    use num_complex::Complex;
+    use {Stride, MutStride};
+    use super::*;
+
+    #[test]
+    fn matches_dft() {
+        let signal = (0..40).map(|i| ((i * 7) % 5) as f32 - 2.0).collect::<Vec<_>>();
+        let (_, odd) = Stride::new(&signal).substrides2();
+        let window = [0.5f32, 1.0, 1.0, 0.5, 0.25];
+        let n = frame_count(odd.len(), 5, 3);
+        assert_eq!(n, 6);
+        let mut out = vec![Complex::new(0.0f32, 0.0); n * 5];
+        assert_eq!(stft(&odd, 5, 3, &window, MutStride::new(&mut out)), n);
+        for (k, frame) in odd.chunks_with_hop(5, 3).enumerate() {
+            for f in 0..5 {
+                let dft = frame.iter().zip(&window).enumerate().map(|(t, (&x, &w))| {
+                    Complex::from_polar(x * w, -2.0 * PI * (f * t) as f32 / 5.0)
+                }).sum::<Complex<f32>>();
+                assert!((out[k * 5 + f] - dft).norm() < 1e-4, "frame {}, bin {}", k, f);
+            }
+        }
+        assert_eq!(frame_count(4, 5, 1), 0);
+        assert_eq!(stft(&odd.slice_to(4), 5, 1, &window, MutStride::new(&mut [])), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "strided::stft::stft: output of 3 bins for 2 frames of 2")]
+    fn wrong_output() {
+        stft(&Stride::new(&[1.0; 4]), 2, 2, &[1.0; 2], MutStride::new(&mut [Complex::new(0.0, 0.0); 3][..]));
+    }
+}
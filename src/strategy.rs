@@ -0,0 +1,117 @@
+//! `proptest` strategies for generating strided layouts.
+//!
+//! These produce `StridedBuf`s (or their parts) with random offsets,
+//! strides and lengths, over-representing the degenerate cases that
+//! strided code tends to get wrong: empty views, single elements, and
+//! views whose stride spans the whole buffer.
+
+use std::fmt::Debug;
+use proptest::arbitrary::{any_with, Arbitrary};
+use proptest::collection::vec;
+use proptest::strategy::{BoxedStrategy, Just, Strategy};
+use StridedBuf;
+
+// the number of elements from `offset` to the end of the last element.
+fn extent(offset: usize, stride: usize, len: usize) -> usize {
+    match len {
+        0 => offset,
+        _ => offset + (len - 1) * stride + 1,
+    }
+}
+
+/// Returns a strategy for layouts `(offset, stride, len, buffer_len)`
+/// with `len <= max_len` and `1 <= stride <= max_stride` (except for
+/// the maximal-stride case, which may use a stride up to
+/// `max_len * max_stride`).
+pub fn layout(max_len: usize, max_stride: usize)
+              -> impl Strategy<Value = (usize, usize, usize, usize)> {
+    assert!(max_stride >= 1, "strided::strategy::layout: max_stride must be positive");
+    let general = (1..=max_stride, 0..=max_stride, 0..=max_len)
+        .prop_flat_map(|(stride, offset, len)| (Just(stride), Just(offset), Just(len), 0..stride))
+        .prop_map(|(stride, offset, len, padding)| {
+            (offset, stride, len, extent(offset, stride, len) + padding)
+        });
+    let empty = (1..=max_stride, 0..=max_stride)
+        .prop_map(|(stride, offset)| (offset, stride, 0, offset));
+    let single = (1..=max_stride, 0..=max_stride)
+        .prop_map(|(stride, offset)| (offset, stride, 1, offset + 1));
+    let maximal = (1..=max_len * max_stride + 1)
+        .prop_map(|stride| (0, stride, 2, stride + 1));
+
+    // the degenerate cases only apply when they fit within `max_len`;
+    // a weight of 0 never picks them.
+    prop_oneof![
+        6 => general,
+        1 => empty,
+        (max_len >= 1) as u32 => single,
+        (max_len >= 2) as u32 => maximal,
+    ]
+}
+
+/// Returns a strategy for `StridedBuf`s whose elements are generated
+/// by `element`, with layouts as described by `layout`.
+pub fn strided_buf<S>(element: S, max_len: usize, max_stride: usize)
+                      -> impl Strategy<Value = StridedBuf<S::Value>>
+    where S: Strategy + Clone, S::Value: Debug
+{
+    layout(max_len, max_stride).prop_flat_map(move |(offset, stride, len, total)| {
+        vec(element.clone(), total).prop_map(move |data| {
+            StridedBuf::new(data, offset, stride, len).expect("strategy generated an invalid layout")
+        })
+    })
+}
+
+/// Returns a strategy for `(data, offset, stride, len)` tuples, the
+/// parts of the `StridedBuf`s generated by `strided_buf`.
+pub fn strided_parts<S>(element: S, max_len: usize, max_stride: usize)
+                        -> impl Strategy<Value = (Vec<S::Value>, usize, usize, usize)>
+    where S: Strategy + Clone, S::Value: Debug
+{
+    strided_buf(element, max_len, max_stride).prop_map(StridedBuf::into_parts)
+}
+
+/// Generates buffers with at most 32 elements in the layout, and
+/// strides of at most 8, so `any::<StridedBuf<T>>()` works directly.
+impl<T: Arbitrary + 'static> Arbitrary for StridedBuf<T> {
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<StridedBuf<T>>;
+
+    fn arbitrary_with(args: T::Parameters) -> BoxedStrategy<StridedBuf<T>> {
+        strided_buf(any_with::<T>(args).boxed(), 32, 8).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::arbitrary::any;
+    use StridedBuf;
+    use super::{layout, strided_buf, strided_parts};
+
+    proptest! {
+        #[test]
+        fn valid_layouts(b in strided_buf(any::<u16>(), 10, 4)) {
+            let s = b.as_stride();
+            prop_assert_eq!(s.len(), b.len());
+            for (i, x) in s.iter().enumerate() {
+                prop_assert_eq!(*x, b.data()[b.offset() + i * b.stride()]);
+            }
+        }
+
+        #[test]
+        fn parts((data, offset, stride, len) in strided_parts(0..100u8, 10, 4)) {
+            prop_assert!(StridedBuf::new(data, offset, stride, len).is_some());
+        }
+
+        #[test]
+        fn short_layouts((_, _, len0, _) in layout(0, 4), (_, _, len1, _) in layout(1, 4)) {
+            prop_assert_eq!(len0, 0);
+            prop_assert!(len1 <= 1);
+        }
+
+        #[test]
+        fn any_buf(mut b in any::<StridedBuf<i32>>()) {
+            let n = b.len();
+            prop_assert_eq!(b.as_stride_mut().len(), n);
+        }
+    }
+}
@@ -1,11 +1,13 @@
-use {Stride, MutStride};
-use std::ops::{Deref, DerefMut};
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+use {Stride, MutStride, Substrides, MutSubstrides};
 
 /// Things that can be viewed as a series of equally spaced `T`s in
 /// memory.
 pub trait Strided {
     type Elem;
-    fn as_stride(&self) -> Stride<Self::Elem>;
+    fn as_stride(&self) -> Stride<'_, Self::Elem>;
 
     fn stride(&self) -> usize {
         self.as_stride().stride()
@@ -15,54 +17,204 @@ pub trait Strided {
 /// Things that can be viewed as a series of mutable equally spaced
 /// `T`s in memory.
 pub trait MutStrided : Strided {
-    fn as_stride_mut(&mut self) -> MutStride<<Self as Strided>::Elem>;
+    fn as_stride_mut(&mut self) -> MutStride<'_, <Self as Strided>::Elem>;
 }
 
-// this isn't as general as it could be.
-impl<T, X: Deref<Target=[T]>> Strided for X {
-    type Elem = T;
-    fn as_stride(&self) -> Stride<T> {
-        Stride::new(&**self)
+/// Shorthands for the methods of `Stride` and `MutStride` that
+/// split a view into substrides, available directly on anything
+/// `Strided`, such as slices and `Vec`s.
+///
+/// ```rust
+/// use strided::StridedExt;
+/// let mut v = vec![1, 2, 3, 4, 5, 6];
+/// let (l, r) = v.substrides2();
+/// assert_eq!(l, [1, 3, 5]);
+/// assert_eq!(r, [2, 4, 6]);
+///
+/// for x in v.substride_mut(3, 2) { *x *= 10 }
+/// assert_eq!(v, [1, 2, 30, 4, 5, 60]);
+/// ```
+pub trait StridedExt: Strided {
+    /// Equivalent to `self.as_stride().substrides(n)`.
+    #[track_caller]
+    fn substrides(&self, n: usize) -> Substrides<'_, Self::Elem> {
+        self.as_stride().substrides(n)
     }
-
-    #[inline(always)]
-    fn stride(&self) -> usize {
-        1
+    /// Equivalent to `self.as_stride().substrides2()`.
+    #[track_caller]
+    fn substrides2(&self) -> (Stride<'_, Self::Elem>, Stride<'_, Self::Elem>) {
+        self.as_stride().substrides2()
+    }
+    /// Equivalent to `self.as_stride().substride(n, i)`.
+    #[track_caller]
+    fn substride(&self, n: usize, i: usize) -> Stride<'_, Self::Elem> {
+        self.as_stride().substride(n, i)
     }
+    /// Equivalent to `self.as_stride_mut().substrides_mut(n)`.
+    #[track_caller]
+    fn substrides_mut(&mut self, n: usize) -> MutSubstrides<'_, Self::Elem>
+        where Self: MutStrided
+    {
+        self.as_stride_mut().substrides_mut(n)
+    }
+    /// Equivalent to `self.as_stride_mut().substrides2_mut()`.
+    #[track_caller]
+    fn substrides2_mut(&mut self) -> (MutStride<'_, Self::Elem>, MutStride<'_, Self::Elem>)
+        where Self: MutStrided
+    {
+        self.as_stride_mut().substrides2_mut()
+    }
+    /// Equivalent to `self.as_stride_mut().substride_mut(n, i)`.
+    #[track_caller]
+    fn substride_mut(&mut self, n: usize, i: usize) -> MutStride<'_, Self::Elem>
+        where Self: MutStrided
+    {
+        self.as_stride_mut().substride_mut(n, i)
+    }
+}
+impl<S: Strided + ?Sized> StridedExt for S {}
+
+// References forward to what they point to, so that implementing
+// the traits for a container also covers references to it.
+impl<X: Strided + ?Sized> Strided for &X {
+    type Elem = X::Elem;
+    fn as_stride(&self) -> Stride<'_, X::Elem> { (**self).as_stride() }
+    fn stride(&self) -> usize { (**self).stride() }
 }
-impl<T, X: DerefMut + Deref<Target=[T]>> MutStrided for X {
-    fn as_stride_mut(&mut self) -> MutStride<T> {
-        MutStride::new(&mut **self)
+impl<X: Strided + ?Sized> Strided for &mut X {
+    type Elem = X::Elem;
+    fn as_stride(&self) -> Stride<'_, X::Elem> { (**self).as_stride() }
+    fn stride(&self) -> usize { (**self).stride() }
+}
+impl<X: MutStrided + ?Sized> MutStrided for &mut X {
+    fn as_stride_mut(&mut self) -> MutStride<'_, X::Elem> { (**self).as_stride_mut() }
+}
+
+macro_rules! contiguous_impls {
+    ($($ty: ty),*) => {
+        $(
+            impl<T> Strided for $ty {
+                type Elem = T;
+                fn as_stride(&self) -> Stride<'_, T> { Stride::new(self) }
+                #[inline(always)]
+                fn stride(&self) -> usize { 1 }
+            }
+            impl<T> MutStrided for $ty {
+                fn as_stride_mut(&mut self) -> MutStride<'_, T> { MutStride::new(self) }
+            }
+        )*
     }
 }
+contiguous_impls!([T], Vec<T>, Box<[T]>);
 
-impl<T> Strided for [T] {
+impl<T> Strided for Rc<[T]> {
+    type Elem = T;
+    fn as_stride(&self) -> Stride<'_, T> { Stride::new(self) }
+    #[inline(always)]
+    fn stride(&self) -> usize { 1 }
+}
+impl<T> Strided for Arc<[T]> {
     type Elem = T;
-    fn as_stride(&self) -> Stride<T> { Stride::new(self) }
+    fn as_stride(&self) -> Stride<'_, T> { Stride::new(self) }
     #[inline(always)]
     fn stride(&self) -> usize { 1 }
 }
-impl<T> MutStrided for [T] {
-    fn as_stride_mut(&mut self) -> MutStride<T> { MutStride::new(self) }
+impl<'b, T: Clone> Strided for Cow<'b, [T]> {
+    type Elem = T;
+    fn as_stride(&self) -> Stride<'_, T> { Stride::new(self) }
+    #[inline(always)]
+    fn stride(&self) -> usize { 1 }
+}
+/// A borrowed `Cow` is cloned into an owned one by `as_stride_mut`,
+/// like `Cow::to_mut`.
+impl<'b, T: Clone> MutStrided for Cow<'b, [T]> {
+    fn as_stride_mut(&mut self) -> MutStride<'_, T> { MutStride::new(self.to_mut()) }
+}
+
+// references to arrays are covered by the forwarding impls above.
+impl<T, const N: usize> Strided for [T; N] {
+    type Elem = T;
+    fn as_stride(&self) -> Stride<'_, T> { Stride::new(self) }
+    #[inline(always)]
+    fn stride(&self) -> usize { 1 }
+}
+impl<T, const N: usize> MutStrided for [T; N] {
+    fn as_stride_mut(&mut self) -> MutStride<'_, T> { MutStride::new(self) }
 }
 
 impl<'a,T> Strided for Stride<'a,T> {
     type Elem = T;
-    fn as_stride(&self) -> Stride<T> { *self }
+    fn as_stride(&self) -> Stride<'_, T> { *self }
     fn stride(&self) -> usize { Stride::stride(self) }
 }
 impl<'a,T> Strided for MutStride<'a,T> {
     type Elem = T;
-    fn as_stride(&self) -> Stride<T> { **self }
+    fn as_stride(&self) -> Stride<'_, T> { **self }
     fn stride(&self) -> usize { MutStride::stride(self) }
 }
 impl<'a,T> MutStrided for MutStride<'a,T> {
-    fn as_stride_mut(&mut self) -> MutStride<T> { self.reborrow() }
+    fn as_stride_mut(&mut self) -> MutStride<'_, T> { self.reborrow() }
 }
 
 impl<'a,T, X: AsRef<[T]> + ?Sized> From<&'a X> for Stride<'a,T> {
-    fn from(value: &X) -> Stride<T> { Stride::new(value.as_ref()) }
+    fn from(value: &'a X) -> Stride<'a, T> { Stride::new(value.as_ref()) }
 }
 impl<'a,T, X: AsMut<[T]> + ?Sized> From<&'a mut X> for MutStride<'a,T> {
-    fn from(value: &mut X) -> MutStride<T> { MutStride::new(value.as_mut()) }
+    fn from(value: &'a mut X) -> MutStride<'a, T> { MutStride::new(value.as_mut()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Stride, MutStride};
+    use super::{Strided, MutStrided};
+
+    // a type outside the crate's impls, as a downstream crate might
+    // define, which must not conflict with them.
+    struct EveryOther(Vec<u8>);
+    impl Strided for EveryOther {
+        type Elem = u8;
+        fn as_stride(&self) -> Stride<'_, u8> { Stride::new(&self.0).substrides2().0 }
+    }
+    impl MutStrided for EveryOther {
+        fn as_stride_mut(&mut self) -> MutStride<'_, u8> { MutStride::new(&mut self.0).substrides2_mut().0 }
+    }
+
+    fn sum<S: Strided<Elem = u8>>(s: S) -> u8 { s.as_stride().iter().sum() }
+    fn zero<S: MutStrided<Elem = u8>>(mut s: S) { for x in s.as_stride_mut() { *x = 0 } }
+
+    #[test]
+    fn impls() {
+        let mut v = vec![1u8, 2, 3, 4, 5];
+        assert_eq!(sum(&v[..]), 15);
+        assert_eq!(sum(&v), 15);
+        assert_eq!(sum(&mut v), 15);
+        assert_eq!(sum(v.clone().into_boxed_slice()), 15);
+        assert_eq!(v.stride(), 1);
+
+        let mut e = EveryOther(v.clone());
+        assert_eq!(sum(&e), 9);
+        zero(&mut e);
+        assert_eq!(e.0, [0, 2, 0, 4, 0]);
+        assert_eq!(e.stride(), 2);
+
+        let mut a = [1u8, 2, 3];
+        assert_eq!(sum(a), 6);
+        let r: &[u8; 3] = &a;
+        assert_eq!(sum(r), 6);
+        zero(&mut a);
+        assert_eq!(a, [0; 3]);
+
+        let rc: ::std::rc::Rc<[u8]> = vec![1, 2].into();
+        let arc: ::std::sync::Arc<[u8]> = vec![3, 4].into();
+        assert_eq!(sum(&rc) + sum(arc), 10);
+        let mut cow = ::std::borrow::Cow::Borrowed(&v[..2]);
+        assert_eq!(sum(&cow), 3);
+        zero(&mut cow);
+        assert_eq!(*cow, [0, 0]);
+        assert_eq!(v[..2], [1, 2]);
+
+        zero(&mut v[1..]);
+        zero(MutStride::new(&mut v).slice_to_mut(0));
+        assert_eq!(v, [1, 0, 0, 0, 0]);
+    }
 }
@@ -0,0 +1,85 @@
+use std::mem::MaybeUninit;
+use {MutStride, StrideError};
+
+/// Writing to possibly-uninitialised memory, such as one channel of a
+/// freshly allocated interleaved buffer from `Vec::spare_capacity_mut`
+/// or `Box::new_uninit_slice`.
+///
+/// ```rust
+/// use std::mem::MaybeUninit;
+/// use strided::MutStride;
+///
+/// let mut buf = Box::<[u16]>::new_uninit_slice(6);
+/// let (mut left, mut right) = MutStride::new(&mut buf).substrides2_mut();
+/// left.fill_uninit(0);
+/// assert_eq!(right.write_iter(1..), 3);
+/// let buf = unsafe { buf.assume_init() };
+/// assert_eq!(*buf, [0, 1, 0, 2, 0, 3]);
+/// ```
+impl<'a, T> MutStride<'a, MaybeUninit<T>> {
+    /// Writes `value` into element `i`, and returns a reference to
+    /// it. Any existing value is overwritten without being dropped.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `i >= self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn write(&mut self, i: usize, value: T) -> &mut T {
+        let len = self.len();
+        match self.base.get_mut(i) {
+            Some(x) => x.write(value),
+            None => self.base.fail("write", StrideError::IndexOutOfBounds { index: i, len }),
+        }
+    }
+
+    /// Writes the values of `iter` into successive elements, from the
+    /// start, until one or the other runs out. Returns the number of
+    /// elements written.
+    pub fn write_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) -> usize {
+        self.iter_mut().zip(iter).map(|(x, v)| { x.write(v); }).count()
+    }
+
+    /// Writes a clone of `value` into every element.
+    pub fn fill_uninit(&mut self, value: T) where T: Clone {
+        for x in self.iter_mut() { x.write(value.clone()); }
+    }
+
+    /// Converts this strided slice into one of initialised values.
+    ///
+    /// # Safety
+    ///
+    /// Every element must have been initialised, such as by `write`,
+    /// `write_iter` or `fill_uninit`.
+    #[inline]
+    pub unsafe fn assume_init(self) -> MutStride<'a, T> {
+        MutStride::new_raw(self.base.cast())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::MaybeUninit;
+    use MutStride;
+
+    #[test]
+    fn initialise_channel() {
+        let mut v = Vec::<String>::with_capacity(6);
+        let mut chans = MutStride::new(&mut v.spare_capacity_mut()[..6]).substrides_mut(3);
+        let (mut a, mut b, mut c) = (chans.next().unwrap(), chans.next().unwrap(), chans.next().unwrap());
+        a.fill_uninit("a".to_string());
+        assert_eq!(b.write_iter(vec!["b0".to_string()]), 1);
+        b.write(1, "b1".to_string()).push('!');
+        assert_eq!(c.write_iter((0..).map(|i| i.to_string())), 2);
+        assert_eq!(unsafe { b.assume_init() }, ["b0", "b1!"]);
+        unsafe { v.set_len(6) };
+        assert_eq!(v, ["a", "b0", "0", "a", "b1!", "1"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride.write: index 2 out of bounds for length 2 (stride 2)")]
+    fn write_out_of_bounds() {
+        let mut v = [MaybeUninit::<u8>::uninit(); 4];
+        MutStride::new(&mut v).substrides2_mut().0.write(2, 0);
+    }
+}
@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use MutStride;
+
+/// A cursor that writes successive values into a mutable strided
+/// slice, like pushing onto a `Vec` with a fixed capacity.
+///
+/// ```rust
+/// use strided::{MutStride, StrideWriter};
+///
+/// let mut v = [0; 6];
+/// let (_, right) = MutStride::new(&mut v).substrides2_mut();
+/// let mut w = StrideWriter::new(right);
+/// w.push(1).unwrap();
+/// w.extend(vec![2, 3]);
+/// assert_eq!(w.remaining(), 0);
+/// assert!(w.push(4).is_err());
+/// assert_eq!(v, [0, 1, 0, 2, 0, 3]);
+/// ```
+pub struct StrideWriter<'a, T: 'a> {
+    buf: MutStride<'a, T>,
+    pos: usize,
+}
+
+/// The error returned by `StrideWriter::push` when there is no space
+/// left, holding the value that could not be written.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Full<T>(pub T);
+
+impl<T> Debug for Full<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Full(..)")
+    }
+}
+impl<T> Display for Full<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("strided slice is full")
+    }
+}
+impl<T> Error for Full<T> {}
+
+impl<'a, T: Debug> Debug for StrideWriter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StrideWriter")
+            .field("written", &self.buf.slice_to(self.pos))
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+impl<'a, T> From<MutStride<'a, T>> for StrideWriter<'a, T> {
+    fn from(buf: MutStride<'a, T>) -> StrideWriter<'a, T> {
+        StrideWriter::new(buf)
+    }
+}
+
+impl<'a, T> StrideWriter<'a, T> {
+    /// Creates a writer that starts at the first element of `buf`.
+    #[inline]
+    pub fn new(buf: MutStride<'a, T>) -> StrideWriter<'a, T> {
+        StrideWriter { buf, pos: 0 }
+    }
+
+    /// Writes `value` into the next element, or returns it in `Full`
+    /// if every element has been written.
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<(), Full<T>> {
+        match self.buf.get_mut(self.pos) {
+            Some(x) => {
+                *x = value;
+                self.pos += 1;
+                Ok(())
+            }
+            None => Err(Full(value)),
+        }
+    }
+
+    /// Returns the number of values written so far.
+    #[inline]
+    pub fn written(&self) -> usize { self.pos }
+    /// Returns the number of values that can still be written.
+    #[inline]
+    pub fn remaining(&self) -> usize { self.buf.len() - self.pos }
+    /// Returns `true` if no more values can be written.
+    #[inline]
+    pub fn is_full(&self) -> bool { self.remaining() == 0 }
+
+    /// Returns the elements that have been written.
+    #[inline]
+    pub fn as_written_mut(&mut self) -> MutStride<'_, T> {
+        self.buf.reborrow().slice_to_mut(self.pos)
+    }
+
+    /// Returns the elements that have been written, and those that
+    /// have not.
+    #[inline]
+    pub fn into_parts(self) -> (MutStride<'a, T>, MutStride<'a, T>) {
+        self.buf.split_at_mut(self.pos)
+    }
+}
+
+/// # Panic
+///
+/// `extend` panics if there are more values than remaining elements,
+/// after writing as many as fit.
+impl<'a, T> Extend<T> for StrideWriter<'a, T> {
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            if self.push(x).is_err() {
+                panic!("StrideWriter.extend: {} values written, and more remain", self.pos)
+            }
+        }
+    }
+}
+impl<'b, 'a, T: Copy + 'b> Extend<&'b T> for StrideWriter<'a, T> {
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = &'b T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {MutStride, StrideWriter, Full};
+
+    #[test]
+    fn push() {
+        let v = &mut [0u16; 7];
+        {
+            let (_, r) = MutStride::new(v).substrides2_mut();
+            let mut w = StrideWriter::from(r);
+            assert_eq!((w.written(), w.remaining()), (0, 3));
+            w.push(1).unwrap();
+            w.extend(&[2, 3]);
+            assert!(w.is_full());
+            assert_eq!(w.push(4), Err(Full(4)));
+            eq!(w.as_written_mut(), [1, 2, 3]);
+        }
+        let mut w = StrideWriter::new(MutStride::new(v).substrides2_mut().0);
+        w.extend(10..12);
+        assert!(format!("{:?}", w).contains("written: [10, 11], remaining: 2"));
+        let (mut done, rest) = w.into_parts();
+        done[0] = 9;
+        eq!(rest, [0, 0]);
+        assert_eq!(*v, [9, 1, 11, 2, 0, 3, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "StrideWriter.extend: 2 values written, and more remain")]
+    fn extend_overflow() {
+        let v = &mut [0u8; 2];
+        StrideWriter::new(MutStride::new(v)).extend(0..3);
+    }
+}
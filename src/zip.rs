@@ -0,0 +1,374 @@
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use base::Stride as Base;
+use {Stride, MutStride};
+
+/// Iterates over a shared and a mutable strided slice in lockstep.
+///
+/// This is like `a.iter().zip(b.iter_mut())`, but the lengths are
+/// checked once up-front and then both slices are traversed with a
+/// single counter, which is simpler for the optimiser to handle in
+/// elementwise kernels.
+///
+/// # Panic
+///
+/// Panics if `a.len() != b.len()`.
+///
+/// ```rust
+/// use strided::{Stride, MutStride};
+///
+/// let src = [1, 2, 3, 4, 5, 6];
+/// let mut dst = [0; 3];
+/// let evens = Stride::new(&src).substrides2().0;
+/// for (x, y) in strided::zip(evens, MutStride::new(&mut dst)) {
+///     *y = *x * 10;
+/// }
+/// assert_eq!(dst, [10, 30, 50]);
+/// ```
+#[inline]
+#[track_caller]
+pub fn zip<'a, 'b, A, B>(a: Stride<'a, A>, b: MutStride<'b, B>) -> Zip<'a, 'b, A, B> {
+    assert!(a.len() == b.len(), "strided::zip: mismatched lengths {} and {}", a.len(), b.len());
+    Zip {
+        a: a.base,
+        b: b.base,
+        index: 0,
+        end: a.len(),
+    }
+}
+
+/// An iterator over pairs of elements of a shared and a mutable
+/// strided slice in lockstep.
+///
+/// This is created by the `zip` function.
+pub struct Zip<'a, 'b, A: 'a, B: 'b> {
+    a: Base<'a, A>,
+    b: Base<'b, B>,
+    index: usize,
+    end: usize,
+}
+
+impl<'a, 'b, A, B> Debug for Zip<'a, 'b, A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Zip")
+            .field("len", &(self.end - self.index))
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<'a, 'b, A, B> Iterator for Zip<'a, 'b, A, B> {
+    type Item = (&'a A, &'b mut B);
+    #[inline]
+    fn next(&mut self) -> Option<(&'a A, &'b mut B)> {
+        if self.index < self.end {
+            let i = self.index;
+            self.index += 1;
+            unsafe { Some((self.a.get_unchecked(i), self.b.get_unchecked_mut(i))) }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.index;
+        (n, Some(n))
+    }
+
+    #[inline]
+    fn fold<Acc, F>(mut self, init: Acc, mut f: F) -> Acc
+        where F: FnMut(Acc, (&'a A, &'b mut B)) -> Acc
+    {
+        if let (Some(a), Some(b)) = (self.a.as_slice(), self.b.as_mut_slice()) {
+            let range = self.index..self.end;
+            return a[range.clone()].iter().zip(&mut b[range]).fold(init, f)
+        }
+        let mut acc = init;
+        for i in self.index..self.end {
+            unsafe {
+                acc = f(acc, (self.a.get_unchecked(i), self.b.get_unchecked_mut(i)));
+            }
+        }
+        acc
+    }
+}
+
+impl<'a, 'b, A, B> DoubleEndedIterator for Zip<'a, 'b, A, B> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(&'a A, &'b mut B)> {
+        if self.index < self.end {
+            self.end -= 1;
+            let i = self.end;
+            unsafe { Some((self.a.get_unchecked(i), self.b.get_unchecked_mut(i))) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, 'b, A, B> ExactSizeIterator for Zip<'a, 'b, A, B> {}
+impl<'a, 'b, A, B> FusedIterator for Zip<'a, 'b, A, B> {}
+
+/// Strided slices, and tuples of them, that can be traversed in
+/// lockstep by `multizip` and `strided_zip!`.
+///
+/// # Safety
+///
+/// If `lockstep_len` returns `Some(n)`, then `get_unchecked(i)` must
+/// be valid for every `i < n`, and calls with distinct `i` must not
+/// return aliasing mutable references.
+pub unsafe trait Lockstep {
+    /// The type of one step of the traversal.
+    type Item;
+    /// Returns the length shared by every part of `self`, or `None`
+    /// if the parts have differing lengths.
+    fn lockstep_len(&self) -> Option<usize>;
+    /// Returns the `i`th step of the traversal, without bounds
+    /// checking.
+    ///
+    /// # Safety
+    ///
+    /// `i` must be less than the length, and each `i` must be used
+    /// at most once.
+    unsafe fn get_unchecked(&mut self, i: usize) -> Self::Item;
+}
+
+unsafe impl<'a, T> Lockstep for Stride<'a, T> {
+    type Item = &'a T;
+    #[inline]
+    fn lockstep_len(&self) -> Option<usize> { Some(self.len()) }
+    #[inline]
+    unsafe fn get_unchecked(&mut self, i: usize) -> &'a T { self.base.get_unchecked(i) }
+}
+unsafe impl<'a, T> Lockstep for MutStride<'a, T> {
+    type Item = &'a mut T;
+    #[inline]
+    fn lockstep_len(&self) -> Option<usize> { Some(self.len()) }
+    #[inline]
+    unsafe fn get_unchecked(&mut self, i: usize) -> &'a mut T {
+        self.base.get_unchecked_mut(i)
+    }
+}
+
+macro_rules! lockstep_tuple {
+    ($($name: ident . $idx: tt),+) => {
+        unsafe impl<$($name: Lockstep),+> Lockstep for ($($name,)+) {
+            type Item = ($($name::Item,)+);
+            #[inline]
+            fn lockstep_len(&self) -> Option<usize> {
+                let mut len = None;
+                $(
+                    let l = self.$idx.lockstep_len()?;
+                    if len.is_some_and(|x| x != l) { return None }
+                    len = Some(l);
+                )+
+                len
+            }
+            #[inline]
+            unsafe fn get_unchecked(&mut self, i: usize) -> Self::Item {
+                ($(self.$idx.get_unchecked(i),)+)
+            }
+        }
+    }
+}
+lockstep_tuple!(A.0);
+lockstep_tuple!(A.0, B.1);
+lockstep_tuple!(A.0, B.1, C.2);
+lockstep_tuple!(A.0, B.1, C.2, D.3);
+lockstep_tuple!(A.0, B.1, C.2, D.3, E.4);
+lockstep_tuple!(A.0, B.1, C.2, D.3, E.4, F.5);
+lockstep_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6);
+lockstep_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7);
+
+/// Iterates over a tuple of strided slices in lockstep, yielding
+/// tuples of references.
+///
+/// Like `zip`, the lengths are checked once up-front and a single
+/// counter drives the traversal. The `strided_zip!` macro is a
+/// more convenient way to call this.
+///
+/// # Panic
+///
+/// Panics if the slices do not all have the same length.
+#[inline]
+#[track_caller]
+pub fn multizip<P: Lockstep>(parts: P) -> MultiZip<P> {
+    let end = parts.lockstep_len().expect("strided::multizip: mismatched lengths");
+    MultiZip {
+        parts,
+        index: 0,
+        end,
+    }
+}
+
+/// Zips any number of `Stride`s and `MutStride`s in lockstep.
+///
+/// `strided_zip!(a, b, c)` yields tuples `(x, y, z)` of references
+/// (shared or mutable, according to the type of each slice) to
+/// successive elements of each slice. It is equivalent to
+/// `strided::multizip((a, b, c))`.
+///
+/// # Panic
+///
+/// Panics if the slices do not all have the same length.
+///
+/// ```rust
+/// #[macro_use] extern crate strided;
+/// use strided::{Stride, MutStride};
+///
+/// fn main() {
+///     let rgb = [1, 2, 3, 4, 5, 6];
+///     let mut sums = [0; 2];
+///     let mut channels = Stride::new(&rgb).substrides(3);
+///     let (r, g, b) = (channels.next().unwrap(),
+///                      channels.next().unwrap(),
+///                      channels.next().unwrap());
+///
+///     for (r, g, b, sum) in strided_zip!(r, g, b, MutStride::new(&mut sums)) {
+///         *sum = r + g + b;
+///     }
+///     assert_eq!(sums, [6, 15]);
+/// }
+/// ```
+#[macro_export]
+macro_rules! strided_zip {
+    ($($e: expr),+ $(,)*) => {
+        $crate::multizip(($($e,)+))
+    }
+}
+
+/// An iterator over a tuple of strided slices in lockstep.
+///
+/// This is created by `multizip` and `strided_zip!`.
+pub struct MultiZip<P> {
+    parts: P,
+    index: usize,
+    end: usize,
+}
+
+impl<P> Debug for MultiZip<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiZip")
+            .field("len", &(self.end - self.index))
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<P: Lockstep> Iterator for MultiZip<P> {
+    type Item = P::Item;
+    #[inline]
+    fn next(&mut self) -> Option<P::Item> {
+        if self.index < self.end {
+            let i = self.index;
+            self.index += 1;
+            unsafe { Some(self.parts.get_unchecked(i)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.index;
+        (n, Some(n))
+    }
+
+    #[inline]
+    fn fold<Acc, F>(mut self, init: Acc, mut f: F) -> Acc
+        where F: FnMut(Acc, P::Item) -> Acc
+    {
+        let mut acc = init;
+        for i in self.index..self.end {
+            unsafe {
+                acc = f(acc, self.parts.get_unchecked(i));
+            }
+        }
+        acc
+    }
+}
+
+impl<P: Lockstep> DoubleEndedIterator for MultiZip<P> {
+    #[inline]
+    fn next_back(&mut self) -> Option<P::Item> {
+        if self.index < self.end {
+            self.end -= 1;
+            unsafe { Some(self.parts.get_unchecked(self.end)) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<P: Lockstep> ExactSizeIterator for MultiZip<P> {}
+impl<P: Lockstep> FusedIterator for MultiZip<P> {}
+
+#[cfg(test)]
+mod tests {
+    use {Stride, MutStride};
+    use super::{zip, multizip};
+
+    #[test]
+    fn lockstep() {
+        let a = [1u16, 2, 3, 4, 5, 6];
+        let b = &mut [0u32; 6];
+        {
+            let (evens, odds) = Stride::new(&a).substrides2();
+            let (l, r) = MutStride::new(b).substrides2_mut();
+            let mut it = zip(odds, l);
+            assert_eq!(it.len(), 3);
+            let (x, y) = it.next().unwrap();
+            *y = *x as u32;
+            let (x, y) = it.next_back().unwrap();
+            *y = *x as u32;
+            assert_eq!(it.len(), 1);
+            it.for_each(|(x, y)| *y = *x as u32 + 100);
+
+            for (x, y) in zip(evens, r) {
+                *y = *x as u32 * 10;
+            }
+        }
+        assert_eq!(*b, [2, 10, 104, 30, 6, 50]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched() {
+        let a = [1u16, 2, 3];
+        let b = &mut [0u16; 2];
+        zip(Stride::new(&a), MutStride::new(b));
+    }
+
+    #[test]
+    fn strided_zip() {
+        let a = [1u16, 2, 3, 4, 5, 6];
+        let b = &mut [0u16; 6];
+        {
+            let mut it = Stride::new(&a).substrides(3);
+            let (x, y, z) = (it.next().unwrap(), it.next().unwrap(), it.next().unwrap());
+            let (l, r) = MutStride::new(b).split_at_mut(3);
+            let mut it = strided_zip!(x, y, z, l.slice_to_mut(2), r.slice_from_mut(1));
+            assert_eq!(it.len(), 2);
+            let (x, y, z, l, r) = it.next_back().unwrap();
+            *l = *x + *y + *z;
+            *r = 1;
+            for (x, y, z, l, r) in it {
+                *l = *x + *y + *z;
+                *r = 2;
+            }
+        }
+        assert_eq!(*b, [6, 15, 0, 0, 2, 1]);
+
+        let n = multizip((Stride::new(&a),)).map(|(x,)| *x).sum::<u16>();
+        assert_eq!(n, 21);
+    }
+
+    #[test]
+    #[should_panic]
+    fn strided_zip_mismatched() {
+        let a = [1u16, 2, 3];
+        let b = &mut [0u16; 3];
+        strided_zip!(Stride::new(&a), MutStride::new(b), Stride::new(&a[1..]));
+    }
+}
@@ -0,0 +1,142 @@
+//! `#[derive(StridedFields)]`, re-exported by `strided` behind its
+//! `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Lifetime};
+
+/// Derives views of each field of a slice of structs as a strided
+/// slice.
+///
+/// For a struct `Point` with named fields, this generates a struct
+/// `PointFields<'a>` holding a `strided::Stride<'a, F>` for each field
+/// of type `F`, and `MutPointFields<'a>` holding `strided::MutStride`s,
+/// along with
+///
+/// ```rust,ignore
+/// impl Point {
+///     pub fn fields(slice: &[Point]) -> PointFields<'_>;
+///     pub fn fields_mut(slice: &mut [Point]) -> MutPointFields<'_>;
+/// }
+/// ```
+///
+/// Fields marked `#[strided(skip)]` are left out, which is needed for
+/// zero-sized fields such as `PhantomData`. The views panic on
+/// creation if the size of the struct is not a multiple of the size of
+/// some field, since they could not then be represented as strided
+/// slices.
+#[proc_macro_derive(StridedFields, attributes(strided))]
+pub fn derive_strided_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn skipped(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("strided")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref s) => match s.fields {
+            Fields::Named(ref f) => &f.named,
+            _ => return Err(Error::new_spanned(&input.ident,
+                                               "StridedFields requires a struct with named fields")),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "StridedFields can only be derived for structs")),
+    };
+    let mut kept = Vec::new();
+    for f in fields {
+        if !skipped(&f.attrs)? { kept.push(f) }
+    }
+    let vis_fields = kept.iter().map(|f| &f.vis).collect::<Vec<_>>();
+    let names = kept.iter().map(|f| f.ident.as_ref().unwrap()).collect::<Vec<_>>();
+    let types = kept.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let shared = format_ident!("{}Fields", ident);
+    let mutable = format_ident!("Mut{}Fields", ident);
+
+    // the views borrow the slice for `'__strided`, which every type
+    // parameter must outlive.
+    let lt = Lifetime::new("'__strided", Span::call_site());
+    let mut view_generics = input.generics.clone();
+    view_generics.params.insert(0, parse_quote!(#lt));
+    {
+        let where_clause = view_generics.make_where_clause();
+        for param in &input.generics.params {
+            match *param {
+                GenericParam::Type(ref t) => {
+                    let t = &t.ident;
+                    where_clause.predicates.push(parse_quote!(#t: #lt));
+                }
+                GenericParam::Lifetime(ref l) => {
+                    let l = &l.lifetime;
+                    where_clause.predicates.push(parse_quote!(#l: #lt));
+                }
+                GenericParam::Const(_) => {}
+            }
+        }
+    }
+    let (view_impl, view_ty, view_where) = view_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let view_args = view_ty.as_turbofish();
+    let doc_shared = format!("Strided views of each field of a slice of `{}`s.", ident);
+    let doc_mutable = format!("Mutable strided views of each field of a slice of `{}`s.", ident);
+
+    Ok(quote! {
+        #[doc = #doc_shared]
+        #vis struct #shared #view_impl #view_where {
+            #( #vis_fields #names: ::strided::Stride<#lt, #types>, )*
+            #[doc(hidden)]
+            pub __marker: ::std::marker::PhantomData<&#lt [#ident #ty_generics]>,
+        }
+
+        #[doc = #doc_mutable]
+        #vis struct #mutable #view_impl #view_where {
+            #( #vis_fields #names: ::strided::MutStride<#lt, #types>, )*
+            #[doc(hidden)]
+            pub __marker: ::std::marker::PhantomData<&#lt mut [#ident #ty_generics]>,
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns a strided view of each field of `slice`.
+            #[track_caller]
+            #vis fn fields<#lt>(slice: &#lt [Self]) -> #shared #view_ty #view_where {
+                #shared #view_args {
+                    #( #names: unsafe {
+                        ::strided::Stride::__project_field(
+                            slice, ::std::mem::offset_of!(Self, #names), |s: &Self| &s.#names)
+                    }, )*
+                    __marker: ::std::marker::PhantomData,
+                }
+            }
+
+            /// Returns a mutable strided view of each field of `slice`.
+            #[track_caller]
+            #vis fn fields_mut<#lt>(slice: &#lt mut [Self]) -> #mutable #view_ty #view_where {
+                let (data, len) = (slice.as_mut_ptr(), slice.len());
+                // the fields are disjoint, so their views do not alias.
+                #mutable #view_args {
+                    #( #names: unsafe {
+                        ::strided::MutStride::__project_field_raw(
+                            data, len, ::std::mem::offset_of!(Self, #names), |s: &mut Self| &mut s.#names)
+                    }, )*
+                    __marker: ::std::marker::PhantomData,
+                }
+            }
+        }
+    })
+}
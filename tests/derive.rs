@@ -0,0 +1,61 @@
+#![cfg(feature = "derive")]
+
+#[macro_use]
+extern crate strided;
+
+use std::marker::PhantomData;
+
+#[derive(StridedFields)]
+struct Particle {
+    pos: [f32; 2],
+    mass: f32,
+    id: u32,
+}
+
+#[derive(StridedFields)]
+#[repr(C)]
+struct Tagged<'a, T> {
+    value: T,
+    tag: u16,
+    #[strided(skip)]
+    _marker: PhantomData<&'a ()>,
+}
+
+#[test]
+fn fields() {
+    let mut ps = (0..3).map(|i| Particle { pos: [i as f32, 0.0], mass: 1.0, id: i })
+                       .collect::<Vec<_>>();
+    {
+        let f = Particle::fields(&ps);
+        assert_eq!(f.id, [0, 1, 2]);
+        assert_eq!(f.pos.iter().map(|p| p[0]).collect::<Vec<_>>(), [0.0, 1.0, 2.0]);
+        assert_eq!(f.mass.stride(), 4);
+    }
+
+    let f = Particle::fields_mut(&mut ps);
+    for (m, id) in f.mass.into_iter().zip(f.id.into_stride()) {
+        *m += *id as f32;
+    }
+    assert_eq!(ps[2].mass, 3.0);
+}
+
+#[test]
+fn generic() {
+    let mut v = [Tagged { value: 1u16, tag: 10, _marker: PhantomData },
+                 Tagged { value: 2, tag: 20, _marker: PhantomData }];
+    for x in Tagged::fields_mut(&mut v).value { *x *= 3 }
+    let f = Tagged::fields(&v);
+    assert_eq!(f.value, [3, 6]);
+    assert_eq!(f.tag, [10, 20]);
+}
+
+#[test]
+#[should_panic(expected = "Stride.project_field")]
+fn uneven() {
+    #[derive(StridedFields, Clone, Copy)]
+    struct Uneven {
+        _wide: u32,
+        narrow: [u8; 3],
+    }
+    Uneven::fields(&[Uneven { _wide: 0, narrow: [0; 3] }; 2]);
+}